@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use url::Url;
+
+use hesp_manifest::data::{ManifestData, ManifestDeserialize, ManifestSerialize};
+use hesp_manifest::lint::lint;
+use hesp_manifest::{Error, Manifest, ManifestVersion, Segment, Track};
+
+const USAGE: &str = "\
+Usage: hesp-manifest <command> <file> [--location <url>]
+
+Commands:
+  validate   check that the manifest is valid, listing every problem
+  lint       list suspicious but valid parts of the manifest
+  normalize  print the manifest with track properties moved to their switching set
+  upgrade    print a 1.0.0 or 1.1.0 manifest as a 2.0.0 manifest
+  tracks     list all tracks with their bandwidth and codecs
+  urls       print the initialization and first segment URL of every track
+
+Options:
+  --location <url>  the URL the manifest was fetched from, used to resolve relative URLs
+                    (defaults to the path of <file>)";
+
+#[derive(Clone, Copy)]
+enum Command {
+    Validate,
+    Lint,
+    Normalize,
+    Upgrade,
+    Tracks,
+    Urls,
+}
+
+struct Args {
+    command: Command,
+    file: PathBuf,
+    location: Option<Url>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let command = match args.next().as_deref() {
+            Some("validate") => Command::Validate,
+            Some("lint") => Command::Lint,
+            Some("normalize") => Command::Normalize,
+            Some("upgrade") => Command::Upgrade,
+            Some("tracks") => Command::Tracks,
+            Some("urls") => Command::Urls,
+            Some(command) => return Err(format!("unknown command '{command}'")),
+            None => return Err("missing command".to_owned()),
+        };
+        let mut file = None;
+        let mut location = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--location" => {
+                    let url = args.next().ok_or("--location requires a URL")?;
+                    let url = Url::parse(&url).map_err(|e| format!("invalid location: {e}"))?;
+                    location = Some(url);
+                }
+                _ if file.is_none() => file = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+        Ok(Self {
+            command,
+            file: file.ok_or("missing file")?,
+            location,
+        })
+    }
+
+    fn location(&self) -> Result<Url, String> {
+        if let Some(location) = &self.location {
+            return Ok(location.clone());
+        }
+        fs::canonicalize(&self.file)
+            .ok()
+            .and_then(|path| Url::from_file_path(path).ok())
+            .ok_or_else(|| format!("cannot derive a location from {}", self.file.display()))
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let json = read(&args.file)?;
+    match args.command {
+        Command::Validate => validate(args, &json)?,
+        Command::Lint => {
+            for diagnostic in lint(&load(args, &json)?) {
+                println!("{diagnostic}");
+            }
+        }
+        Command::Normalize => println!("{}", normalize(&json)?),
+        Command::Upgrade => {
+            let data = ManifestSerialize::new(load(args, &json)?, ManifestVersion::V2_0_0)
+                .map_err(describe)?;
+            println!("{}", to_json(&data)?);
+        }
+        Command::Tracks => print_tracks(&load(args, &json)?),
+        Command::Urls => print_urls(&load(args, &json)?),
+    }
+    Ok(())
+}
+
+fn read(file: &Path) -> Result<String, String> {
+    fs::read_to_string(file).map_err(|e| format!("cannot read {}: {e}", file.display()))
+}
+
+fn validate(args: &Args, json: &str) -> Result<(), String> {
+    let report = Manifest::validate_json(args.location()?, json);
+    for issue in report.issues() {
+        println!("{issue}");
+    }
+    if report.is_valid() {
+        println!("{} is valid", args.file.display());
+        Ok(())
+    } else {
+        let errors = report.errors().count();
+        Err(format!("{} has {errors} error(s)", args.file.display()))
+    }
+}
+
+fn load(args: &Args, json: &str) -> Result<Manifest, String> {
+    Manifest::from_json(args.location()?, json).map_err(describe)
+}
+
+/// Describes an error, pointing to the offending JSON path for deserialization errors.
+fn describe(error: Error) -> String {
+    match error {
+        Error::InvalidJson(error) => {
+            format!("invalid manifest at {}: {}", error.path(), error.inner())
+        }
+        error => format!("invalid manifest: {error}"),
+    }
+}
+
+fn normalize(json: &str) -> Result<String, String> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    let mut data: ManifestData =
+        match serde_path_to_error::deserialize(deserializer).map_err(|e| describe(e.into()))? {
+            ManifestDeserialize::V1_0_0(data) => data.try_into().map_err(describe)?,
+            ManifestDeserialize::V1_1_0(data) => data.try_into().map_err(describe)?,
+            ManifestDeserialize::V2_0_0(data) => data,
+        };
+    data.normalize();
+    to_json(&ManifestSerialize::V2_0_0(data))
+}
+
+fn to_json(data: &ManifestSerialize) -> Result<String, String> {
+    serde_json::to_string_pretty(data).map_err(|e| e.to_string())
+}
+
+fn print_tracks(manifest: &Manifest) {
+    let bandwidth = |track: &dyn Track| {
+        track
+            .bandwidth()
+            .or_else(|| track.average_bandwidth())
+            .map_or_else(|| "-".to_owned(), |bandwidth| bandwidth.to_string())
+    };
+    for track in manifest.video_tracks() {
+        println!("{}\t{}\t{}", track.uid(), bandwidth(track), track.codecs());
+    }
+    for track in manifest.audio_tracks() {
+        println!("{}\t{}\t{}", track.uid(), bandwidth(track), track.codecs());
+    }
+    for track in manifest.metadata_tracks() {
+        let codecs = track.codecs().unwrap_or("-");
+        println!("{}\t{}\t{codecs}", track.uid(), bandwidth(track));
+    }
+}
+
+fn print_urls(manifest: &Manifest) {
+    let segment = |track: &dyn Track| {
+        let id = track
+            .segments()
+            .first()
+            .map_or_else(|| track.start_segment_id(), Segment::id);
+        track.continuation_pattern().segment(id)
+    };
+    for presentation in manifest.presentations() {
+        for track in presentation.initializable_tracks() {
+            let init = track
+                .initialization_pattern()
+                .init_id(track.start_sequence_number());
+            println!("{}", track.uid());
+            println!("  init:    {init}");
+            println!("  segment: {}", segment(track));
+        }
+        for track in presentation.metadata_tracks() {
+            println!("{}", track.uid());
+            println!("  segment: {}", segment(track));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn parse_arguments() -> Result<(), String> {
+        let args = parse(&[
+            "urls",
+            "manifest.json",
+            "--location",
+            "https://example.com/",
+        ])?;
+
+        assert!(matches!(args.command, Command::Urls));
+        assert_eq!(args.file, PathBuf::from("manifest.json"));
+        assert_eq!(args.location()?.as_str(), "https://example.com/");
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_arguments() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["convert", "manifest.json"]).is_err());
+        assert!(parse(&["validate"]).is_err());
+        assert!(parse(&["validate", "a.json", "b.json"]).is_err());
+        assert!(parse(&["validate", "a.json", "--location"]).is_err());
+    }
+}
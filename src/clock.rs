@@ -0,0 +1,395 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use url::Url;
+
+use crate::{Error, Result, TimeSource};
+
+/// The difference between the NTP epoch (1900) and the Unix epoch (1970) in seconds.
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+const NTP_ERA: i64 = 1 << 32;
+
+/// The DASH UTC timing schemes a [`TimeSource`] can carry (ISO/IEC 23009-1 section 5.8.5.7).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimingScheme {
+    /// The value is a URL returning an `xs:dateTime`.
+    HttpXsDate,
+    /// The value is a URL returning an ISO 8601 date and time.
+    HttpIso,
+    /// The value is a URL returning a 64-bit NTP timestamp.
+    HttpNtp,
+    /// The value is an NTP server.
+    Ntp,
+    /// The value itself is an `xs:dateTime`.
+    Direct,
+    /// The value is a URL whose `Date` response header holds the time.
+    HttpHead,
+}
+
+impl TimingScheme {
+    #[must_use]
+    pub const fn uri(self) -> &'static str {
+        match self {
+            Self::HttpXsDate => "urn:mpeg:dash:utc:http-xsdate:2014",
+            Self::HttpIso => "urn:mpeg:dash:utc:http-iso:2014",
+            Self::HttpNtp => "urn:mpeg:dash:utc:http-ntp:2014",
+            Self::Ntp => "urn:mpeg:dash:utc:ntp:2014",
+            Self::Direct => "urn:mpeg:dash:utc:direct:2014",
+            Self::HttpHead => "urn:mpeg:dash:utc:http-head:2014",
+        }
+    }
+}
+
+impl FromStr for TimingScheme {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        [
+            Self::HttpXsDate,
+            Self::HttpIso,
+            Self::HttpNtp,
+            Self::Ntp,
+            Self::Direct,
+            Self::HttpHead,
+        ]
+        .into_iter()
+        .find(|scheme| scheme.uri() == input)
+        .ok_or_else(|| Error::UnknownTimingScheme(input.to_owned()))
+    }
+}
+
+impl fmt::Display for TimingScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.uri())
+    }
+}
+
+/// The network requests of the timing schemes, so a [`ClockSync`] can run against any client.
+pub trait TimingNetwork {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Returns the body of a `GET` request.
+    fn get(&mut self, url: &Url) -> std::result::Result<Vec<u8>, Self::Error>;
+
+    /// Returns the `Date` header of the response to a `HEAD` request.
+    fn head(&mut self, url: &Url) -> std::result::Result<Option<String>, Self::Error>;
+
+    /// Sends an NTP packet to `server` over UDP and returns the response packet.
+    fn ntp(
+        &mut self,
+        server: &str,
+        request: [u8; 48],
+    ) -> std::result::Result<[u8; 48], Self::Error>;
+}
+
+/// The result of a clock synchronisation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockMeasurement {
+    offset: TimeDelta,
+    round_trip_time: TimeDelta,
+}
+
+impl ClockMeasurement {
+    /// How far the local clock is behind the time source.
+    #[must_use]
+    pub const fn offset(&self) -> TimeDelta {
+        self.offset
+    }
+
+    /// The duration of the request, which bounds the error of the offset.
+    #[must_use]
+    pub const fn round_trip_time(&self) -> TimeDelta {
+        self.round_trip_time
+    }
+
+    /// Converts a time of the local clock to the time of the time source.
+    #[must_use]
+    pub fn source_time(&self, local: DateTime<Utc>) -> DateTime<Utc> {
+        local + self.offset
+    }
+}
+
+/// Measures the offset of the local clock against the [`TimeSource`] of a live stream.
+pub struct ClockSync<N: TimingNetwork> {
+    network: N,
+    now: fn() -> DateTime<Utc>,
+}
+
+impl<N: TimingNetwork> ClockSync<N> {
+    #[must_use]
+    pub fn new(network: N) -> Self {
+        Self {
+            network,
+            now: Utc::now,
+        }
+    }
+
+    /// Replaces the local clock, which is the system clock by default.
+    #[must_use]
+    pub fn with_local_clock(mut self, now: fn() -> DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Synchronises with `source`, trying the whitespace separated values in order.
+    pub fn measure(&mut self, source: &TimeSource) -> Result<ClockMeasurement> {
+        let scheme = source.scheme.as_str().parse()?;
+        let mut error = None;
+        for value in source.value.split_whitespace() {
+            match self.measure_value(scheme, value) {
+                Ok(measurement) => return Ok(measurement),
+                Err(value_error) => error = Some(value_error),
+            }
+        }
+        Err(error.unwrap_or_else(|| Error::InvalidTimingResponse(source.value.clone())))
+    }
+
+    fn measure_value(&mut self, scheme: TimingScheme, value: &str) -> Result<ClockMeasurement> {
+        let failed = |error| Error::ClockSync(value.to_owned(), Box::new(error));
+        match scheme {
+            TimingScheme::Direct => {
+                let now = (self.now)();
+                Ok(ClockMeasurement {
+                    offset: parse_date_time(value)? - now,
+                    round_trip_time: TimeDelta::zero(),
+                })
+            }
+            TimingScheme::HttpXsDate | TimingScheme::HttpIso => {
+                let url = Url::parse(value)?;
+                self.measure_http(|network| {
+                    let body = network.get(&url).map_err(failed)?;
+                    let body = std::str::from_utf8(&body)
+                        .map_err(|_| Error::InvalidTimingResponse(value.to_owned()))?;
+                    parse_date_time(body)
+                })
+            }
+            TimingScheme::HttpNtp => {
+                let url = Url::parse(value)?;
+                let near = (self.now)();
+                self.measure_http(|network| {
+                    let body = network.get(&url).map_err(failed)?;
+                    let timestamp = <[u8; 8]>::try_from(body.as_slice())
+                        .map_err(|_| Error::InvalidTimingResponse(value.to_owned()))?;
+                    from_ntp(timestamp, near)
+                        .ok_or_else(|| Error::InvalidTimingResponse(value.to_owned()))
+                })
+            }
+            TimingScheme::HttpHead => {
+                let url = Url::parse(value)?;
+                self.measure_http(|network| {
+                    let date = network
+                        .head(&url)
+                        .map_err(failed)?
+                        .ok_or_else(|| Error::InvalidTimingResponse(value.to_owned()))?;
+                    DateTime::parse_from_rfc2822(&date)
+                        .map(|date| date.to_utc())
+                        .map_err(|_| Error::InvalidTimingResponse(date))
+                })
+            }
+            TimingScheme::Ntp => self.measure_ntp(value),
+        }
+    }
+
+    /// Assumes the source time was taken halfway through the request.
+    fn measure_http<F>(&mut self, request: F) -> Result<ClockMeasurement>
+    where
+        F: FnOnce(&mut N) -> Result<DateTime<Utc>>,
+    {
+        let sent = (self.now)();
+        let source_time = request(&mut self.network)?;
+        let round_trip_time = ((self.now)() - sent).max(TimeDelta::zero());
+        Ok(ClockMeasurement {
+            offset: source_time - (sent + round_trip_time / 2),
+            round_trip_time,
+        })
+    }
+
+    /// An SNTP exchange as described in RFC 4330 section 5.
+    fn measure_ntp(&mut self, server: &str) -> Result<ClockMeasurement> {
+        let invalid = || Error::InvalidTimingResponse(server.to_owned());
+        let originate = (self.now)();
+        let mut request = [0; 48];
+        // LI 0, version 4, mode 3 (client)
+        request[0] = 0x23;
+        request[40..].copy_from_slice(&to_ntp(originate));
+        let response = self
+            .network
+            .ntp(server, request)
+            .map_err(|error| Error::ClockSync(server.to_owned(), Box::new(error)))?;
+        let destination = (self.now)();
+        // mode 4 (server), a stratum other than 0 (Kiss-o'-Death) and a transmit timestamp
+        if response[0] & 0x07 != 4
+            || response[1] == 0
+            || response[24..32] != request[40..]
+            || response[40..] == [0; 8]
+        {
+            return Err(invalid());
+        }
+        let timestamp = |range: std::ops::Range<usize>| {
+            let bytes = response[range].try_into().unwrap();
+            from_ntp(bytes, originate).ok_or_else(invalid)
+        };
+        let receive = timestamp(32..40)?;
+        let transmit = timestamp(40..48)?;
+        Ok(ClockMeasurement {
+            offset: ((receive - originate) + (transmit - destination)) / 2,
+            round_trip_time: ((destination - originate) - (transmit - receive))
+                .max(TimeDelta::zero()),
+        })
+    }
+}
+
+/// Parses an `xs:dateTime` or ISO 8601 date and time, which is UTC when it has no time zone.
+fn parse_date_time(input: &str) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+    DateTime::parse_from_rfc3339(input)
+        .map(|date| date.to_utc())
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S%.f").map(|date| date.and_utc())
+        })
+        .map_err(|_| Error::InvalidTimingResponse(input.to_owned()))
+}
+
+fn to_ntp(time: DateTime<Utc>) -> [u8; 8] {
+    let seconds = (time.timestamp() + NTP_UNIX_OFFSET).rem_euclid(NTP_ERA);
+    let fraction = (u64::from(time.timestamp_subsec_nanos()) << 32) / 1_000_000_000;
+    let mut timestamp = [0; 8];
+    timestamp[..4].copy_from_slice(&u32::try_from(seconds).unwrap().to_be_bytes());
+    timestamp[4..].copy_from_slice(&u32::try_from(fraction).unwrap().to_be_bytes());
+    timestamp
+}
+
+/// Converts an NTP timestamp to the time in the NTP era closest to `near`.
+fn from_ntp(timestamp: [u8; 8], near: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let [s0, s1, s2, s3, f0, f1, f2, f3] = timestamp;
+    let seconds = i64::from(u32::from_be_bytes([s0, s1, s2, s3])) - NTP_UNIX_OFFSET;
+    let fraction = u64::from(u32::from_be_bytes([f0, f1, f2, f3]));
+    let era = (near.timestamp() - seconds + NTP_ERA / 2).div_euclid(NTP_ERA);
+    let nanos = u32::try_from((fraction * 1_000_000_000) >> 32).ok()?;
+    DateTime::from_timestamp(seconds + era * NTP_ERA, nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io;
+
+    use super::*;
+
+    /// The stand-in server runs 90 seconds ahead of the local clock.
+    const SERVER_TIME: &str = "2021-03-31T08:01:30Z";
+
+    fn local_clock() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2021-03-31T08:00:00Z")
+            .unwrap()
+            .to_utc()
+    }
+
+    #[derive(Default)]
+    struct StandIn {
+        bodies: HashMap<Url, Vec<u8>>,
+        dates: HashMap<Url, String>,
+    }
+
+    impl TimingNetwork for StandIn {
+        type Error = io::Error;
+
+        fn get(&mut self, url: &Url) -> io::Result<Vec<u8>> {
+            Ok(self.bodies.get(url).ok_or(io::ErrorKind::NotFound)?.clone())
+        }
+
+        fn head(&mut self, url: &Url) -> io::Result<Option<String>> {
+            Ok(self.dates.get(url).cloned())
+        }
+
+        fn ntp(&mut self, server: &str, request: [u8; 48]) -> io::Result<[u8; 48]> {
+            let stratum = match server {
+                "ntp.example.com" => 1,
+                "kod.example.com" => 0,
+                _ => return Err(io::ErrorKind::NotFound.into()),
+            };
+            let time = to_ntp(parse_date_time(SERVER_TIME).unwrap());
+            let mut response = [0; 48];
+            response[0] = 0x24;
+            response[1] = stratum;
+            response[24..32].copy_from_slice(&request[40..]);
+            response[32..40].copy_from_slice(&time);
+            response[40..].copy_from_slice(&time);
+            Ok(response)
+        }
+    }
+
+    fn measure(scheme: TimingScheme, value: &str) -> anyhow::Result<ClockMeasurement> {
+        let mut network = StandIn::default();
+        let server_time = parse_date_time(SERVER_TIME)?;
+        network.bodies.insert(
+            Url::parse("https://time.example.com/xsdate")?,
+            SERVER_TIME.into(),
+        );
+        network.bodies.insert(
+            Url::parse("https://time.example.com/ntp")?,
+            to_ntp(server_time).to_vec(),
+        );
+        network.dates.insert(
+            Url::parse("https://time.example.com/")?,
+            "Wed, 31 Mar 2021 08:01:30 GMT".to_owned(),
+        );
+        let source = TimeSource {
+            scheme: Url::parse(scheme.uri())?,
+            value: value.to_owned(),
+        };
+        let mut sync = ClockSync::new(network).with_local_clock(local_clock);
+        Ok(sync.measure(&source)?)
+    }
+
+    #[test]
+    fn measure_all_schemes() -> anyhow::Result<()> {
+        for (scheme, value) in [
+            (TimingScheme::HttpXsDate, "https://time.example.com/xsdate"),
+            (TimingScheme::HttpIso, "https://time.example.com/xsdate"),
+            (TimingScheme::HttpNtp, "https://time.example.com/ntp"),
+            (TimingScheme::Ntp, "ntp.example.com"),
+            (TimingScheme::Direct, "2021-03-31T08:01:30"),
+            (TimingScheme::HttpHead, "https://time.example.com/"),
+        ] {
+            let measurement = measure(scheme, value)?;
+            assert_eq!(measurement.offset(), TimeDelta::seconds(90), "{scheme}");
+            assert_eq!(measurement.round_trip_time(), TimeDelta::zero(), "{scheme}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fall_back_to_next_value() -> anyhow::Result<()> {
+        let measurement = measure(TimingScheme::Ntp, "unreachable.example.com ntp.example.com")?;
+        assert_eq!(measurement.offset(), TimeDelta::seconds(90));
+
+        let error = measure(TimingScheme::HttpIso, "https://time.example.com/missing");
+        assert!(matches!(
+            error.unwrap_err().downcast::<Error>()?,
+            Error::ClockSync(..)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn reject_kiss_of_death() {
+        let error = measure(TimingScheme::Ntp, "kod.example.com");
+        assert!(matches!(
+            error.unwrap_err().downcast::<Error>(),
+            Ok(Error::InvalidTimingResponse(server)) if server == "kod.example.com"
+        ));
+    }
+
+    #[test]
+    fn ntp_timestamps_round_trip() -> anyhow::Result<()> {
+        let time = parse_date_time("2036-02-07T06:28:16.5Z")?;
+        assert_eq!(from_ntp(to_ntp(time), local_clock()), Some(time));
+        assert!(matches!(
+            "urn:mpeg:dash:utc:sntp:2014".parse::<TimingScheme>(),
+            Err(Error::UnknownTimingScheme(_))
+        ));
+        Ok(())
+    }
+}
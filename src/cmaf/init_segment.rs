@@ -0,0 +1,463 @@
+use std::fmt::Write;
+
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+/// The parts of a CMAF initialization segment (`ftyp` and `moov`) that the manifest describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitSegment {
+    pub major_brand: String,
+    pub compatible_brands: Vec<String>,
+    pub tracks: Vec<InitTrack>,
+    /// The system ids of the `pssh` boxes in the `moov`.
+    pub protection_systems: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitTrack {
+    /// The `track_ID` of the `tkhd`.
+    pub track_id: u32,
+    /// The `handler_type` of the `hdlr`, e.g. `vide` or `soun`.
+    pub handler: String,
+    /// The timescale of the `mdhd`.
+    pub timescale: u32,
+    /// The visual presentation width of the `tkhd`, rounded down.
+    pub width: u32,
+    /// The visual presentation height of the `tkhd`, rounded down.
+    pub height: u32,
+    /// The first sample entry of the `stsd`.
+    pub sample_entry: SampleEntry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleEntry {
+    /// The sample entry type, or the original format of an encrypted (`encv`/`enca`) entry.
+    pub format: String,
+    /// The RFC 6381 codec derived from the codec configuration box, e.g. `avc1.64001f`.
+    ///
+    /// Just the format for codecs without parameters or without a known configuration box.
+    pub codec: String,
+    /// The coded width and height of a visual sample entry.
+    pub resolution: Option<(u16, u16)>,
+    /// The channel count of an audio sample entry, from its configuration when available.
+    pub channels: Option<u16>,
+    /// The sample rate of an audio sample entry.
+    pub sample_rate: Option<u32>,
+    pub encryption: Option<Encryption>,
+}
+
+/// The protection scheme information (`sinf`) of an encrypted sample entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Encryption {
+    /// The `scheme_type` of the `schm`, e.g. `cenc` or `cbcs`.
+    pub scheme_type: String,
+    /// The default key id of the `tenc`, `None` when there is no `tenc`.
+    pub default_kid: Option<Uuid>,
+}
+
+impl InitSegment {
+    /// Parses the `ftyp` and `moov` boxes of an initialization segment.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let top = boxes(data)?;
+        let ftyp = require(&top, "ftyp")?;
+        let moov = require(&top, "moov")?;
+        let mut ftyp = Reader(ftyp);
+        let major_brand = ftyp.fourcc()?;
+        ftyp.take(4)?;
+        let mut compatible_brands = Vec::new();
+        while !ftyp.0.is_empty() {
+            compatible_brands.push(ftyp.fourcc()?);
+        }
+        let moov = boxes(moov)?;
+        let tracks = moov
+            .iter()
+            .filter(|mp4_box| &mp4_box.kind == b"trak")
+            .map(|trak| InitTrack::parse(trak.data))
+            .collect::<Result<_>>()?;
+        let protection_systems = moov
+            .iter()
+            .filter(|mp4_box| &mp4_box.kind == b"pssh")
+            .map(|pssh| {
+                let mut pssh = Reader(pssh.data);
+                pssh.take(4)?;
+                pssh.uuid()
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            major_brand,
+            compatible_brands,
+            tracks,
+            protection_systems,
+        })
+    }
+
+    /// The first track with the given handler type.
+    #[must_use]
+    pub fn track(&self, handler: &str) -> Option<&InitTrack> {
+        self.tracks.iter().find(|track| track.handler == handler)
+    }
+}
+
+impl InitTrack {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let trak = boxes(data)?;
+        let mut tkhd = Reader(require(&trak, "tkhd")?);
+        let version = tkhd.u8()?;
+        tkhd.take(if version == 1 { 19 } else { 11 })?;
+        let track_id = tkhd.u32()?;
+        tkhd.take(if version == 1 { 12 } else { 8 })?;
+        tkhd.take(52)?;
+        let width = tkhd.u32()? >> 16;
+        let height = tkhd.u32()? >> 16;
+
+        let mdia = boxes(require(&trak, "mdia")?)?;
+        let mut mdhd = Reader(require(&mdia, "mdhd")?);
+        let version = mdhd.u8()?;
+        mdhd.take(if version == 1 { 19 } else { 11 })?;
+        let timescale = mdhd.u32()?;
+        let mut hdlr = Reader(require(&mdia, "hdlr")?);
+        hdlr.take(8)?;
+        let handler = hdlr.fourcc()?;
+
+        let minf = boxes(require(&mdia, "minf")?)?;
+        let stbl = boxes(require(&minf, "stbl")?)?;
+        let mut stsd = Reader(require(&stbl, "stsd")?);
+        stsd.take(8)?;
+        let entry = boxes(stsd.0)?
+            .into_iter()
+            .next()
+            .ok_or(Error::InvalidMp4("stsd box has no sample entry"))?;
+
+        Ok(Self {
+            track_id,
+            handler,
+            timescale,
+            width,
+            height,
+            sample_entry: SampleEntry::parse(&entry)?,
+        })
+    }
+}
+
+impl SampleEntry {
+    fn parse(entry: &Mp4Box) -> Result<Self> {
+        let mut reader = Reader(entry.data);
+        reader.take(8)?;
+        let mut resolution = None;
+        let mut channels = None;
+        let mut sample_rate = None;
+        if is_visual(entry.kind) {
+            reader.take(16)?;
+            resolution = Some((reader.u16()?, reader.u16()?));
+            reader.take(50)?;
+        } else if is_audio(entry.kind) {
+            reader.take(8)?;
+            channels = Some(reader.u16()?);
+            reader.take(6)?;
+            sample_rate = Some(reader.u32()? >> 16);
+        }
+        let children = boxes(reader.0)?;
+        let mut format = fourcc(entry.kind);
+        let encryption = match find(&children, "sinf") {
+            Some(sinf) if matches!(&entry.kind, b"encv" | b"enca") => {
+                let sinf = boxes(sinf)?;
+                if let Some(frma) = find(&sinf, "frma") {
+                    format = Reader(frma).fourcc()?;
+                }
+                Some(Encryption::parse(&sinf)?)
+            }
+            _ => None,
+        };
+        let codec = match format.as_str() {
+            "avc1" | "avc3" => find(&children, "avcC").map(avc).transpose()?,
+            "hvc1" | "hev1" => find(&children, "hvcC").map(hevc).transpose()?,
+            "av01" => find(&children, "av1C").map(av1).transpose()?,
+            "mp4a" => match find(&children, "esds") {
+                Some(esds) => {
+                    let (codec, config_channels) = mp4a(esds)?;
+                    channels = config_channels.or(channels);
+                    Some(codec)
+                }
+                None => None,
+            },
+            _ => None,
+        }
+        .map_or_else(
+            || format.clone(),
+            |parameters| format!("{format}.{parameters}"),
+        );
+        Ok(Self {
+            format,
+            codec,
+            resolution,
+            channels,
+            sample_rate,
+            encryption,
+        })
+    }
+}
+
+impl Encryption {
+    fn parse(sinf: &[Mp4Box]) -> Result<Self> {
+        let mut schm = Reader(require(sinf, "schm")?);
+        schm.take(4)?;
+        let scheme_type = schm.fourcc()?;
+        let scheme_information = find(sinf, "schi").map(boxes).transpose()?;
+        let default_kid = match scheme_information
+            .as_deref()
+            .and_then(|schi| find(schi, "tenc"))
+        {
+            Some(tenc) => {
+                let mut tenc = Reader(tenc);
+                tenc.take(8)?;
+                Some(tenc.uuid()?)
+            }
+            None => None,
+        };
+        Ok(Self {
+            scheme_type,
+            default_kid,
+        })
+    }
+}
+
+const fn is_visual(kind: [u8; 4]) -> bool {
+    matches!(
+        &kind,
+        b"avc1" | b"avc3" | b"hvc1" | b"hev1" | b"av01" | b"vp09" | b"encv"
+    )
+}
+
+const fn is_audio(kind: [u8; 4]) -> bool {
+    matches!(
+        &kind,
+        b"mp4a" | b"ac-3" | b"ec-3" | b"Opus" | b"fLaC" | b"enca"
+    )
+}
+
+/// `profile_idc`, `constraint_set_flags` and `level_idc`, e.g. `64001f`.
+fn avc(avcc: &[u8]) -> Result<String> {
+    let mut avcc = Reader(avcc);
+    avcc.take(1)?;
+    let [profile, constraints, level] = avcc.array()?;
+    Ok(format!("{profile:02x}{constraints:02x}{level:02x}"))
+}
+
+/// E.g. `1.6.L93.B0`, see ISO/IEC 14496-15 annex E.
+fn hevc(hvcc: &[u8]) -> Result<String> {
+    let mut hvcc = Reader(hvcc);
+    hvcc.take(1)?;
+    let profile = hvcc.u8()?;
+    let compatibility_flags = hvcc.u32()?.reverse_bits();
+    let constraints: [u8; 6] = hvcc.array()?;
+    let level = hvcc.u8()?;
+    let mut codec = match profile >> 6 {
+        0 => String::new(),
+        space => char::from(b'A' + space - 1).to_string(),
+    };
+    let tier = if profile & 0x20 == 0 { 'L' } else { 'H' };
+    write!(
+        codec,
+        "{}.{compatibility_flags:X}.{tier}{level}",
+        profile & 0x1f
+    )
+    .unwrap();
+    let len = constraints
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |index| index + 1);
+    for byte in &constraints[..len] {
+        write!(codec, ".{byte:X}").unwrap();
+    }
+    Ok(codec)
+}
+
+/// E.g. `0.05M.08`, see the AV1 codec ISO media file format binding.
+fn av1(av1c: &[u8]) -> Result<String> {
+    let mut av1c = Reader(av1c);
+    av1c.take(1)?;
+    let [profile_level, flags] = av1c.array()?;
+    let tier = if flags & 0x80 == 0 { 'M' } else { 'H' };
+    let bit_depth = match flags & 0x60 {
+        0x60 => 12,
+        0x40 => 10,
+        _ => 8,
+    };
+    Ok(format!(
+        "{}.{:02}{tier}.{bit_depth:02}",
+        profile_level >> 5,
+        profile_level & 0x1f
+    ))
+}
+
+/// The object type indication and audio object type, e.g. `40.2`, and the channel
+/// configuration of the `AudioSpecificConfig`, see ISO/IEC 14496-1 and 14496-3.
+fn mp4a(esds: &[u8]) -> Result<(String, Option<u16>)> {
+    let mut esds = Reader(esds);
+    esds.take(4)?;
+    let mut es = esds.descriptor(0x03)?;
+    es.take(2)?;
+    let flags = es.u8()?;
+    if flags & 0x80 != 0 {
+        es.take(2)?;
+    }
+    if flags & 0x40 != 0 {
+        let len = es.u8()?;
+        es.take(len.into())?;
+    }
+    if flags & 0x20 != 0 {
+        es.take(2)?;
+    }
+    let mut config = es.descriptor(0x04)?;
+    let object_type = config.u8()?;
+    config.take(12)?;
+    if object_type != 0x40 {
+        return Ok((format!("{object_type:02x}"), None));
+    }
+    let specific = config.descriptor(0x05)?;
+    let mut bits = Bits::new(specific.0);
+    let mut audio_object_type = bits.read(5)?;
+    if audio_object_type == 31 {
+        audio_object_type = 32 + bits.read(6)?;
+    }
+    if bits.read(4)? == 15 {
+        bits.read(24)?;
+    }
+    let channels = match bits.read(4)? {
+        configuration @ 1..=6 => u16::try_from(configuration).ok(),
+        7 | 12 | 14 => Some(8),
+        11 => Some(7),
+        13 => Some(24),
+        _ => None,
+    };
+    Ok((format!("{object_type:02x}.{audio_object_type}"), channels))
+}
+
+struct Mp4Box<'a> {
+    kind: [u8; 4],
+    data: &'a [u8],
+}
+
+fn boxes(mut data: &[u8]) -> Result<Vec<Mp4Box<'_>>> {
+    let mut boxes = Vec::new();
+    while !data.is_empty() {
+        let mut reader = Reader(data);
+        let size = reader.u32()?;
+        let kind = reader.array()?;
+        let size = match size {
+            0 => data.len(),
+            1 => {
+                usize::try_from(reader.u64()?).map_err(|_| Error::InvalidMp4("box is too large"))?
+            }
+            size => size as usize,
+        };
+        let header = data.len() - reader.0.len();
+        if size < header || size > data.len() {
+            return Err(Error::InvalidMp4("box size does not match the data"));
+        }
+        boxes.push(Mp4Box {
+            kind,
+            data: &data[header..size],
+        });
+        data = &data[size..];
+    }
+    Ok(boxes)
+}
+
+fn find<'a>(boxes: &[Mp4Box<'a>], kind: &str) -> Option<&'a [u8]> {
+    boxes
+        .iter()
+        .find(|mp4_box| mp4_box.kind == kind.as_bytes())
+        .map(|mp4_box| mp4_box.data)
+}
+
+fn require<'a>(boxes: &[Mp4Box<'a>], kind: &'static str) -> Result<&'a [u8]> {
+    find(boxes, kind).ok_or(Error::MissingMp4Box(kind))
+}
+
+fn fourcc(kind: [u8; 4]) -> String {
+    String::from_utf8_lossy(&kind).into_owned()
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    const fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::InvalidMp4("box is truncated"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    fn uuid(&mut self) -> Result<Uuid> {
+        self.array().map(Uuid::from_bytes)
+    }
+
+    fn fourcc(&mut self) -> Result<String> {
+        self.array().map(fourcc)
+    }
+
+    /// Reads an ISO/IEC 14496-1 descriptor with the given tag and returns its contents.
+    fn descriptor(&mut self, tag: u8) -> Result<Self> {
+        if self.u8()? != tag {
+            return Err(Error::InvalidMp4("unexpected esds descriptor"));
+        }
+        let mut len = 0;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            len = (len << 7) | usize::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        self.take(len).map(Reader)
+    }
+}
+
+struct Bits<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Bits<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn read(&mut self, count: usize) -> Result<u32> {
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = self
+                .data
+                .get(self.position / 8)
+                .ok_or(Error::InvalidMp4("AudioSpecificConfig is truncated"))?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
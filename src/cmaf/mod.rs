@@ -0,0 +1,550 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+pub use init_segment::*;
+
+use crate::{
+    AudioTrack, Codec, Codecs, Error, Initialization, Manifest, MediaType, Result, SwitchingSet,
+    SwitchingSetProtection, SwitchingSetProtectionSystem, TrackUid, UnsignedScaledValue,
+    VideoTrack,
+};
+
+mod init_segment;
+
+/// A property on which the manifest and an initialization segment disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    property: &'static str,
+    manifest: String,
+    init_segment: String,
+}
+
+impl Mismatch {
+    const fn new(property: &'static str, manifest: String, init_segment: String) -> Self {
+        Self {
+            property,
+            manifest,
+            init_segment,
+        }
+    }
+
+    #[must_use]
+    pub const fn property(&self) -> &'static str {
+        self.property
+    }
+
+    #[must_use]
+    pub fn manifest(&self) -> &str {
+        &self.manifest
+    }
+
+    #[must_use]
+    pub fn init_segment(&self) -> &str {
+        &self.init_segment
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: the manifest has {} but the initialization segment has {}",
+            self.property, self.manifest, self.init_segment
+        )
+    }
+}
+
+impl InitSegment {
+    /// Compares this initialization segment with the track `track_uid` of `manifest` and its
+    /// switching set protection.
+    pub fn check(&self, manifest: &Manifest, track_uid: &TrackUid) -> Result<Vec<Mismatch>> {
+        let unknown = || Error::UnknownTrack(track_uid.clone());
+        let presentation = manifest
+            .presentation(track_uid.presentation_id())
+            .ok_or_else(unknown)?;
+        let set_id = track_uid.switching_set_id();
+        let id = track_uid.track_id();
+        let mut mismatches = Vec::new();
+        match track_uid.media_type() {
+            MediaType::Video => {
+                let set = presentation
+                    .video_switching_set(set_id)
+                    .ok_or_else(unknown)?;
+                let track = set.track(id).ok_or_else(unknown)?;
+                if let Some(init) = self.handler_track("vide", &mut mismatches) {
+                    check_video(track, init, &mut mismatches);
+                    self.check_protection(set.protection(), init, &mut mismatches);
+                }
+            }
+            MediaType::Audio => {
+                let set = presentation
+                    .audio_switching_set(set_id)
+                    .ok_or_else(unknown)?;
+                let track = set.track(id).ok_or_else(unknown)?;
+                if let Some(init) = self.handler_track("soun", &mut mismatches) {
+                    check_audio(track, set.channels(), init, &mut mismatches);
+                    self.check_protection(set.protection(), init, &mut mismatches);
+                }
+            }
+            MediaType::Metadata => return Err(Error::NoInitSegment(track_uid.clone())),
+        }
+        Ok(mismatches)
+    }
+
+    fn handler_track(&self, handler: &str, mismatches: &mut Vec<Mismatch>) -> Option<&InitTrack> {
+        let track = self.track(handler);
+        if track.is_none() {
+            let handlers = self.tracks.iter().map(|track| &track.handler).join(", ");
+            mismatches.push(Mismatch::new("handler", handler.to_owned(), handlers));
+        }
+        track
+    }
+
+    fn check_protection(
+        &self,
+        protection: Option<&SwitchingSetProtection>,
+        init: &InitTrack,
+        mismatches: &mut Vec<Mismatch>,
+    ) {
+        let encryption = init.sample_entry.encryption.as_ref();
+        let (protection, encryption) = match (protection, encryption) {
+            (None, None) => return,
+            (Some(protection), None) => {
+                mismatches.push(Mismatch::new(
+                    "protection",
+                    protection.scheme().to_string(),
+                    "no encryption".to_owned(),
+                ));
+                return;
+            }
+            (None, Some(encryption)) => {
+                mismatches.push(Mismatch::new(
+                    "protection",
+                    "no protection".to_owned(),
+                    encryption.scheme_type.clone(),
+                ));
+                return;
+            }
+            (Some(protection), Some(encryption)) => (protection, encryption),
+        };
+        let scheme = protection.scheme().to_string();
+        if scheme != encryption.scheme_type {
+            mismatches.push(Mismatch::new(
+                "protection scheme",
+                scheme,
+                encryption.scheme_type.clone(),
+            ));
+        }
+        if encryption.default_kid.is_none() {
+            mismatches.push(Mismatch::new(
+                "tenc",
+                "protection".to_owned(),
+                "no tenc box".to_owned(),
+            ));
+        }
+        let systems = protection.systems();
+        for system in &self.protection_systems {
+            if !systems.iter().any(|listed| listed.scheme_id() == *system) {
+                let listed = systems
+                    .iter()
+                    .map(SwitchingSetProtectionSystem::scheme_id)
+                    .join(", ");
+                mismatches.push(Mismatch::new(
+                    "protection system",
+                    listed,
+                    system.to_string(),
+                ));
+            }
+        }
+    }
+}
+
+fn check_video(track: &VideoTrack, init: &InitTrack, mismatches: &mut Vec<Mismatch>) {
+    check_codecs(track.codecs(), &init.sample_entry, mismatches);
+    let resolution = track.resolution();
+    if let Some((width, height)) = init.sample_entry.resolution {
+        if (u64::from(width), u64::from(height)) != (resolution.width(), resolution.height()) {
+            mismatches.push(Mismatch::new(
+                "resolution",
+                format!("{}x{}", resolution.width(), resolution.height()),
+                format!("{width}x{height}"),
+            ));
+        }
+    }
+    let display_width = resolution
+        .width()
+        .checked_mul(u64::from(resolution.sar_width()))
+        .map(|width| width / u64::from(resolution.sar_height()));
+    if display_width.is_none_or(|width| u64::from(init.width).abs_diff(width) > 1)
+        || u64::from(init.height) != resolution.height()
+    {
+        mismatches.push(Mismatch::new(
+            "display size",
+            display_width.map_or_else(
+                || "an overflowing display width".to_owned(),
+                |width| format!("{width}x{}", resolution.height()),
+            ),
+            format!("{}x{}", init.width, init.height),
+        ));
+    }
+    let frame_rate = UnsignedScaledValue::from(track.frame_rate());
+    let frame_duration =
+        UnsignedScaledValue::from(u64::from(init.timescale)).checked_div(frame_rate);
+    if frame_duration.is_none_or(|duration| duration != UnsignedScaledValue::from(duration.floor()))
+    {
+        mismatches.push(Mismatch::new(
+            "timescale",
+            format!("frame rate {frame_rate}"),
+            format!("timescale {}", init.timescale),
+        ));
+    }
+}
+
+fn check_audio(
+    track: &AudioTrack,
+    set_channels: Option<u64>,
+    init: &InitTrack,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    let entry = &init.sample_entry;
+    check_codecs(track.codecs(), entry, mismatches);
+    let sample_rate = track.sample_rate();
+    if u64::from(init.timescale) != sample_rate {
+        mismatches.push(Mismatch::new(
+            "timescale",
+            format!("sample rate {sample_rate}"),
+            format!("timescale {}", init.timescale),
+        ));
+    }
+    // The sample entry stores the sample rate as 16.16 fixed point.
+    if let Some(entry_rate) = entry
+        .sample_rate
+        .filter(|_| u16::try_from(sample_rate).is_ok())
+    {
+        if u64::from(entry_rate) != sample_rate {
+            mismatches.push(Mismatch::new(
+                "sample rate",
+                sample_rate.to_string(),
+                entry_rate.to_string(),
+            ));
+        }
+    }
+    if let (Some(channels), Some(entry_channels)) =
+        (track.channels().or(set_channels), entry.channels)
+    {
+        if channels != u64::from(entry_channels) {
+            mismatches.push(Mismatch::new(
+                "channels",
+                channels.to_string(),
+                entry_channels.to_string(),
+            ));
+        }
+    }
+}
+
+/// The manifest must list a codec with the sample entry format of the initialization segment
+/// and, when the configuration box is known, the same parameters.
+fn check_codecs(codecs: &Codecs, entry: &SampleEntry, mismatches: &mut Vec<Mismatch>) {
+    let init_codec = entry.codec.parse::<Codec>().ok();
+    let matches = codecs
+        .as_str()
+        .split(',')
+        .zip(codecs.codecs())
+        .filter(|(value, _)| {
+            let format = value.trim().split('.').next().unwrap_or_default();
+            format.eq_ignore_ascii_case(&entry.format)
+        })
+        .any(|(_, codec)| {
+            init_codec
+                .as_ref()
+                .is_none_or(|init| same_codec(codec, init))
+        });
+    if !matches {
+        mismatches.push(Mismatch::new(
+            "codecs",
+            codecs.to_string(),
+            entry.codec.clone(),
+        ));
+    }
+}
+
+fn same_codec(manifest: &Codec, init: &Codec) -> bool {
+    match (manifest, init) {
+        (Codec::Mp4a(manifest), Codec::Mp4a(init)) => {
+            manifest.object_type == init.object_type
+                && manifest
+                    .audio_object_type
+                    .is_none_or(|object_type| init.audio_object_type == Some(object_type))
+        }
+        (Codec::Other(_), Codec::Other(_)) => true,
+        (manifest, init) => manifest == init,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::{Resolution, Scale};
+
+    const MANIFEST: &str = r#"
+        {
+            "manifestVersion": "2.0.0",
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "streamType": "vod",
+            "presentations": [{
+                "id": "0",
+                "timeBounds": {"startTime": 0, "endTime": 10},
+                "video": [{
+                    "id": "video",
+                    "codecs": "avc1.64001f",
+                    "frameRate": {"value": 25},
+                    "protection": {
+                        "type": "cbcs",
+                        "systems": [{
+                            "schemeId": "94ce86fb-07ff-4f43-adb8-93d2fa968ca2",
+                            "uri": "skd://key",
+                            "keyformat": "com.apple.streamingkeydelivery",
+                            "keyformatversions": "1"
+                        }]
+                    },
+                    "continuationPattern": "video/{segmentId}.mp4",
+                    "initializationPattern": "video/init-{initId}.mp4",
+                    "tracks": [{
+                        "id": "720p",
+                        "bandwidth": 3000000,
+                        "resolution": {"width": 1280, "height": 720},
+                        "segmentDuration": {"value": 2},
+                        "segments": [{"id": 0}]
+                    }]
+                }],
+                "audio": [{
+                    "id": "aac",
+                    "codecs": "mp4a.40.5",
+                    "language": "eng",
+                    "continuationPattern": "aac/{segmentId}.mp4",
+                    "initializationPattern": "aac/init-{initId}.mp4",
+                    "tracks": [{
+                        "id": "128k",
+                        "bandwidth": 128000,
+                        "channels": 6,
+                        "sampleRate": 48000,
+                        "segmentDuration": {"value": 2},
+                        "segments": [{"id": 0}]
+                    }]
+                }]
+            }]
+        }"#;
+
+    const WIDEVINE: &str = "edef8ba9-79d6-4ace-a3c8-27dcd51d21ed";
+
+    fn mp4_box(kind: &str, parts: &[&[u8]]) -> Vec<u8> {
+        let data = parts.concat();
+        let size = u32::try_from(data.len() + 8).unwrap();
+        [&size.to_be_bytes(), kind.as_bytes(), &data].concat()
+    }
+
+    fn init_segment(
+        handler: &str,
+        timescale: u32,
+        (width, height): (u32, u32),
+        sample_entry: &[u8],
+        pssh: &[u8],
+    ) -> Vec<u8> {
+        let tkhd = mp4_box(
+            "tkhd",
+            &[
+                &[0; 12],
+                &1u32.to_be_bytes(),
+                &[0; 60],
+                &(width << 16).to_be_bytes(),
+                &(height << 16).to_be_bytes(),
+            ],
+        );
+        let mdhd = mp4_box("mdhd", &[&[0; 12], &timescale.to_be_bytes(), &[0; 8]]);
+        let hdlr = mp4_box("hdlr", &[&[0; 8], handler.as_bytes(), &[0; 13]]);
+        let stsd = mp4_box("stsd", &[&[0; 4], &1u32.to_be_bytes(), sample_entry]);
+        let stbl = mp4_box("stbl", &[&stsd]);
+        let minf = mp4_box("minf", &[&stbl]);
+        let mdia = mp4_box("mdia", &[&mdhd, &hdlr, &minf]);
+        let trak = mp4_box("trak", &[&tkhd, &mdia]);
+        let ftyp = mp4_box("ftyp", &[b"cmfc", &[0; 4], b"iso6", b"cmfc"]);
+        let moov = mp4_box("moov", &[&trak, pssh]);
+        [ftyp, moov].concat()
+    }
+
+    fn video_entry(width: u16, height: u16) -> Vec<u8> {
+        let avcc = mp4_box("avcC", &[&[1, 0x64, 0x00, 0x1f, 0xff, 0xe0, 0]]);
+        let sinf = mp4_box(
+            "sinf",
+            &[
+                &mp4_box("frma", &[b"avc1"]),
+                &mp4_box("schm", &[&[0; 4], b"cbcs", &[0, 1, 0, 0]]),
+                &mp4_box(
+                    "schi",
+                    &[&mp4_box(
+                        "tenc",
+                        &[&[1, 0, 0, 0, 0, 0x19, 1, 0], &[0x42; 16]],
+                    )],
+                ),
+            ],
+        );
+        mp4_box(
+            "encv",
+            &[
+                &[0, 0, 0, 0, 0, 0, 0, 1],
+                &[0; 16],
+                &width.to_be_bytes(),
+                &height.to_be_bytes(),
+                &[0; 50],
+                &avcc,
+                &sinf,
+            ],
+        )
+    }
+
+    /// AAC-LC at 48 kHz with the given channel configuration.
+    fn audio_entry(channels: u8) -> Vec<u8> {
+        let config = [0x11, 0x80 | (channels << 3)];
+        let esds = mp4_box(
+            "esds",
+            &[
+                &[0; 4],
+                &[3, 22, 0, 1, 0],
+                &[4, 17, 0x40, 0x15],
+                &[0; 11],
+                &[5, 2],
+                &config,
+            ],
+        );
+        mp4_box(
+            "mp4a",
+            &[
+                &[0, 0, 0, 0, 0, 0, 0, 1],
+                &[0; 8],
+                &2u16.to_be_bytes(),
+                &16u16.to_be_bytes(),
+                &[0; 4],
+                &(48_000u32 << 16).to_be_bytes(),
+                &esds,
+            ],
+        )
+    }
+
+    fn manifest() -> anyhow::Result<Manifest> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        Ok(Manifest::from_json(location, MANIFEST)?)
+    }
+
+    #[test]
+    fn parse_encrypted_video() -> anyhow::Result<()> {
+        let data = init_segment("vide", 90_000, (1280, 720), &video_entry(1280, 720), &[]);
+
+        let init = InitSegment::parse(&data)?;
+
+        assert_eq!(init.major_brand, "cmfc");
+        assert_eq!(init.compatible_brands, ["iso6", "cmfc"]);
+        let track = init.track("vide").unwrap();
+        assert_eq!(track.track_id, 1);
+        assert_eq!(track.timescale, 90_000);
+        assert_eq!(track.sample_entry.format, "avc1");
+        assert_eq!(track.sample_entry.codec, "avc1.64001f");
+        assert_eq!(track.sample_entry.resolution, Some((1280, 720)));
+        let encryption = track.sample_entry.encryption.as_ref().unwrap();
+        assert_eq!(encryption.scheme_type, "cbcs");
+        assert_eq!(
+            encryption.default_kid,
+            Some(uuid::Uuid::from_bytes([0x42; 16]))
+        );
+
+        let uid = "0/video/video/720p".parse()?;
+        assert_eq!(init.check(&manifest()?, &uid)?, []);
+        Ok(())
+    }
+
+    #[test]
+    fn report_video_mismatches() -> anyhow::Result<()> {
+        let pssh = mp4_box(
+            "pssh",
+            &[
+                &[0; 4],
+                uuid::Uuid::parse_str(WIDEVINE)?.as_bytes(),
+                &[0; 4],
+            ],
+        );
+        let data = init_segment("vide", 1000, (1920, 1080), &video_entry(1920, 1080), &pssh);
+        let uid = "0/video/video/720p".parse()?;
+
+        let mismatches = InitSegment::parse(&data)?.check(&manifest()?, &uid)?;
+
+        let properties: Vec<_> = mismatches.iter().map(Mismatch::property).collect();
+        assert_eq!(
+            properties,
+            ["resolution", "display size", "protection system"]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "resolution: the manifest has 1280x720 but the initialization segment has 1920x1080"
+        );
+        assert_eq!(mismatches[2].init_segment(), WIDEVINE);
+        Ok(())
+    }
+
+    #[test]
+    fn report_overflowing_display_size() -> anyhow::Result<()> {
+        let data = init_segment("vide", 90_000, (1280, 720), &video_entry(1280, 720), &[]);
+        let uid = "0/video/video/720p".parse()?;
+        let mut manifest = manifest()?;
+        let tracks = manifest.presentation_mut("0").unwrap().video_tracks_mut();
+        tracks.last().unwrap().set_resolution(
+            Resolution::new(u64::MAX, 720)
+                .with_sample_aspect_ratio(Scale::try_from(2u64)?, Scale::ONE),
+        );
+
+        let mismatches = InitSegment::parse(&data)?.check(&manifest, &uid)?;
+
+        let properties: Vec<_> = mismatches.iter().map(Mismatch::property).collect();
+        assert_eq!(properties, ["resolution", "display size"]);
+        assert_eq!(mismatches[1].manifest(), "an overflowing display width");
+        Ok(())
+    }
+
+    #[test]
+    fn report_audio_mismatches() -> anyhow::Result<()> {
+        let data = init_segment("soun", 44_100, (0, 0), &audio_entry(2), &[]);
+        let uid = "0/audio/aac/128k".parse()?;
+
+        let mismatches = InitSegment::parse(&data)?.check(&manifest()?, &uid)?;
+
+        let found: Vec<_> = mismatches
+            .iter()
+            .map(|mismatch| (mismatch.property(), mismatch.init_segment()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("codecs", "mp4a.40.2"),
+                ("timescale", "timescale 44100"),
+                ("channels", "2"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_truncated_boxes() {
+        let data = init_segment("soun", 48_000, (0, 0), &audio_entry(2), &[]);
+
+        assert!(matches!(
+            InitSegment::parse(&data[..data.len() - 1]),
+            Err(Error::InvalidMp4(_))
+        ));
+        assert!(matches!(
+            InitSegment::parse(&data[..24]),
+            Err(Error::MissingMp4Box("moov"))
+        ));
+    }
+}
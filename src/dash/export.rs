@@ -0,0 +1,662 @@
+use std::io;
+
+use chrono::{DateTime, FixedOffset, SecondsFormat, TimeDelta, Utc};
+use itertools::Itertools;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::Writer;
+
+use super::time::{format_duration, timescale, to_signed_units, to_units};
+use super::{
+    AUDIO_CHANNEL_CONFIGURATION_SCHEME_ID_URI, CENC_NAMESPACE, LIVE_PROFILE,
+    MP4_PROTECTION_SCHEME_ID_URI, MPD_NAMESPACE,
+};
+use crate::util::Entity;
+use crate::{
+    segment_times, AudioSwitchingSet, Fairplay, Initialization, LiveStream, Manifest,
+    MetadataSwitchingSet, Presentation, ProtectionScheme, ScaledDuration, ScaledValue, Segment,
+    StreamType, SwitchingSet, SwitchingSetProtection, Track, UnsignedScaledValue,
+    VideoSwitchingSet,
+};
+
+type XmlWriter = Writer<Vec<u8>>;
+type Attributes = Vec<(&'static str, String)>;
+
+const DEFAULT_MIN_BUFFER_TIME: ScaledDuration = ScaledDuration::new(2, crate::Scale::ONE);
+
+/// Renders `manifest` as an MPEG-DASH media presentation description.
+///
+/// Presentations become periods, switching sets adaptation sets and tracks representations with
+/// a `$Number$` segment template. VOD manifests result in a static MPD, live manifests in a
+/// dynamic MPD whose `availabilityStartTime` is the wall clock time of media time zero.
+#[must_use]
+pub fn to_mpd(manifest: &Manifest) -> String {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    write_mpd(&mut writer, manifest).expect("writing to a Vec cannot fail");
+    String::from_utf8(writer.into_inner()).expect("quick-xml only writes UTF-8")
+}
+
+fn write_mpd(writer: &mut XmlWriter, manifest: &Manifest) -> io::Result<()> {
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    let mut attributes: Attributes = vec![
+        ("xmlns", MPD_NAMESPACE.to_owned()),
+        ("xmlns:cenc", CENC_NAMESPACE.to_owned()),
+        ("profiles", LIVE_PROFILE.to_owned()),
+    ];
+    let live = match manifest.stream_type() {
+        StreamType::Live(live) => {
+            attributes.extend(live_attributes(manifest, live));
+            Some(live)
+        }
+        StreamType::Vod => {
+            attributes.push(("type", "static".to_owned()));
+            if let Some(duration) = presentation_duration(manifest) {
+                attributes.push(("mediaPresentationDuration", format_duration(duration)));
+            }
+            None
+        }
+    };
+    attributes.push(("minBufferTime", format_duration(min_buffer_time(manifest))));
+    write_element(writer, "MPD", &attributes, |writer| {
+        let presentations = manifest
+            .presentations()
+            .sorted_by_key(|presentation| presentation.time_bounds().start_time());
+        for presentation in presentations {
+            write_period(writer, presentation, live.is_some())?;
+        }
+        if let Some(time_source) = live.and_then(|live| live.time_source.as_ref()) {
+            write_empty(
+                writer,
+                "UTCTiming",
+                &vec![
+                    ("schemeIdUri", time_source.scheme.to_string()),
+                    ("value", time_source.value.clone()),
+                ],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+fn live_attributes(manifest: &Manifest, live: &LiveStream) -> Attributes {
+    let current_time = to_units(live.current_time, 1000);
+    let availability_start_time = i64::try_from(current_time)
+        .ok()
+        .and_then(TimeDelta::try_milliseconds)
+        .and_then(|current_time| manifest.creation_date.checked_sub_signed(current_time))
+        .unwrap_or(manifest.creation_date);
+    vec![
+        ("type", "dynamic".to_owned()),
+        (
+            "availabilityStartTime",
+            format_date_time(availability_start_time),
+        ),
+        ("publishTime", format_date_time(manifest.creation_date)),
+        (
+            "minimumUpdatePeriod",
+            format_duration(manifest.fallback_poll_rate.into()),
+        ),
+        (
+            "timeShiftBufferDepth",
+            format_duration(live.availability_duration.into()),
+        ),
+    ]
+}
+
+fn format_date_time(date_time: DateTime<FixedOffset>) -> String {
+    date_time
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn presentation_duration(manifest: &Manifest) -> Option<UnsignedScaledValue> {
+    let start = manifest
+        .presentations()
+        .map(|presentation| presentation.time_bounds().start_time())
+        .min()??;
+    let end = manifest
+        .presentations()
+        .map(|presentation| presentation.time_bounds().end_time())
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .max()?;
+    end.checked_sub(start)
+}
+
+fn min_buffer_time(manifest: &Manifest) -> UnsignedScaledValue {
+    manifest
+        .presentations()
+        .flat_map(Presentation::tracks)
+        .filter_map(Track::segment_duration)
+        .map(UnsignedScaledValue::from)
+        .max()
+        .unwrap_or_else(|| DEFAULT_MIN_BUFFER_TIME.into())
+}
+
+/// The timing of the period a representation belongs to.
+#[derive(Clone, Copy)]
+struct Period {
+    start: UnsignedScaledValue,
+    end: Option<UnsignedScaledValue>,
+    dynamic: bool,
+}
+
+fn write_period(
+    writer: &mut XmlWriter,
+    presentation: &Presentation,
+    dynamic: bool,
+) -> io::Result<()> {
+    let bounds = presentation.time_bounds();
+    let period = Period {
+        start: bounds.start_time().unwrap_or_default(),
+        end: bounds.end_time(),
+        dynamic,
+    };
+    let mut attributes = vec![
+        ("id", presentation.id().to_owned()),
+        ("start", format_duration(period.start)),
+    ];
+    if let Some(duration) = period.end.and_then(|end| end.checked_sub(period.start)) {
+        attributes.push(("duration", format_duration(duration)));
+    }
+    write_element(writer, "Period", &attributes, |writer| {
+        let mut ids = 1..;
+        for set in presentation.video() {
+            write_video(writer, ids.next().unwrap(), period, set)?;
+        }
+        for set in presentation.audio() {
+            write_audio(writer, ids.next().unwrap(), period, set)?;
+        }
+        for set in presentation.metadata() {
+            write_metadata(writer, ids.next().unwrap(), period, set)?;
+        }
+        Ok(())
+    })
+}
+
+fn adaptation_set_attributes<S: SwitchingSet>(id: u64, set: &S) -> Attributes {
+    let mime_type = set.mime_type();
+    let content_type = mime_type.split('/').next().unwrap_or_default();
+    vec![
+        ("id", id.to_string()),
+        ("contentType", content_type.to_owned()),
+        ("mimeType", mime_type.to_owned()),
+        ("segmentAlignment", "true".to_owned()),
+    ]
+}
+
+fn write_video(
+    writer: &mut XmlWriter,
+    id: u64,
+    period: Period,
+    set: &VideoSwitchingSet,
+) -> io::Result<()> {
+    let mut attributes = adaptation_set_attributes(id, set);
+    attributes.push(("startWithSAP", "1".to_owned()));
+    write_element(writer, "AdaptationSet", &attributes, |writer| {
+        write_protection(writer, set.protection.as_ref())?;
+        write_label(writer, set.label.as_deref())?;
+        for track in set.tracks() {
+            let resolution = &track.resolution;
+            let mut attributes = vec![
+                ("id", track.id().to_owned()),
+                ("bandwidth", track.bandwidth.to_string()),
+                ("codecs", track.codecs.to_string()),
+                ("width", resolution.width().to_string()),
+                ("height", resolution.height().to_string()),
+                (
+                    "frameRate",
+                    UnsignedScaledValue::from(track.frame_rate).to_string(),
+                ),
+            ];
+            if !resolution.sar_width().is_one() || !resolution.sar_height().is_one() {
+                attributes.push((
+                    "sar",
+                    format!("{}:{}", resolution.sar_width(), resolution.sar_height()),
+                ));
+            }
+            write_element(writer, "Representation", &attributes, |writer| {
+                write_segment_template(
+                    writer,
+                    period,
+                    track,
+                    track.media_time_offset,
+                    Some(initialization(track)),
+                )
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_audio(
+    writer: &mut XmlWriter,
+    id: u64,
+    period: Period,
+    set: &AudioSwitchingSet,
+) -> io::Result<()> {
+    let mut attributes = adaptation_set_attributes(id, set);
+    attributes.push(("lang", set.language.code()));
+    attributes.push(("startWithSAP", "1".to_owned()));
+    write_element(writer, "AdaptationSet", &attributes, |writer| {
+        write_channels(writer, set.channels)?;
+        write_protection(writer, set.protection.as_ref())?;
+        write_label(writer, set.label.as_deref())?;
+        for track in set.tracks() {
+            let attributes = vec![
+                ("id", track.id().to_owned()),
+                ("bandwidth", track.bandwidth.to_string()),
+                ("codecs", track.codecs.to_string()),
+                ("audioSamplingRate", track.sample_rate.to_string()),
+            ];
+            write_element(writer, "Representation", &attributes, |writer| {
+                if track.channels != set.channels {
+                    write_channels(writer, track.channels)?;
+                }
+                write_segment_template(
+                    writer,
+                    period,
+                    track,
+                    track.media_time_offset,
+                    Some(initialization(track)),
+                )
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_metadata(
+    writer: &mut XmlWriter,
+    id: u64,
+    period: Period,
+    set: &MetadataSwitchingSet,
+) -> io::Result<()> {
+    let mut attributes = adaptation_set_attributes(id, set);
+    if let Some(language) = set.language {
+        attributes.push(("lang", language.code()));
+    }
+    write_element(writer, "AdaptationSet", &attributes, |writer| {
+        write_empty(
+            writer,
+            "InbandEventStream",
+            &vec![("schemeIdUri", set.scheme_id.clone())],
+        )?;
+        write_label(writer, set.label.as_deref())?;
+        for track in set.tracks() {
+            let bandwidth = track.bandwidth.or(track.average_bandwidth).unwrap_or(0);
+            let mut attributes = vec![
+                ("id", track.id().to_owned()),
+                ("bandwidth", bandwidth.to_string()),
+            ];
+            if let Some(codecs) = &track.codecs {
+                attributes.push(("codecs", codecs.clone()));
+            }
+            write_element(writer, "Representation", &attributes, |writer| {
+                write_segment_template(writer, period, track, track.media_time_offset, None)
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_channels(writer: &mut XmlWriter, channels: Option<u64>) -> io::Result<()> {
+    let Some(channels) = channels else {
+        return Ok(());
+    };
+    write_empty(
+        writer,
+        "AudioChannelConfiguration",
+        &vec![
+            (
+                "schemeIdUri",
+                AUDIO_CHANNEL_CONFIGURATION_SCHEME_ID_URI.to_owned(),
+            ),
+            ("value", channels.to_string()),
+        ],
+    )
+}
+
+fn write_protection(
+    writer: &mut XmlWriter,
+    protection: Option<&SwitchingSetProtection>,
+) -> io::Result<()> {
+    let Some(protection) = protection else {
+        return Ok(());
+    };
+    let scheme = match protection.scheme() {
+        ProtectionScheme::Cenc => "cenc",
+        ProtectionScheme::Cbcs => "cbcs",
+    };
+    write_empty(
+        writer,
+        "ContentProtection",
+        &vec![
+            ("schemeIdUri", MP4_PROTECTION_SCHEME_ID_URI.to_owned()),
+            ("value", scheme.to_owned()),
+        ],
+    )?;
+    // Fairplay is only signalled through HLS
+    for system in protection
+        .systems()
+        .iter()
+        .filter(|system| system.scheme_id() != Fairplay::SCHEME_ID)
+    {
+        let attributes = vec![("schemeIdUri", system.scheme_id().urn().to_string())];
+        match system.pssh() {
+            Some(pssh) => write_element(writer, "ContentProtection", &attributes, |writer| {
+                writer
+                    .create_element("cenc:pssh")
+                    .write_text_content(BytesText::new(pssh))?;
+                Ok(())
+            })?,
+            None => write_empty(writer, "ContentProtection", &attributes)?,
+        }
+    }
+    Ok(())
+}
+
+fn write_label(writer: &mut XmlWriter, label: Option<&str>) -> io::Result<()> {
+    if let Some(label) = label {
+        writer
+            .create_element("Label")
+            .write_text_content(BytesText::new(label))?;
+    }
+    Ok(())
+}
+
+fn initialization<T: Initialization>(track: &T) -> String {
+    let pattern = track.initialization_pattern().clone().into_full_pattern();
+    escape_template(&pattern).replace("{initId}", &track.start_sequence_number().to_string())
+}
+
+/// Escapes the `$` characters that DASH reserves for template identifiers.
+fn escape_template(pattern: &str) -> String {
+    pattern.replace('$', "$$")
+}
+
+fn write_segment_template(
+    writer: &mut XmlWriter,
+    period: Period,
+    track: &dyn Track,
+    media_time_offset: ScaledValue,
+    initialization: Option<String>,
+) -> io::Result<()> {
+    let timescale = timescale(
+        track
+            .segments()
+            .iter()
+            .filter_map(|segment| segment.time_bounds().map(|bounds| bounds.scale()))
+            .chain(
+                track
+                    .segment_duration()
+                    .map(|d| UnsignedScaledValue::from(d).scale),
+            )
+            .chain([period.start.scale, media_time_offset.scale]),
+    );
+    let offset = to_signed_units(media_time_offset, timescale);
+    let start_number = track
+        .segments()
+        .first()
+        .map_or_else(|| track.start_segment_id(), Segment::id);
+    let media = escape_template(&track.continuation_pattern().clone().into_full_pattern())
+        .replace("{segmentId}", "$Number$");
+    let mut attributes = vec![
+        ("timescale", timescale.to_string()),
+        (
+            "presentationTimeOffset",
+            media_units(period.start, timescale, offset).to_string(),
+        ),
+        ("startNumber", start_number.to_string()),
+        ("media", media),
+    ];
+    if let Some(initialization) = initialization {
+        attributes.push(("initialization", initialization));
+    }
+    let timeline = timeline(period, track, timescale, offset);
+    write_element(writer, "SegmentTemplate", &attributes, |writer| {
+        write_element(writer, "SegmentTimeline", &Vec::new(), |writer| {
+            let mut expected_start = None;
+            for entry in &timeline {
+                let mut attributes = Vec::new();
+                if expected_start != Some(entry.start) {
+                    attributes.push(("t", entry.start.to_string()));
+                }
+                attributes.push(("d", entry.duration.to_string()));
+                if entry.repeat != 0 {
+                    attributes.push(("r", entry.repeat.to_string()));
+                }
+                write_empty(writer, "S", &attributes)?;
+                expected_start = u64::try_from(entry.repeat + 1)
+                    .ok()
+                    .map(|count| entry.start + entry.duration * count);
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Converts a presentation time to the media time of a track in units of `timescale`.
+fn media_units(time: UnsignedScaledValue, timescale: u64, offset: i128) -> u64 {
+    let units = i128::from(to_units(time, timescale)) + offset;
+    u64::try_from(units.max(0)).unwrap_or(u64::MAX)
+}
+
+/// An `S` element of a `SegmentTimeline`; a `repeat` of -1 repeats until the end of the period.
+struct TimelineEntry {
+    start: u64,
+    duration: u64,
+    repeat: i64,
+}
+
+fn timeline(period: Period, track: &dyn Track, timescale: u64, offset: i128) -> Vec<TimelineEntry> {
+    let mut entries: Vec<TimelineEntry> = Vec::new();
+    let mut push = |start: u64, end: u64| {
+        let duration = end.saturating_sub(start);
+        match entries.last_mut() {
+            Some(last)
+                if last.duration == duration
+                    && u64::try_from(last.repeat + 1)
+                        .is_ok_and(|count| last.start + last.duration * count == start) =>
+            {
+                last.repeat += 1;
+            }
+            _ => entries.push(TimelineEntry {
+                start,
+                duration,
+                repeat: 0,
+            }),
+        }
+    };
+    let mut last_end = None;
+    for (_, start, end) in segment_times(track.segments(), track.segment_duration()) {
+        push(
+            media_units(start, timescale, offset),
+            media_units(end, timescale, offset),
+        );
+        last_end = Some(end);
+    }
+    let Some(duration) = track.segment_duration().map(UnsignedScaledValue::from) else {
+        return entries;
+    };
+    let mut start = last_end.unwrap_or(period.start);
+    match period.end {
+        Some(period_end) => {
+            while start < period_end {
+                let end = start
+                    .checked_add(duration)
+                    .map_or(period_end, |end| end.min(period_end));
+                push(
+                    media_units(start, timescale, offset),
+                    media_units(end, timescale, offset),
+                );
+                start = end;
+            }
+        }
+        None if period.dynamic => {
+            let start = media_units(start, timescale, offset);
+            push(start, start + to_units(duration, timescale));
+            if let Some(last) = entries.last_mut() {
+                last.repeat = -1;
+            }
+        }
+        None => {}
+    }
+    entries
+}
+
+fn write_element(
+    writer: &mut XmlWriter,
+    name: &str,
+    attributes: &Attributes,
+    inner: impl FnOnce(&mut XmlWriter) -> io::Result<()>,
+) -> io::Result<()> {
+    writer
+        .create_element(name)
+        .with_attributes(attributes.iter().map(|(key, value)| (*key, value.as_str())))
+        .write_inner_content(inner)?;
+    Ok(())
+}
+
+fn write_empty(writer: &mut XmlWriter, name: &str, attributes: &Attributes) -> io::Result<()> {
+    writer
+        .create_element(name)
+        .with_attributes(attributes.iter().map(|(key, value)| (*key, value.as_str())))
+        .write_empty()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use url::Url;
+    use uuid::uuid;
+
+    use crate::{
+        FrameRate, PresentationBuilder, ProtectionSystemAttributes, Resolution, Scale,
+        SwitchingSetProtectionSystem, TimeBounds, VideoSwitchingSetBuilder, VideoTrackBuilder,
+    };
+
+    use super::*;
+
+    fn scale() -> Scale {
+        Scale::try_from(90_000u64).unwrap()
+    }
+
+    #[test]
+    fn static_mpd_for_vod() -> anyhow::Result<()> {
+        let widevine = SwitchingSetProtectionSystem::new(
+            Some("AAAAW3Bzc2g=".to_owned()),
+            ProtectionSystemAttributes::Generic {
+                scheme_id: uuid!("edef8ba9-79d6-4ace-a3c8-27dcd51d21ed"),
+                attributes: HashMap::new(),
+            },
+        );
+        let manifest = Manifest::builder(Url::parse("http://localhost/vod/manifest.json")?, 300)
+            .presentation(
+                PresentationBuilder::new("0", TimeBounds::new(Some(0), Some(7), Scale::ONE)?)
+                    .video(
+                        VideoSwitchingSetBuilder::new("main")
+                            .codecs("avc1.4d001f")
+                            .frame_rate(FrameRate::new(30_000, Scale::try_from(1001u64)?))
+                            .continuation_pattern("video/$-{segmentId}.mp4")
+                            .initialization_pattern("video/init-{initId}.mp4")
+                            .protection(SwitchingSetProtection::new(
+                                ProtectionScheme::Cenc,
+                                vec![widevine],
+                            )?)
+                            .track(
+                                VideoTrackBuilder::new(
+                                    "720p",
+                                    3_000_000,
+                                    Resolution::new(1280, 720),
+                                )
+                                .segment_duration(ScaledDuration::new(180_000, scale()))
+                                .segment(Segment::new(
+                                    1.into(),
+                                    Some(TimeBounds::new(Some(0), None, scale())?),
+                                )),
+                            ),
+                    ),
+            )
+            .build()?;
+
+        let mpd = to_mpd(&manifest);
+
+        assert!(mpd.contains(r#"type="static""#), "{mpd}");
+        assert!(mpd.contains(r#"mediaPresentationDuration="PT7S""#));
+        assert!(mpd.contains(r#"<Period id="0" start="PT0S" duration="PT7S">"#));
+        assert!(mpd.contains(r#"frameRate="30000/1001""#));
+        assert!(mpd.contains(r#"media="video/$$-$Number$.mp4" initialization="video/init-0.mp4""#));
+        assert!(mpd.contains(r#"<S t="0" d="180000" r="2"/>"#));
+        assert!(mpd.contains(r#"<S d="90000"/>"#));
+        assert!(mpd.contains(
+            r#"<ContentProtection schemeIdUri="urn:mpeg:dash:mp4protection:2011" value="cenc"/>"#
+        ));
+        assert!(mpd.contains(
+            r#"<ContentProtection schemeIdUri="urn:uuid:edef8ba9-79d6-4ace-a3c8-27dcd51d21ed">"#
+        ));
+        assert!(mpd.contains("<cenc:pssh>AAAAW3Bzc2g=</cenc:pssh>"));
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_mpd_for_live() -> anyhow::Result<()> {
+        let data = r#"
+            {
+                "availabilityDuration": {"value": 60},
+                "creationDate": "2021-03-31T08:00:00.000Z",
+                "fallbackPollRate": 10,
+                "manifestVersion": "2.0.0",
+                "streamType": "live",
+                "activePresentation": "0",
+                "currentTime": {"value": 3600},
+                "timeSource": {"scheme": "urn:mpeg:dash:utc:http-iso:2014", "value": "https://time"},
+                "presentations": [
+                    {
+                        "id": "0",
+                        "timeBounds": {"startTime": 0},
+                        "audio": [
+                            {
+                                "id": "main",
+                                "language": "nld",
+                                "codecs": "mp4a.40.2",
+                                "sampleRate": 48000,
+                                "mediaTimeOffset": {"value": 10},
+                                "continuationPattern": "audio-{segmentId}.mp4",
+                                "initializationPattern": "audio-init-{initId}.mp4",
+                                "tracks": [
+                                    {
+                                        "id": "128k",
+                                        "bandwidth": 128000,
+                                        "startSequenceNumber": 42,
+                                        "segmentDuration": {"value": 2},
+                                        "segments": [
+                                            {"id": 1799, "timeBounds": {"startTime": 3598}}
+                                        ]
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                ]
+            }"#;
+        let manifest =
+            Manifest::from_json(Url::parse("http://localhost/live/manifest.json")?, data)?;
+
+        let mpd = to_mpd(&manifest);
+
+        assert!(mpd.contains(r#"type="dynamic""#), "{mpd}");
+        assert!(mpd.contains(r#"availabilityStartTime="2021-03-31T07:00:00.000Z""#));
+        assert!(mpd.contains(r#"publishTime="2021-03-31T08:00:00.000Z""#));
+        assert!(mpd.contains(r#"minimumUpdatePeriod="PT10S" timeShiftBufferDepth="PT60S""#));
+        assert!(mpd.contains(r#"lang="nld""#));
+        assert!(mpd.contains(r#"presentationTimeOffset="10" startNumber="1799""#));
+        assert!(mpd.contains(r#"initialization="audio-init-42.mp4""#));
+        assert!(mpd.contains(r#"<S t="3608" d="2" r="-1"/>"#));
+        assert!(mpd.contains(
+            r#"<UTCTiming schemeIdUri="urn:mpeg:dash:utc:http-iso:2014" value="https://time"/>"#
+        ));
+        Ok(())
+    }
+}
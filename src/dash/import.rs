@@ -0,0 +1,955 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use url::Url;
+use uuid::Uuid;
+
+use super::time::parse_duration;
+use super::MP4_PROTECTION_SCHEME_ID_URI;
+use crate::data::{
+    AudioSwitchingSetData, AudioTrackData, ManifestData, MetadataSwitchingSetData,
+    MetadataTrackData, PresentationData, VideoSwitchingSetData, VideoTrackData,
+};
+use crate::util::{UInt, Uri};
+use crate::{
+    AudioMimeType, Error, Fairplay, FrameRate, Language, LiveStream, ProtectionScheme,
+    ProtectionSystemAttributes, Resolution, Result, Scale, ScaledDuration, ScaledValue, Segment,
+    SegmentId, Segments, StreamType, SwitchingSetProtection, SwitchingSetProtectionSystem,
+    TimeBounds, TimeSource, UnsignedScaledValue, VideoMimeType,
+};
+
+const DEFAULT_FALLBACK_POLL_RATE: u64 = 300;
+/// The most segments a single `SegmentTimeline` may expand to.
+const MAX_TIMELINE_SEGMENTS: u64 = 100_000;
+
+/// Converts an MPEG-DASH media presentation description into a 2.0.0 manifest.
+///
+/// Periods become presentations, adaptation sets switching sets and representations tracks.
+/// Segments must be addressed with a `$Number$` segment template. DASH initialization segments
+/// are not numbered, so `{initId}` is added to their URL as a query parameter.
+pub fn from_mpd(mpd: &str) -> Result<ManifestData> {
+    let mpd = Element::parse(mpd)?;
+    if mpd.name != "MPD" {
+        return Err(invalid(format!("unexpected root element {}", mpd.name)));
+    }
+    let creation_date = match mpd.attribute("publishTime") {
+        Some(publish_time) => parse_date_time(publish_time)?,
+        None => Utc::now().fixed_offset(),
+    };
+    let presentations = presentations(&mpd)?;
+    let stream_type = if mpd.attribute("type") == Some("dynamic") {
+        StreamType::Live(live_stream(&mpd, creation_date, &presentations)?)
+    } else {
+        StreamType::Vod
+    };
+    let fallback_poll_rate = match mpd.attribute("minimumUpdatePeriod") {
+        Some(period) => duration(period)?.div_ceil(1000),
+        None => DEFAULT_FALLBACK_POLL_RATE,
+    };
+    Ok(ManifestData {
+        creation_date: creation_date.into(),
+        fallback_poll_rate: fallback_poll_rate.into(),
+        presentations,
+        stream_type,
+        content_base_url: base_url(&mpd)?,
+    })
+}
+
+fn live_stream(
+    mpd: &Element,
+    creation_date: DateTime<FixedOffset>,
+    presentations: &[PresentationData],
+) -> Result<LiveStream> {
+    let availability_start_time = mpd
+        .attribute("availabilityStartTime")
+        .ok_or_else(|| invalid("a dynamic MPD must have an availabilityStartTime"))?;
+    let current_time = creation_date
+        .signed_duration_since(parse_date_time(availability_start_time)?)
+        .num_milliseconds();
+    let current_time = u64::try_from(current_time)
+        .map_err(|_| invalid("publishTime must not precede availabilityStartTime"))?;
+    let availability_duration = match mpd.attribute("timeShiftBufferDepth") {
+        Some(depth) => duration(depth)?,
+        None => current_time,
+    };
+    let current_time = UnsignedScaledValue::new(current_time, Scale::MILLIS);
+    let active_presentation = presentations
+        .iter()
+        .rev()
+        .find(|presentation| {
+            presentation
+                .time_bounds
+                .start_time()
+                .is_some_and(|start| start <= current_time)
+        })
+        .or_else(|| presentations.first())
+        .ok_or_else(|| invalid("an MPD must have a Period"))?;
+    let time_source = mpd
+        .child("UTCTiming")
+        .map(|timing| {
+            Ok::<_, Error>(TimeSource {
+                scheme: Url::parse(required(timing, "schemeIdUri")?)?,
+                value: timing.attribute("value").unwrap_or_default().to_owned(),
+            })
+        })
+        .transpose()?;
+    Ok(LiveStream {
+        availability_duration: ScaledDuration::new(availability_duration, Scale::MILLIS),
+        active_presentation: active_presentation.id.clone(),
+        time_source,
+        current_time,
+    })
+}
+
+fn presentations(mpd: &Element) -> Result<Vec<PresentationData>> {
+    let periods: Vec<_> = mpd.children("Period").collect();
+    let mut timings = Vec::with_capacity(periods.len());
+    let mut previous_end = Some(0);
+    for period in &periods {
+        let start = match period.attribute("start") {
+            Some(start) => duration(start)?,
+            None => previous_end
+                .ok_or_else(|| invalid("a Period without start follows an open-ended Period"))?,
+        };
+        let end = period
+            .attribute("duration")
+            .map(duration)
+            .transpose()?
+            .map(|duration| {
+                start
+                    .checked_add(duration)
+                    .ok_or_else(|| invalid("Period ends after the maximum time"))
+            })
+            .transpose()?;
+        previous_end = end;
+        timings.push((start, end));
+    }
+    let presentation_duration = mpd
+        .attribute("mediaPresentationDuration")
+        .map(duration)
+        .transpose()?;
+    periods
+        .iter()
+        .enumerate()
+        .map(|(index, period)| {
+            let (start, end) = timings[index];
+            let end = end.or_else(|| match timings.get(index + 1) {
+                Some((next_start, _)) => Some(*next_start),
+                None => presentation_duration,
+            });
+            presentation(index, period, start, end)
+        })
+        .collect()
+}
+
+fn presentation(
+    index: usize,
+    period: &Element,
+    start: u64,
+    end: Option<u64>,
+) -> Result<PresentationData> {
+    let mut presentation = PresentationData {
+        id: id(period, index),
+        time_bounds: TimeBounds::new(Some(start), end, Scale::MILLIS)?,
+        audio: Vec::new(),
+        base_url: base_url(period)?,
+        events: Vec::new(),
+        metadata: Vec::new(),
+        video: Vec::new(),
+    };
+    for (index, set) in period.children("AdaptationSet").enumerate() {
+        let scope = Scope(vec![period, set]);
+        let id = id(set, index);
+        match content_type(set) {
+            Some("video") => presentation.video.push(video(id, &scope, start)?),
+            Some("audio") => presentation.audio.push(audio(id, &scope, start)?),
+            _ if set.child("InbandEventStream").is_some() => {
+                presentation.metadata.push(metadata(id, &scope, start)?);
+            }
+            // subtitles and other content have no HESP equivalent
+            _ => {}
+        }
+    }
+    Ok(presentation)
+}
+
+fn content_type(set: &Element) -> Option<&str> {
+    set.attribute("contentType")
+        .or_else(|| mime_type(set))
+        .and_then(|content_type| content_type.split('/').next())
+}
+
+fn mime_type(set: &Element) -> Option<&str> {
+    set.attribute("mimeType").or_else(|| {
+        set.children("Representation")
+            .find_map(|representation| representation.attribute("mimeType"))
+    })
+}
+
+fn video(id: String, scope: &Scope, period_start: u64) -> Result<VideoSwitchingSetData> {
+    let set = scope.element();
+    let tracks = set
+        .children("Representation")
+        .map(|representation| video_track(&scope.with(representation), period_start))
+        .collect::<Result<_>>()?;
+    Ok(VideoSwitchingSetData {
+        id,
+        tracks,
+        align_id: None,
+        base_url: base_url(set)?,
+        codecs: None,
+        continuation_pattern: None,
+        frame_rate: None,
+        initialization_pattern: None,
+        label: label(set),
+        media_time_offset: None,
+        mime_type: mime_type(set)
+            .map(|mime_type| VideoMimeType::try_from(mime_type.to_owned()))
+            .transpose()?,
+        protection: protection(set)?,
+    })
+}
+
+fn video_track(scope: &Scope, period_start: u64) -> Result<VideoTrackData> {
+    let representation = scope.element();
+    let id = required(representation, "id")?;
+    let bandwidth = required_number(representation, "bandwidth")?;
+    let width = scope
+        .number("width")?
+        .ok_or_else(|| invalid(format!("Representation {id} has no width")))?;
+    let height = scope
+        .number("height")?
+        .ok_or_else(|| invalid(format!("Representation {id} has no height")))?;
+    let mut resolution = Resolution::new(width, height);
+    if let Some(sar) = scope.attribute("sar") {
+        let (sar_width, sar_height) = sar
+            .split_once(':')
+            .and_then(|(width, height)| {
+                Some((width.parse::<u64>().ok()?, height.parse::<u64>().ok()?))
+            })
+            .ok_or_else(|| invalid(format!("'{sar}' is not a valid sar")))?;
+        resolution = resolution
+            .with_sample_aspect_ratio(Scale::try_from(sar_width)?, Scale::try_from(sar_height)?);
+    }
+    let template = Template::new(scope, id, bandwidth, period_start)?;
+    #[allow(deprecated)]
+    Ok(VideoTrackData {
+        id: id.to_owned(),
+        bandwidth: bandwidth.into(),
+        resolution,
+        segments: template.segments,
+        start_segment_id: SegmentId::default(),
+        active_segment_id: None,
+        start_sequence_number: UInt::default(),
+        active_sequence_number: None,
+        average_bandwidth: None,
+        base_url: base_url(representation)?,
+        codecs: scope.attribute("codecs").map(str::to_owned),
+        continuation_pattern: Some(template.media),
+        frame_rate: scope
+            .attribute("frameRate")
+            .map(parse_frame_rate)
+            .transpose()?,
+        label: None,
+        initialization_pattern: Some(template.initialization?),
+        media_time_offset: Some(template.media_time_offset),
+        segment_duration: template.segment_duration,
+    })
+}
+
+fn audio(id: String, scope: &Scope, period_start: u64) -> Result<AudioSwitchingSetData> {
+    let set = scope.element();
+    let tracks = set
+        .children("Representation")
+        .map(|representation| audio_track(&scope.with(representation), period_start))
+        .collect::<Result<_>>()?;
+    Ok(AudioSwitchingSetData {
+        id,
+        language: set.attribute("lang").map_or(Language::Und, parse_language),
+        tracks,
+        align_id: None,
+        base_url: base_url(set)?,
+        channels: channels(set)?,
+        codecs: None,
+        continuation_pattern: None,
+        samples_per_frame: None,
+        initialization_pattern: None,
+        label: label(set),
+        media_time_offset: None,
+        mime_type: mime_type(set)
+            .map(|mime_type| AudioMimeType::try_from(mime_type.to_owned()))
+            .transpose()?,
+        protection: protection(set)?,
+        sample_rate: None,
+    })
+}
+
+fn audio_track(scope: &Scope, period_start: u64) -> Result<AudioTrackData> {
+    let representation = scope.element();
+    let id = required(representation, "id")?;
+    let bandwidth = required_number(representation, "bandwidth")?;
+    let template = Template::new(scope, id, bandwidth, period_start)?;
+    #[allow(deprecated)]
+    Ok(AudioTrackData {
+        id: id.to_owned(),
+        bandwidth: bandwidth.into(),
+        segments: template.segments,
+        start_segment_id: SegmentId::default(),
+        active_segment_id: None,
+        start_sequence_number: UInt::default(),
+        active_sequence_number: None,
+        average_bandwidth: None,
+        base_url: base_url(representation)?,
+        channels: channels(representation)?,
+        codecs: scope.attribute("codecs").map(str::to_owned),
+        continuation_pattern: Some(template.media),
+        samples_per_frame: None,
+        label: None,
+        initialization_pattern: Some(template.initialization?),
+        media_time_offset: Some(template.media_time_offset),
+        sample_rate: scope.number::<u64>("audioSamplingRate")?.map(UInt::from),
+        segment_duration: template.segment_duration,
+    })
+}
+
+fn metadata(id: String, scope: &Scope, period_start: u64) -> Result<MetadataSwitchingSetData> {
+    let set = scope.element();
+    let tracks = set
+        .children("Representation")
+        .map(|representation| metadata_track(&scope.with(representation), period_start))
+        .collect::<Result<_>>()?;
+    let event_stream = set.child("InbandEventStream").unwrap();
+    Ok(MetadataSwitchingSetData {
+        id,
+        mime_type: mime_type(set).unwrap_or("application/mp4").to_owned(),
+        tracks,
+        scheme_id: required(event_stream, "schemeIdUri")?.to_owned(),
+        align_id: None,
+        base_url: base_url(set)?,
+        codecs: None,
+        continuation_pattern: None,
+        label: label(set),
+        language: set.attribute("lang").map(parse_language),
+        media_time_offset: None,
+    })
+}
+
+fn metadata_track(scope: &Scope, period_start: u64) -> Result<MetadataTrackData> {
+    let representation = scope.element();
+    let id = required(representation, "id")?;
+    let bandwidth = required_number(representation, "bandwidth")?;
+    let template = Template::new(scope, id, bandwidth, period_start)?;
+    Ok(MetadataTrackData {
+        id: id.to_owned(),
+        segments: template.segments,
+        start_segment_id: SegmentId::default(),
+        average_bandwidth: None,
+        bandwidth: Some(bandwidth.into()),
+        base_url: base_url(representation)?,
+        codecs: scope.attribute("codecs").map(str::to_owned),
+        continuation_pattern: Some(template.media),
+        label: None,
+        media_time_offset: Some(template.media_time_offset),
+        segment_duration: template.segment_duration,
+    })
+}
+
+/// The `SegmentTemplate` of a representation, merged with those of its adaptation set and period.
+struct Template {
+    media: String,
+    initialization: Result<String>,
+    media_time_offset: ScaledValue,
+    segment_duration: Option<ScaledDuration>,
+    segments: Segments,
+}
+
+impl Template {
+    fn new(
+        scope: &Scope,
+        representation_id: &str,
+        bandwidth: u64,
+        period_start: u64,
+    ) -> Result<Self> {
+        let templates = Scope(
+            scope
+                .0
+                .iter()
+                .filter_map(|element| element.child("SegmentTemplate"))
+                .collect(),
+        );
+        let media = templates.attribute("media").ok_or_else(|| {
+            invalid(format!(
+                "Representation {representation_id} has no SegmentTemplate@media"
+            ))
+        })?;
+        let media = url_pattern(media, representation_id, bandwidth, Some("{segmentId}"))?;
+        let initialization = templates
+            .attribute("initialization")
+            .ok_or_else(|| {
+                invalid(format!(
+                    "Representation {representation_id} has no SegmentTemplate@initialization"
+                ))
+            })
+            .and_then(|pattern| url_pattern(pattern, representation_id, bandwidth, None))
+            .map(|pattern| {
+                let separator = if pattern.contains('?') { '&' } else { '?' };
+                format!("{pattern}{separator}initId={{initId}}")
+            });
+        let timescale = templates.number::<u64>("timescale")?.unwrap_or(1);
+        let scale = Scale::try_from(timescale)?;
+        let start_number = templates.number("startNumber")?.unwrap_or(1);
+        let presentation_time_offset = templates.number("presentationTimeOffset")?.unwrap_or(0);
+        let period_start = u128::from(period_start) * u128::from(timescale) / 1000;
+        let offset = i128::from(presentation_time_offset) - i128::try_from(period_start).unwrap();
+        let offset = i64::try_from(offset)
+            .map_err(|_| invalid(format!("presentationTimeOffset {presentation_time_offset}")))?;
+        let (segment_duration, segments) =
+            if let Some(timeline) = templates.child("SegmentTimeline") {
+                timeline_segments(timeline, start_number, offset, scale)?
+            } else {
+                let duration = templates.number("duration")?.ok_or_else(|| {
+                    invalid(format!(
+                        "Representation {representation_id} has no SegmentTimeline or duration"
+                    ))
+                })?;
+                let start = u64::try_from(period_start).unwrap_or(u64::MAX);
+                let first = Segment::new(
+                    start_number.into(),
+                    Some(TimeBounds::new(Some(start), None, scale)?),
+                );
+                (Some(ScaledDuration::new(duration, scale)), vec![first])
+            };
+        Ok(Self {
+            media,
+            initialization,
+            media_time_offset: ScaledValue::new(offset, scale),
+            segment_duration,
+            segments: Segments::try_from(segments)?,
+        })
+    }
+}
+
+/// Lists the segments of a `SegmentTimeline`, with `offset` converting media to presentation time.
+///
+/// The duration of the last entry becomes the segment duration of the track, so only segments
+/// that start after a gap or that have a different duration need explicit time bounds.
+fn timeline_segments(
+    timeline: &Element,
+    start_number: u64,
+    offset: i64,
+    scale: Scale,
+) -> Result<(Option<ScaledDuration>, Vec<Segment>)> {
+    let entries: Vec<_> = timeline.children("S").collect();
+    let Some(last) = entries.last() else {
+        return Ok((None, Vec::new()));
+    };
+    let segment_duration = required_number(last, "d")?;
+    let mut segments = Vec::new();
+    let mut id = SegmentId::from(start_number);
+    let mut time = None;
+    for (index, entry) in entries.iter().enumerate() {
+        let duration = required_number(entry, "d")?;
+        if duration == 0 {
+            return Err(invalid("S@d must be strictly positive"));
+        }
+        let mut start = entry.number("t")?.or(time).unwrap_or(0);
+        let repeat: i64 = entry.number("r")?.unwrap_or(0);
+        let count = match u64::try_from(repeat) {
+            Ok(repeat) => repeat + 1,
+            Err(_) => match entries.get(index + 1).map(|next| next.number::<u64>("t")) {
+                Some(next) => next?
+                    .ok_or_else(|| invalid("S@r=\"-1\" must be followed by an S@t"))?
+                    .saturating_sub(start)
+                    .div_ceil(duration),
+                None => 1,
+            },
+        };
+        if count > MAX_TIMELINE_SEGMENTS - segments.len() as u64 {
+            return Err(invalid(format!(
+                "SegmentTimeline has more than {MAX_TIMELINE_SEGMENTS} segments"
+            )));
+        }
+        let overflow = || invalid("SegmentTimeline exceeds the maximum time");
+        for _ in 0..count {
+            let presentation_start = u64::try_from(i128::from(start) - i128::from(offset))
+                .map_err(|_| invalid(format!("segment at {start} starts before the period")))?;
+            let start_time = (time != Some(start)).then_some(presentation_start);
+            let end_time = if duration == segment_duration {
+                None
+            } else {
+                Some(
+                    presentation_start
+                        .checked_add(duration)
+                        .ok_or_else(overflow)?,
+                )
+            };
+            let time_bounds = match (start_time, end_time) {
+                (None, None) => None,
+                _ => Some(TimeBounds::new(start_time, end_time, scale)?),
+            };
+            segments.push(Segment::new(id, time_bounds));
+            id = id.next();
+            start = start.checked_add(duration).ok_or_else(overflow)?;
+            time = Some(start);
+        }
+    }
+    Ok((Some(ScaledDuration::new(segment_duration, scale)), segments))
+}
+
+/// Converts a DASH URL template to a HESP pattern, with `number` replacing `$Number$`.
+fn url_pattern(
+    template: &str,
+    representation_id: &str,
+    bandwidth: u64,
+    number: Option<&str>,
+) -> Result<String> {
+    let unsupported = || Error::UnsupportedSegmentTemplate(template.to_owned());
+    let mut parts = template.split('$');
+    let mut pattern = parts.next().unwrap_or_default().to_owned();
+    while let Some(identifier) = parts.next() {
+        let rest = parts.next().ok_or_else(unsupported)?;
+        match identifier {
+            "" => pattern.push('$'),
+            "RepresentationID" => pattern.push_str(representation_id),
+            "Bandwidth" => pattern.push_str(&bandwidth.to_string()),
+            "Number" => pattern.push_str(number.ok_or_else(unsupported)?),
+            _ => return Err(unsupported()),
+        }
+        pattern.push_str(rest);
+    }
+    Ok(pattern)
+}
+
+fn protection(set: &Element) -> Result<Option<SwitchingSetProtection>> {
+    let mut scheme = ProtectionScheme::Cenc;
+    let mut default_kid = None;
+    let mut systems = Vec::new();
+    for element in set.children("ContentProtection") {
+        let scheme_id_uri = element.attribute("schemeIdUri").unwrap_or_default();
+        if scheme_id_uri.eq_ignore_ascii_case(MP4_PROTECTION_SCHEME_ID_URI) {
+            if element.attribute("value") == Some("cbcs") {
+                scheme = ProtectionScheme::Cbcs;
+            }
+            default_kid = element.attribute("default_KID").or(default_kid);
+        } else if let Some(uuid) = scheme_id_uri
+            .get(..9)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("urn:uuid:"))
+            .map(|_| &scheme_id_uri[9..])
+        {
+            let scheme_id = Uuid::parse_str(uuid)
+                .map_err(|_| invalid(format!("'{scheme_id_uri}' is not a valid UUID URN")))?;
+            // Fairplay needs key attributes that DASH does not carry
+            if scheme_id != Fairplay::SCHEME_ID {
+                systems.push((scheme_id, element));
+            }
+        }
+    }
+    if systems.is_empty() {
+        return Ok(None);
+    }
+    let systems = systems
+        .into_iter()
+        .map(|(scheme_id, element)| {
+            let attributes = element
+                .attribute("default_KID")
+                .or(default_kid)
+                .map(|kid| ("defaultKid".to_owned(), kid.to_owned()))
+                .into_iter()
+                .collect();
+            SwitchingSetProtectionSystem::new(
+                element.child("pssh").map(|pssh| pssh.text.clone()),
+                ProtectionSystemAttributes::Generic {
+                    scheme_id,
+                    attributes,
+                },
+            )
+        })
+        .collect();
+    SwitchingSetProtection::new(scheme, systems).map(Some)
+}
+
+fn channels(element: &Element) -> Result<Option<UInt>> {
+    element
+        .child("AudioChannelConfiguration")
+        .map(|configuration| required_number::<u64>(configuration, "value").map(UInt::from))
+        .transpose()
+}
+
+/// Parses the primary language of an RFC 5646 language tag, e.g. `en` or `nld-NL`.
+fn parse_language(tag: &str) -> Language {
+    let code = tag.split('-').next().unwrap_or_default().to_lowercase();
+    Language::from_iso_639_1(&code)
+        .or_else(|| serde_json::from_value(serde_json::Value::String(code)).ok())
+        .unwrap_or(Language::Und)
+}
+
+fn parse_frame_rate(frame_rate: &str) -> Result<FrameRate> {
+    let invalid_frame_rate = || invalid(format!("'{frame_rate}' is not a valid frameRate"));
+    let (value, scale) = frame_rate.split_once('/').unwrap_or((frame_rate, "1"));
+    let value = value.parse().map_err(|_| invalid_frame_rate())?;
+    let scale = scale.parse::<u64>().map_err(|_| invalid_frame_rate())?;
+    Ok(FrameRate::new(value, Scale::try_from(scale)?))
+}
+
+fn parse_date_time(date_time: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(date_time)
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(date_time, "%Y-%m-%dT%H:%M:%S%.f")
+                .map(|date_time| date_time.and_utc().fixed_offset())
+        })
+        .map_err(|_| invalid(format!("'{date_time}' is not a valid date")))
+}
+
+fn duration(duration: &str) -> Result<u64> {
+    parse_duration(duration).ok_or_else(|| invalid(format!("'{duration}' is not a valid duration")))
+}
+
+fn base_url(element: &Element) -> Result<Option<Uri>> {
+    Ok(element
+        .child("BaseURL")
+        .map(|base_url| Uri::try_from(base_url.text.clone()))
+        .transpose()?)
+}
+
+fn label(element: &Element) -> Option<String> {
+    element.child("Label").map(|label| label.text.clone())
+}
+
+fn id(element: &Element, index: usize) -> String {
+    element
+        .attribute("id")
+        .map_or_else(|| index.to_string(), str::to_owned)
+}
+
+fn required<'a>(element: &'a Element, name: &str) -> Result<&'a str> {
+    element
+        .attribute(name)
+        .ok_or_else(|| invalid(format!("{} must have a {name}", element.name)))
+}
+
+fn required_number<T: FromStr>(element: &Element, name: &str) -> Result<T> {
+    element
+        .number(name)?
+        .ok_or_else(|| invalid(format!("{} must have a {name}", element.name)))
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidMpd(message.into())
+}
+
+/// An XML element, with namespace prefixes stripped from its name and attribute names.
+struct Element {
+    name: String,
+    attributes: HashMap<String, String>,
+    children: Vec<Self>,
+    text: String,
+}
+
+impl Element {
+    fn parse(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+        let mut open: Vec<Self> = Vec::new();
+        loop {
+            let closed = match reader.read_event()? {
+                Event::Start(start) => {
+                    open.push(Self::new(&start)?);
+                    continue;
+                }
+                Event::Empty(start) => Self::new(&start)?,
+                Event::End(_) => open.pop().ok_or_else(|| invalid("unbalanced end tag"))?,
+                Event::Text(text) => {
+                    if let Some(element) = open.last_mut() {
+                        element.text.push_str(&text.unescape()?);
+                    }
+                    continue;
+                }
+                Event::CData(data) => {
+                    if let Some(element) = open.last_mut() {
+                        element.text.push_str(&String::from_utf8_lossy(&data));
+                    }
+                    continue;
+                }
+                Event::Eof => return Err(invalid("no root element")),
+                _ => continue,
+            };
+            match open.last_mut() {
+                Some(parent) => parent.children.push(closed),
+                None => return Ok(closed),
+            }
+        }
+    }
+
+    fn new(start: &BytesStart) -> Result<Self> {
+        let attributes = start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute.map_err(quick_xml::Error::from)?;
+                let name =
+                    String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+                Ok((name, attribute.unescape_value()?.into_owned()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            children: Vec::new(),
+            text: String::new(),
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    fn number<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        parse_number(self.attribute(name), name)
+    }
+
+    fn child(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Self> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// A chain of nested elements whose attributes are inherited by the innermost one.
+struct Scope<'a>(Vec<&'a Element>);
+
+impl<'a> Scope<'a> {
+    fn with(&self, element: &'a Element) -> Self {
+        let mut elements = self.0.clone();
+        elements.push(element);
+        Self(elements)
+    }
+
+    fn element(&self) -> &'a Element {
+        self.0.last().expect("a scope is never empty")
+    }
+
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.0
+            .iter()
+            .rev()
+            .find_map(|element| element.attribute(name))
+    }
+
+    fn number<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        parse_number(self.attribute(name), name)
+    }
+
+    fn child(&self, name: &str) -> Option<&'a Element> {
+        self.0.iter().rev().find_map(|element| element.child(name))
+    }
+}
+
+fn parse_number<T: FromStr>(value: Option<&str>, name: &str) -> Result<Option<T>> {
+    value
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("'{value}' is not a valid {name}")))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use url::Url;
+    use uuid::uuid;
+
+    use crate::{Manifest, SwitchingSet, Track};
+
+    use super::super::to_mpd;
+    use super::*;
+
+    fn location() -> Url {
+        Url::parse("http://localhost/manifest.json").unwrap()
+    }
+
+    #[test]
+    fn import_static_mpd() -> anyhow::Result<()> {
+        let mpd = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <MPD xmlns="urn:mpeg:dash:schema:mpd:2011" xmlns:cenc="urn:mpeg:cenc:2013"
+                 type="static" mediaPresentationDuration="PT9S" minBufferTime="PT2S">
+              <BaseURL>https://cdn.example.com/vod/</BaseURL>
+              <Period id="main">
+                <AdaptationSet mimeType="video/mp4" codecs="avc1.4d001f" frameRate="30000/1001">
+                  <ContentProtection schemeIdUri="urn:mpeg:dash:mp4protection:2011" value="cenc"
+                                     cenc:default_KID="10000000-1000-1000-1000-100000000001"/>
+                  <ContentProtection schemeIdUri="urn:uuid:EDEF8BA9-79D6-4ACE-A3C8-27DCD51D21ED">
+                    <cenc:pssh>AAAAW3Bzc2g=</cenc:pssh>
+                  </ContentProtection>
+                  <SegmentTemplate timescale="1000" media="$RepresentationID$/$Number$.m4s"
+                                   initialization="$RepresentationID$/init.mp4">
+                    <SegmentTimeline>
+                      <S t="0" d="4000" r="1"/>
+                      <S d="1000"/>
+                    </SegmentTimeline>
+                  </SegmentTemplate>
+                  <Representation id="720p" bandwidth="3000000" width="1280" height="720" sar="1:1"/>
+                </AdaptationSet>
+                <AdaptationSet contentType="audio" mimeType="audio/mp4" lang="nld-NL">
+                  <Label>Nederlands</Label>
+                  <Representation id="a" bandwidth="128000" codecs="mp4a.40.2"
+                                  audioSamplingRate="48000">
+                    <AudioChannelConfiguration value="2"
+                        schemeIdUri="urn:mpeg:dash:23003:3:audio_channel_configuration:2011"/>
+                    <SegmentTemplate timescale="48000" duration="96000" startNumber="0"
+                                     media="audio-$Bandwidth$-$Number$.m4s"
+                                     initialization="audio-init.mp4?token=1"/>
+                  </Representation>
+                </AdaptationSet>
+                <AdaptationSet mimeType="text/vtt" lang="en">
+                  <Representation id="subtitles" bandwidth="256"/>
+                </AdaptationSet>
+              </Period>
+            </MPD>"#;
+
+        let data = from_mpd(mpd)?;
+        let manifest = Manifest::new(location(), data)?;
+
+        assert!(matches!(manifest.stream_type(), StreamType::Vod));
+        let presentation = manifest.presentation("main").unwrap();
+        assert_eq!(
+            presentation.time_bounds().end_time(),
+            Some(UnsignedScaledValue::from(9))
+        );
+        let video = presentation.video_switching_set("0").unwrap();
+        let protection = video.protection.as_ref().unwrap();
+        assert_eq!(protection.scheme(), ProtectionScheme::Cenc);
+        let system = &protection.systems()[0];
+        assert_eq!(
+            system.scheme_id(),
+            uuid!("edef8ba9-79d6-4ace-a3c8-27dcd51d21ed")
+        );
+        assert_eq!(system.pssh(), Some("AAAAW3Bzc2g="));
+        let track = video.track("720p").unwrap();
+        assert_eq!(
+            track.frame_rate,
+            FrameRate::new(30_000, Scale::try_from(1001u64)?)
+        );
+        assert_eq!(
+            track.continuation_pattern().clone().into_full_pattern(),
+            "https://cdn.example.com/vod/720p/{segmentId}.m4s"
+        );
+        assert_eq!(
+            track.initialization_pattern.clone().into_full_pattern(),
+            "https://cdn.example.com/vod/720p/init.mp4?initId={initId}"
+        );
+        assert_eq!(track.segments().len(), 3);
+        assert_eq!(
+            track.segment_time_bounds(3.into()),
+            TimeBounds::new(Some(8), Some(9), Scale::ONE).ok()
+        );
+        let audio = presentation.audio_switching_set("1").unwrap();
+        assert_eq!(audio.language, Language::Nld);
+        assert_eq!(audio.label.as_deref(), Some("Nederlands"));
+        let track = audio.track("a").unwrap();
+        assert_eq!(track.channels, Some(2));
+        assert_eq!(track.sample_rate, 48000);
+        assert_eq!(
+            track.initialization_pattern.clone().into_full_pattern(),
+            "https://cdn.example.com/vod/audio-init.mp4?token=1&initId={initId}"
+        );
+        assert_eq!(
+            track.segment_at(UnsignedScaledValue::from(5)),
+            Some(2.into())
+        );
+        assert_eq!(presentation.tracks().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn reject_oversized_timelines() -> anyhow::Result<()> {
+        let huge = Element::parse(
+            r#"<SegmentTimeline><S t="0" d="1" r="-1"/><S t="1000000000000000000" d="1"/></SegmentTimeline>"#,
+        )?;
+        let result = timeline_segments(&huge, 1, 0, Scale::ONE);
+        assert!(matches!(result, Err(Error::InvalidMpd(_))));
+
+        let overflowing = Element::parse(
+            r#"<SegmentTimeline><S t="18446744073709551615" d="2"/></SegmentTimeline>"#,
+        )?;
+        let result = timeline_segments(&overflowing, 1, 0, Scale::ONE);
+        assert!(matches!(result, Err(Error::InvalidMpd(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_language_tags() {
+        assert_eq!(parse_language("en"), Language::Eng);
+        assert_eq!(parse_language("nl-BE"), Language::Nld);
+        assert_eq!(parse_language("nld-NL"), Language::Nld);
+        assert_eq!(parse_language("zz"), Language::Und);
+    }
+
+    #[test]
+    fn reject_overflowing_period() {
+        let mpd = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <MPD xmlns="urn:mpeg:dash:schema:mpd:2011" type="static" minBufferTime="PT2S">
+              <Period start="PT18446744073709551S" duration="PT18446744073709551S"/>
+            </MPD>"#;
+
+        assert!(
+            matches!(from_mpd(mpd), Err(Error::InvalidMpd(message)) if message.contains("Period"))
+        );
+    }
+
+    #[test]
+    fn import_exported_mpd() -> anyhow::Result<()> {
+        let original =
+            Manifest::from_json(location(), include_str!("../../tests/v2_0_0-manifest.json"))?;
+
+        let data = from_mpd(&to_mpd(&original))?;
+        let manifest = Manifest::new(location(), data)?;
+
+        let StreamType::Live(live) = manifest.stream_type() else {
+            panic!("expected a live stream");
+        };
+        let StreamType::Live(original_live) = original.stream_type() else {
+            panic!("expected a live stream");
+        };
+        assert_eq!(live.current_time, original_live.current_time);
+        assert_eq!(live.active_presentation, "1");
+        assert_eq!(
+            live.time_source.as_ref().unwrap().scheme.as_str(),
+            "urn:mpeg:dash:utc:ntp:2014"
+        );
+        let tracks = |manifest: &Manifest| {
+            manifest
+                .presentations()
+                .flat_map(|presentation| {
+                    presentation.tracks().map(|track| {
+                        let first = track.segments()[0].id();
+                        (
+                            track.uid().presentation_id().to_owned(),
+                            track.uid().track_id().to_owned(),
+                            first,
+                            track.segment_time_bounds(first + 5),
+                        )
+                    })
+                })
+                .sorted_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)))
+                .collect_vec()
+        };
+        let imported = tracks(&manifest);
+        assert_eq!(imported.len(), 5);
+        for (imported, original) in imported.iter().zip(tracks(&original)) {
+            assert_eq!(imported.1, original.1);
+            assert_eq!(imported.2, original.2);
+            assert_eq!(
+                imported.3.and_then(|bounds| bounds.start_time()),
+                original.3.and_then(|bounds| bounds.start_time())
+            );
+        }
+        Ok(())
+    }
+}
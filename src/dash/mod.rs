@@ -0,0 +1,13 @@
+pub use export::to_mpd;
+pub use import::from_mpd;
+
+mod export;
+mod import;
+mod time;
+
+pub const MPD_NAMESPACE: &str = "urn:mpeg:dash:schema:mpd:2011";
+pub const CENC_NAMESPACE: &str = "urn:mpeg:cenc:2013";
+pub const LIVE_PROFILE: &str = "urn:mpeg:dash:profile:isoff-live:2011";
+pub const MP4_PROTECTION_SCHEME_ID_URI: &str = "urn:mpeg:dash:mp4protection:2011";
+pub const AUDIO_CHANNEL_CONFIGURATION_SCHEME_ID_URI: &str =
+    "urn:mpeg:dash:23003:3:audio_channel_configuration:2011";
@@ -0,0 +1,120 @@
+use gcd::Gcd;
+
+use crate::{Scale, ScaledValue, UnsignedScaledValue};
+
+/// Formats a media time or duration as an ISO 8601 duration with millisecond precision.
+pub fn format_duration(value: UnsignedScaledValue) -> String {
+    let millis = u128::from(value.value) * 1000 / u128::from(value.scale);
+    let (seconds, millis) = (millis / 1000, millis % 1000);
+    if millis == 0 {
+        format!("PT{seconds}S")
+    } else {
+        let fraction = format!("{millis:03}");
+        format!("PT{seconds}.{}S", fraction.trim_end_matches('0'))
+    }
+}
+
+/// Parses an ISO 8601 duration such as `PT1M30.5S` into milliseconds.
+///
+/// Years and months have no fixed length and are therefore not supported.
+pub fn parse_duration(duration: &str) -> Option<u64> {
+    let rest = duration.strip_prefix('P')?;
+    let (date, time) = rest.split_once('T').unwrap_or((rest, ""));
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+    let date = parse_components(date, &[('D', 86_400_000)])?;
+    let time = parse_components(time, &[('H', 3_600_000), ('M', 60_000), ('S', 1000)])?;
+    date.checked_add(time)
+}
+
+fn parse_components(input: &str, units: &[(char, u64)]) -> Option<u64> {
+    let mut millis = 0u64;
+    let mut start = 0;
+    for (index, designator) in input.char_indices() {
+        if designator.is_ascii_digit() || designator == '.' {
+            continue;
+        }
+        let (_, unit) = units.iter().find(|(unit, _)| *unit == designator)?;
+        millis = millis.checked_add(to_millis(&input[start..index], *unit)?)?;
+        start = index + 1;
+    }
+    (start == input.len()).then_some(millis)
+}
+
+fn to_millis(number: &str, unit: u64) -> Option<u64> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let mut millis = whole.parse::<u64>().ok()?.checked_mul(unit)?;
+    if !fraction.is_empty() {
+        let fraction = &fraction[..fraction.len().min(9)];
+        let denominator = 10u128.pow(u32::try_from(fraction.len()).ok()?);
+        let part = fraction.parse::<u128>().ok()? * u128::from(unit) / denominator;
+        millis = millis.checked_add(u64::try_from(part).ok()?)?;
+    }
+    Some(millis)
+}
+
+/// Returns the least common multiple of `scales`, so every value can be expressed exactly.
+///
+/// Falls back to milliseconds when that multiple does not fit in a `u64`.
+pub fn timescale(scales: impl IntoIterator<Item = Scale>) -> u64 {
+    scales
+        .into_iter()
+        .map(u64::from)
+        .try_fold(1u64, |lcm, scale| (lcm / lcm.gcd(scale)).checked_mul(scale))
+        .unwrap_or_else(|| Scale::MILLIS.into())
+}
+
+/// Expresses `value` in units of `timescale`, rounding down.
+pub fn to_units(value: UnsignedScaledValue, timescale: u64) -> u64 {
+    let units = u128::from(value.value) * u128::from(timescale) / u128::from(value.scale);
+    u64::try_from(units).unwrap_or(u64::MAX)
+}
+
+/// Expresses a signed `value` in units of `timescale`, rounding towards zero.
+pub fn to_signed_units(value: ScaledValue, timescale: u64) -> i128 {
+    i128::from(value.value) * i128::from(timescale) / i128::from(u64::from(value.scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_durations() -> anyhow::Result<()> {
+        let scale = Scale::try_from(90_000u64)?;
+
+        assert_eq!(format_duration(UnsignedScaledValue::ZERO), "PT0S");
+        assert_eq!(format_duration(1500.into()), "PT1500S");
+        assert_eq!(
+            format_duration(UnsignedScaledValue::new(135_000, scale)),
+            "PT1.5S"
+        );
+        assert_eq!(
+            format_duration(UnsignedScaledValue::new(3003, Scale::MILLIS)),
+            "PT3.003S"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(parse_duration("PT0S"), Some(0));
+        assert_eq!(parse_duration("PT1M30.5S"), Some(90_500));
+        assert_eq!(parse_duration("P1DT1H"), Some(90_000_000));
+        assert_eq!(parse_duration("PT0.25H"), Some(900_000));
+        assert_eq!(parse_duration("PT3.0033S"), Some(3003));
+        assert_eq!(parse_duration("P1Y"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("PT5"), None);
+    }
+
+    #[test]
+    fn common_timescale() -> anyhow::Result<()> {
+        let scales = [Scale::try_from(90_000u64)?, Scale::try_from(48_000u64)?];
+
+        assert_eq!(timescale(scales), 720_000);
+        assert_eq!(timescale([]), 1);
+        Ok(())
+    }
+}
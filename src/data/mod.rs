@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 pub use v2_0_0::*;
 
-use crate::Manifest;
+use crate::{Manifest, ManifestVersion, Result};
 
 mod v1_0_0;
 mod v1_1_0;
@@ -22,10 +22,28 @@ pub enum ManifestDeserialize {
 #[derive(Serialize, Debug, Clone)]
 #[serde(tag = "manifestVersion")]
 pub enum ManifestSerialize {
+    #[serde(rename = "1.0.0")]
+    V1_0_0(v1_0_0::ManifestData),
+    #[serde(rename = "1.1.0")]
+    V1_1_0(v1_1_0::ManifestData),
     #[serde(rename = "2.0.0")]
     V2_0_0(v2_0_0::ManifestData),
 }
 
+impl ManifestSerialize {
+    /// Converts `manifest` to the data format of the given `version`.
+    ///
+    /// Fails when the manifest contains information that cannot be represented in that version.
+    pub fn new(manifest: Manifest, version: ManifestVersion) -> Result<Self> {
+        let data = ManifestData::from(manifest);
+        Ok(match version {
+            ManifestVersion::V1_0_0 => Self::V1_0_0(v1_1_0::ManifestData::try_from(data)?.into()),
+            ManifestVersion::V1_1_0 => Self::V1_1_0(data.try_into()?),
+            ManifestVersion::V2_0_0 => Self::V2_0_0(data),
+        })
+    }
+}
+
 impl From<Manifest> for ManifestSerialize {
     fn from(input: Manifest) -> Self {
         Self::V2_0_0(ManifestData::from(input))
@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::v1_1_0;
 use crate::util::{UInt, Uri};
@@ -7,7 +8,8 @@ use crate::{
     SwitchingSetProtection,
 };
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioSwitchingSetData {
     pub id: String,
@@ -39,7 +41,7 @@ impl From<AudioSwitchingSetData> for v1_1_0::AudioSwitchingSetData {
                 .collect(),
             align_id: input.align_id,
             base_url: input.base_url,
-            channels: input.channels.map(UInt::from),
+            channels: input.channels,
             codecs: input.codecs,
             continuation_pattern: input.continuation_pattern,
             samples_per_frame: input.frame_rate,
@@ -48,12 +50,13 @@ impl From<AudioSwitchingSetData> for v1_1_0::AudioSwitchingSetData {
             media_time_offset: input.media_time_offset,
             mime_type: input.mime_type,
             protection: input.protection,
-            sample_rate: input.sample_rate.map(UInt::from),
+            sample_rate: input.sample_rate,
         }
     }
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioTrackData {
     pub id: String,
@@ -96,3 +99,48 @@ impl From<AudioTrackData> for v1_1_0::AudioTrackData {
         }
     }
 }
+
+impl From<v1_1_0::AudioSwitchingSetData> for AudioSwitchingSetData {
+    fn from(input: v1_1_0::AudioSwitchingSetData) -> Self {
+        Self {
+            id: input.id,
+            language: input.language,
+            tracks: input.tracks.into_iter().map(AudioTrackData::from).collect(),
+            align_id: input.align_id,
+            base_url: input.base_url,
+            channels: input.channels,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            frame_rate: input.samples_per_frame,
+            initialization_pattern: input.initialization_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            mime_type: input.mime_type,
+            protection: input.protection,
+            sample_rate: input.sample_rate,
+        }
+    }
+}
+
+impl From<v1_1_0::AudioTrackData> for AudioTrackData {
+    fn from(input: v1_1_0::AudioTrackData) -> Self {
+        Self {
+            id: input.id,
+            bandwidth: input.bandwidth,
+            segments: input.segments,
+            active_segment: input.active_segment,
+            active_sequence_number: input.active_sequence_number,
+            average_bandwidth: input.average_bandwidth,
+            base_url: input.base_url,
+            channels: input.channels,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            frame_rate: input.samples_per_frame,
+            label: input.label,
+            initialization_pattern: input.initialization_pattern,
+            media_time_offset: input.media_time_offset,
+            sample_rate: input.sample_rate,
+            segment_duration: input.segment_duration,
+        }
+    }
+}
@@ -1,10 +1,12 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::{v1_0_0, v1_1_0};
 use crate::util::{Timestamp, UInt, Uri};
 use crate::{Error, Result};
 
-#[derive(Deserialize, Debug, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ManifestData {
     pub creation_date: Timestamp,
@@ -31,6 +33,22 @@ impl From<ManifestData> for v1_1_0::ManifestData {
     }
 }
 
+impl From<v1_1_0::ManifestData> for ManifestData {
+    fn from(input: v1_1_0::ManifestData) -> Self {
+        Self {
+            creation_date: input.creation_date,
+            fallback_poll_rate: input.fallback_poll_rate,
+            presentations: input
+                .presentations
+                .into_iter()
+                .map(v1_0_0::PresentationData::from)
+                .collect(),
+            stream_type: input.stream_type,
+            content_base_url: input.content_base_url,
+        }
+    }
+}
+
 impl TryFrom<ManifestData> for crate::data::ManifestData {
     type Error = Error;
 
@@ -1,10 +1,12 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::v2_0_0;
 use crate::util::{UInt, Uri};
 use crate::{Language, ScaledDuration, ScaledValue, SegmentId, Segments};
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MetadataSwitchingSetData {
     pub id: String,
@@ -20,7 +22,8 @@ pub struct MetadataSwitchingSetData {
     pub media_time_offset: Option<ScaledValue>,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MetadataTrackData {
     pub id: String,
@@ -75,3 +78,44 @@ impl From<MetadataTrackData> for v2_0_0::MetadataTrackData {
         }
     }
 }
+
+impl From<v2_0_0::MetadataSwitchingSetData> for MetadataSwitchingSetData {
+    fn from(input: v2_0_0::MetadataSwitchingSetData) -> Self {
+        Self {
+            id: input.id,
+            mime_type: input.mime_type,
+            tracks: input
+                .tracks
+                .into_iter()
+                .map(MetadataTrackData::from)
+                .collect(),
+            scheme_id: input.scheme_id,
+            align_id: input.align_id,
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            label: input.label,
+            language: input.language,
+            media_time_offset: input.media_time_offset,
+        }
+    }
+}
+
+impl From<v2_0_0::MetadataTrackData> for MetadataTrackData {
+    fn from(input: v2_0_0::MetadataTrackData) -> Self {
+        Self {
+            id: input.id,
+            segments: input.segments,
+            active_segment: (input.start_segment_id != SegmentId::default())
+                .then_some(input.start_segment_id),
+            average_bandwidth: input.average_bandwidth,
+            bandwidth: input.bandwidth,
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            segment_duration: input.segment_duration,
+        }
+    }
+}
@@ -1,23 +1,25 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::{v1_0_0, v1_1_0};
 use crate::util::Uri;
 use crate::{PresentationEvent, TimeBounds, UnsignedScaledValue};
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PresentationData {
     pub id: String,
     pub time_bounds: TimeBounds,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub audio: Vec<v1_0_0::AudioSwitchingSetData>,
     pub base_url: Option<Uri>,
     pub current_time: Option<UnsignedScaledValue>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub events: Vec<PresentationEvent>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub metadata: Vec<v1_0_0::MetadataSwitchingSetData>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub video: Vec<v1_0_0::VideoSwitchingSetData>,
 }
 
@@ -39,3 +41,22 @@ impl From<PresentationData> for v1_1_0::PresentationData {
         }
     }
 }
+
+impl From<v1_1_0::PresentationData> for PresentationData {
+    fn from(input: v1_1_0::PresentationData) -> Self {
+        Self {
+            id: input.id,
+            time_bounds: input.time_bounds,
+            audio: input
+                .audio
+                .into_iter()
+                .map(v1_0_0::AudioSwitchingSetData::from)
+                .collect(),
+            current_time: input.current_time,
+            base_url: input.base_url,
+            events: input.events,
+            metadata: input.metadata,
+            video: input.video,
+        }
+    }
+}
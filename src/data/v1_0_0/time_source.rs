@@ -1,8 +1,10 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+use crate::{Error, Result};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 pub struct TimeSource {
     pub scheme: Uuid,
     pub url: Url,
@@ -18,10 +20,49 @@ impl From<TimeSource> for crate::TimeSource {
     }
 }
 
+impl TryFrom<crate::TimeSource> for TimeSource {
+    type Error = Error;
+
+    fn try_from(input: crate::TimeSource) -> Result<Self> {
+        let scheme = Uuid::parse_str(input.scheme.as_str())
+            .map_err(|_| Error::UnsupportedTimeSourceScheme(input.scheme.to_string()))?;
+        Ok(Self {
+            scheme,
+            url: Url::parse(&input.value)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn convert_time_source() -> anyhow::Result<()> {
+        let source = crate::TimeSource {
+            scheme: Url::parse("urn:uuid:2f7f5b6a-54e2-4b1f-9cf3-3b2a0d7a7f11")?,
+            value: "https://time.example.com/".to_owned(),
+        };
+
+        let legacy = TimeSource::try_from(source.clone())?;
+
+        assert_eq!(crate::TimeSource::from(legacy), source);
+        Ok(())
+    }
+
+    #[test]
+    fn convert_time_source_without_uuid() -> anyhow::Result<()> {
+        let source = crate::TimeSource {
+            scheme: Url::parse("urn:mpeg:dash:utc:ntp:2014")?,
+            value: "https://time.example.com".to_owned(),
+        };
+
+        let result = TimeSource::try_from(source);
+
+        assert!(matches!(result, Err(Error::UnsupportedTimeSourceScheme(_))));
+        Ok(())
+    }
+
     #[test]
     fn invalid_uuid_in_time_source() {
         let data = r#"
@@ -1,13 +1,15 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::v2_0_0;
 use crate::util::{UInt, Uri};
 use crate::{
-    FrameRate, Resolution, ScaledDuration, ScaledValue, SegmentId, Segments,
+    Error, FrameRate, Resolution, Result, ScaledDuration, ScaledValue, SegmentId, Segments,
     SwitchingSetProtection, VideoMimeType,
 };
 
-#[derive(Deserialize, Debug, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct VideoSwitchingSetData {
     pub id: String,
@@ -24,7 +26,8 @@ pub struct VideoSwitchingSetData {
     pub protection: Option<SwitchingSetProtection>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct VideoTrackData {
     pub id: String,
@@ -91,3 +94,76 @@ impl From<VideoTrackData> for v2_0_0::VideoTrackData {
         }
     }
 }
+
+impl TryFrom<v2_0_0::VideoSwitchingSetData> for VideoSwitchingSetData {
+    type Error = Error;
+
+    fn try_from(input: v2_0_0::VideoSwitchingSetData) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            tracks: input
+                .tracks
+                .into_iter()
+                .map(VideoTrackData::try_from)
+                .collect::<Result<_>>()?,
+            align_id: input.align_id,
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            frame_rate: input.frame_rate,
+            initialization_pattern: input.initialization_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            mime_type: input.mime_type,
+            protection: input.protection,
+        })
+    }
+}
+
+impl TryFrom<v2_0_0::VideoTrackData> for VideoTrackData {
+    type Error = Error;
+
+    #[allow(deprecated)]
+    fn try_from(input: v2_0_0::VideoTrackData) -> Result<Self> {
+        let (active_segment, active_sequence_number) = legacy_active_segment(
+            &input.id,
+            (input.start_segment_id, input.start_sequence_number),
+            input.active_segment_id,
+            input.active_sequence_number,
+        )?;
+        Ok(Self {
+            id: input.id,
+            bandwidth: input.bandwidth,
+            resolution: input.resolution,
+            segments: input.segments,
+            active_segment,
+            active_sequence_number,
+            average_bandwidth: input.average_bandwidth,
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            frame_rate: input.frame_rate,
+            label: input.label,
+            initialization_pattern: input.initialization_pattern,
+            media_time_offset: input.media_time_offset,
+            segment_duration: input.segment_duration,
+        })
+    }
+}
+
+/// Maps the `startSegmentId`/`startSequenceNumber` pair of a 2.0.0 track onto the
+/// `activeSegment`/`activeSequenceNumber` pair of the legacy versions.
+///
+/// Both pairs link a segment id to its sequence number, so only one of them can be carried over.
+pub fn legacy_active_segment(
+    track_id: &str,
+    start: (SegmentId, UInt),
+    active_segment: Option<SegmentId>,
+    active_sequence_number: Option<UInt>,
+) -> Result<(Option<SegmentId>, Option<UInt>)> {
+    match (active_segment, active_sequence_number) {
+        _ if start == Default::default() => Ok((active_segment, active_sequence_number)),
+        (None, None) => Ok((Some(start.0), Some(start.1))),
+        _ => Err(Error::UnsupportedStartSegment(track_id.to_owned())),
+    }
+}
@@ -1,13 +1,15 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
-use crate::data::v2_0_0;
+use crate::data::{v1_0_0, v2_0_0};
 use crate::util::{UInt, Uri};
 use crate::{
-    AudioMimeType, Language, SamplesPerFrame, ScaledDuration, ScaledValue, SegmentId, Segments,
-    SwitchingSetProtection,
+    AudioMimeType, Error, Language, Result, SamplesPerFrame, ScaledDuration, ScaledValue,
+    SegmentId, Segments, SwitchingSetProtection,
 };
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioSwitchingSetData {
     pub id: String,
@@ -53,7 +55,8 @@ impl From<AudioSwitchingSetData> for v2_0_0::AudioSwitchingSetData {
     }
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioTrackData {
     pub id: String,
@@ -99,3 +102,63 @@ impl From<AudioTrackData> for v2_0_0::AudioTrackData {
         }
     }
 }
+
+impl TryFrom<v2_0_0::AudioSwitchingSetData> for AudioSwitchingSetData {
+    type Error = Error;
+
+    fn try_from(input: v2_0_0::AudioSwitchingSetData) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            language: input.language,
+            tracks: input
+                .tracks
+                .into_iter()
+                .map(AudioTrackData::try_from)
+                .collect::<Result<_>>()?,
+            align_id: input.align_id,
+            base_url: input.base_url,
+            channels: input.channels,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            samples_per_frame: input.samples_per_frame,
+            initialization_pattern: input.initialization_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            mime_type: input.mime_type,
+            protection: input.protection,
+            sample_rate: input.sample_rate,
+        })
+    }
+}
+
+impl TryFrom<v2_0_0::AudioTrackData> for AudioTrackData {
+    type Error = Error;
+
+    #[allow(deprecated)]
+    fn try_from(input: v2_0_0::AudioTrackData) -> Result<Self> {
+        let (active_segment, active_sequence_number) = v1_0_0::legacy_active_segment(
+            &input.id,
+            (input.start_segment_id, input.start_sequence_number),
+            input.active_segment_id,
+            input.active_sequence_number,
+        )?;
+        Ok(Self {
+            id: input.id,
+            bandwidth: input.bandwidth,
+            segments: input.segments,
+            active_segment,
+            active_sequence_number,
+            average_bandwidth: input.average_bandwidth,
+            base_url: input.base_url,
+            channels: input.channels,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            label: input.label,
+            initialization_pattern: input.initialization_pattern,
+            media_time_offset: input.media_time_offset,
+            sample_rate: input.sample_rate,
+            samples_per_frame: input.samples_per_frame,
+            segment_duration: input.segment_duration,
+        })
+    }
+}
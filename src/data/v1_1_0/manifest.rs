@@ -1,10 +1,12 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::{v1_1_0, v2_0_0};
 use crate::util::{Timestamp, UInt, Uri};
 use crate::{Error, Result};
 
-#[derive(Deserialize, Debug, Clone)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ManifestData {
     pub creation_date: Timestamp,
@@ -45,3 +47,37 @@ impl TryFrom<ManifestData> for v2_0_0::ManifestData {
         })
     }
 }
+
+impl TryFrom<v2_0_0::ManifestData> for ManifestData {
+    type Error = Error;
+
+    fn try_from(value: v2_0_0::ManifestData) -> Result<Self> {
+        let current_time = match &value.stream_type {
+            crate::StreamType::Live(live) => {
+                Some((live.active_presentation.clone(), live.current_time))
+            }
+            crate::StreamType::Vod => None,
+        };
+        let stream_type = value.stream_type.try_into()?;
+        let mut presentations = value
+            .presentations
+            .into_iter()
+            .map(v1_1_0::PresentationData::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        if let Some((active_presentation, current_time)) = current_time {
+            let presentation = presentations
+                .iter_mut()
+                .find(|p| p.id == active_presentation)
+                .ok_or(Error::InvalidActivePresentationId(active_presentation))?;
+            presentation.validate_legacy_active()?;
+            presentation.current_time = Some(current_time);
+        }
+        Ok(Self {
+            creation_date: value.creation_date,
+            fallback_poll_rate: value.fallback_poll_rate,
+            presentations,
+            stream_type,
+            content_base_url: value.content_base_url,
+        })
+    }
+}
@@ -1,24 +1,26 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 
 use crate::data::{v1_0_0, v1_1_0, v2_0_0};
 use crate::util::Uri;
 use crate::{Error, Result};
 use crate::{PresentationEvent, TimeBounds, UnsignedScaledValue};
 
-#[derive(Clone, Deserialize, Debug)]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PresentationData {
     pub id: String,
     pub time_bounds: TimeBounds,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub audio: Vec<v1_1_0::AudioSwitchingSetData>,
     pub base_url: Option<Uri>,
     pub current_time: Option<UnsignedScaledValue>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub events: Vec<PresentationEvent>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub metadata: Vec<v1_0_0::MetadataSwitchingSetData>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub video: Vec<v1_0_0::VideoSwitchingSetData>,
 }
 
@@ -68,3 +70,32 @@ impl From<PresentationData> for v2_0_0::PresentationData {
         }
     }
 }
+
+impl TryFrom<v2_0_0::PresentationData> for PresentationData {
+    type Error = Error;
+
+    fn try_from(input: v2_0_0::PresentationData) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            time_bounds: input.time_bounds,
+            audio: input
+                .audio
+                .into_iter()
+                .map(v1_1_0::AudioSwitchingSetData::try_from)
+                .collect::<Result<_>>()?,
+            base_url: input.base_url,
+            current_time: None,
+            events: input.events,
+            metadata: input
+                .metadata
+                .into_iter()
+                .map(v1_0_0::MetadataSwitchingSetData::from)
+                .collect(),
+            video: input
+                .video
+                .into_iter()
+                .map(v1_0_0::VideoSwitchingSetData::try_from)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
@@ -1,9 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::data::{v1_0_0, v1_1_0};
 use crate::{Error, Result, ScaledDuration};
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LiveStream {
     pub availability_duration: ScaledDuration,
@@ -12,7 +12,7 @@ pub struct LiveStream {
     pub time_source: Option<v1_0_0::TimeSource>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "streamType", rename_all = "lowercase")]
 pub enum StreamType {
     Live(LiveStream),
@@ -44,3 +44,21 @@ impl StreamType {
         })
     }
 }
+
+impl TryFrom<crate::StreamType> for StreamType {
+    type Error = Error;
+
+    fn try_from(input: crate::StreamType) -> Result<Self> {
+        Ok(match input {
+            crate::StreamType::Live(live) => Self::Live(LiveStream {
+                availability_duration: live.availability_duration,
+                active_presentation: live.active_presentation,
+                time_source: live
+                    .time_source
+                    .map(v1_0_0::TimeSource::try_from)
+                    .transpose()?,
+            }),
+            crate::StreamType::Vod => Self::Vod,
+        })
+    }
+}
@@ -123,7 +123,7 @@ impl From<AudioTrack> for AudioTrackData {
             average_bandwidth: input.average_bandwidth.map(UInt::from),
             base_url,
             channels: input.channels.map(UInt::from),
-            codecs: Some(input.codecs),
+            codecs: Some(input.codecs.into()),
             continuation_pattern: Some(continuation_pattern),
             samples_per_frame: Some(input.samples_per_frame),
             label: input.label,
@@ -139,7 +139,7 @@ impl AudioTrackData {
     #[must_use]
     pub fn with_default_codecs(mut self, codecs: &Option<String>) -> Self {
         if self.codecs.is_none() {
-            self.codecs = codecs.clone();
+            self.codecs.clone_from(codecs);
         }
         self
     }
@@ -150,7 +150,7 @@ impl AudioTrackData {
         continuation_pattern: &Option<String>,
     ) -> Self {
         if self.continuation_pattern.is_none() {
-            self.continuation_pattern = continuation_pattern.clone();
+            self.continuation_pattern.clone_from(continuation_pattern);
         }
         self
     }
@@ -161,7 +161,8 @@ impl AudioTrackData {
         initialization_pattern: &Option<String>,
     ) -> Self {
         if self.initialization_pattern.is_none() {
-            self.initialization_pattern = initialization_pattern.clone();
+            self.initialization_pattern
+                .clone_from(initialization_pattern);
         }
         self
     }
@@ -92,7 +92,7 @@ impl MetadataTrackData {
     #[must_use]
     pub fn with_default_codecs(mut self, codecs: &Option<String>) -> Self {
         if self.codecs.is_none() {
-            self.codecs = codecs.clone();
+            self.codecs.clone_from(codecs);
         }
         self
     }
@@ -103,7 +103,7 @@ impl MetadataTrackData {
         continuation_pattern: &Option<String>,
     ) -> Self {
         if self.continuation_pattern.is_none() {
-            self.continuation_pattern = continuation_pattern.clone();
+            self.continuation_pattern.clone_from(continuation_pattern);
         }
         self
     }
@@ -33,11 +33,7 @@ impl From<Presentation> for PresentationData {
                 .map(AudioSwitchingSetData::from)
                 .collect(),
             base_url: None,
-            events: input
-                .events
-                .into_iter()
-                .map(PresentationEvent::from)
-                .collect(),
+            events: input.events.into_iter().collect(),
             metadata: input
                 .metadata
                 .into_iter()
@@ -115,7 +115,7 @@ impl From<VideoTrack> for VideoTrackData {
             active_sequence_number: input.active_sequence_number.map(UInt::from),
             average_bandwidth: input.average_bandwidth.map(UInt::from),
             base_url,
-            codecs: Some(input.codecs),
+            codecs: Some(input.codecs.into()),
             continuation_pattern: Some(continuation_pattern),
             frame_rate: Some(input.frame_rate),
             label: input.label,
@@ -130,7 +130,7 @@ impl VideoTrackData {
     #[must_use]
     pub fn with_default_codecs(mut self, codecs: &Option<String>) -> Self {
         if self.codecs.is_none() {
-            self.codecs = codecs.clone();
+            self.codecs.clone_from(codecs);
         }
         self
     }
@@ -141,7 +141,7 @@ impl VideoTrackData {
         continuation_pattern: &Option<String>,
     ) -> Self {
         if self.continuation_pattern.is_none() {
-            self.continuation_pattern = continuation_pattern.clone();
+            self.continuation_pattern.clone_from(continuation_pattern);
         }
         self
     }
@@ -152,7 +152,8 @@ impl VideoTrackData {
         initialization_pattern: &Option<String>,
     ) -> Self {
         if self.initialization_pattern.is_none() {
-            self.initialization_pattern = initialization_pattern.clone();
+            self.initialization_pattern
+                .clone_from(initialization_pattern);
         }
         self
     }
@@ -10,6 +10,10 @@ pub enum Error {
     NullScale(),
     #[error("activePresentation references unknown id {0}")]
     InvalidActivePresentationId(String),
+    #[error("Presentation with id {0} is active so it cannot be removed")]
+    CannotRemoveActivePresentation(String),
+    #[error("Presentation with id {0} is active but has no audio or video tracks")]
+    NoActiveTracks(String),
     #[error("'{0}' is not a valid audio MIME Type")]
     InvalidAudioMime(String),
     #[error("'{0}' is not a valid video MIME Type")]
@@ -22,6 +26,22 @@ pub enum Error {
         "Presentation with id {0} is active but its currentTime is earlier than its startTime"
     )]
     ImpossibleCurrentTime(String),
+    #[error("No presentation is playing at {0}")]
+    NoPresentationAt(crate::UnsignedScaledValue),
+    #[error("Presentation with id {0} must have a startTime and an endTime to be spliced")]
+    MissingPresentationDuration(String),
+    #[error("Presentation with id {0} cannot resume after its endTime")]
+    InvalidResumeOffset(String),
+    #[error("Segment time bounds of presentation {0} cannot be moved to the splice point")]
+    InvalidSegmentTimeBounds(String),
+    #[error("Presentation with id {0} cannot end at the splice point")]
+    InvalidSplicePoint(String),
+    #[error("Cannot start at the live edge of a VOD stream")]
+    NoLiveEdge,
+    #[error("Track {0} does not exist")]
+    UnknownTrack(crate::TrackUid),
+    #[error("Track {0} has no segment at {1}")]
+    NoSegmentAt(crate::TrackUid, crate::UnsignedScaledValue),
     #[error("segment id's must be incremented by one: {1} must not follow {0}")]
     InvalidSegmentIds(SegmentId, SegmentId),
     #[error("SwitchingSetProtection must contain at least one system")]
@@ -36,6 +56,16 @@ pub enum Error {
     MissingSegmentDuration(String),
     #[error("Track {0} is active so it must have an active sequence number")]
     MissingActiveSequenceNumber(String),
+    #[error("Track {0} has both a start and an active segment, which cannot be represented before manifest version 2.0.0")]
+    UnsupportedStartSegment(String),
+    #[error("timeSource scheme {0} must be a UUID URN before manifest version 2.0.0")]
+    UnsupportedTimeSourceScheme(String),
+    #[error("Manifest version {0} is deprecated, use 2.0.0 instead")]
+    DeprecatedManifestVersion(crate::ManifestVersion),
+    #[error("'{0}' is not a valid manifest version")]
+    InvalidManifestVersion(String),
+    #[error("'{0}' is not a valid RFC 6381 codec")]
+    InvalidCodecs(String),
     #[error("Track {0} must have codecs")]
     MissingCodecs(String),
     #[error("Track {0} must have a continuation pattern")]
@@ -46,6 +76,8 @@ pub enum Error {
     MissingSampleRate(String),
     #[error("Track {0} must have an initialization pattern")]
     MissingInitializationPattern(String),
+    #[error("Track {higher} has a higher bandwidth than track {lower} but a lower resolution")]
+    NonMonotonicLadder { lower: String, higher: String },
     #[error("Ids must be unique (found duplicate: {0})")]
     DuplicateId(String),
     #[error("Pattern '{0}' must contain {1}")]
@@ -62,10 +94,38 @@ pub enum Error {
     MissingFairplayAttribute(&'static str),
     #[error("Invalid Fairplay scheme ID")]
     FairplaySchemeId,
+    #[error("Invalid MPD: {0}")]
+    InvalidMpd(String),
+    #[error("Segment template '{0}' is not supported")]
+    UnsupportedSegmentTemplate(String),
+    #[error("Invalid base64 event data: {0}")]
+    InvalidEventData(#[from] base64::DecodeError),
+    #[error("Invalid emsg box: {0}")]
+    InvalidEmsg(&'static str),
+    #[error("emsg box version {0} is not supported")]
+    UnsupportedEmsgVersion(u8),
+    #[error("Invalid MP4 box: {0}")]
+    InvalidMp4(&'static str),
+    #[error("Missing {0} box")]
+    MissingMp4Box(&'static str),
+    #[error("Track {0} has no initialization segment")]
+    NoInitSegment(crate::TrackUid),
+    #[error("'{0}' is not a supported UTC timing scheme")]
+    UnknownTimingScheme(String),
+    #[error("Invalid time from time source: {0}")]
+    InvalidTimingResponse(String),
+    #[error("Failed to synchronise with {0}: {1}")]
+    ClockSync(String, Box<dyn std::error::Error + Send + Sync>),
+    #[error("Failed to fetch {0}: {1}")]
+    Fetch(url::Url, Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
     #[error(transparent)]
     UrlParseError(#[from] url::ParseError),
     #[error(transparent)]
     InvalidJson(#[from] serde_path_to_error::Error<serde_json::Error>),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
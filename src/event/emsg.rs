@@ -0,0 +1,289 @@
+use crate::event::HespEvent;
+use crate::{Error, Result};
+
+const BOX_TYPE: &[u8; 4] = b"emsg";
+
+/// The presentation time of an [`Emsg`], which depends on the box version.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EmsgTime {
+    /// Version 0: relative to the earliest presentation time of the segment.
+    Delta(u32),
+    /// Version 1: on the media timeline of the track.
+    Absolute(u64),
+}
+
+/// The message data of an [`Emsg`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum EmsgMessage<'a> {
+    Hesp(HespEvent),
+    /// Message data of another scheme, or of a value the HESP scheme does not define.
+    Raw(&'a [u8]),
+}
+
+/// An ISO-BMFF event message box (ISO/IEC 23009-1 section 5.10.3.3).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Emsg {
+    pub scheme_id_uri: String,
+    pub value: String,
+    pub timescale: u32,
+    pub time: EmsgTime,
+    pub event_duration: u32,
+    pub id: u32,
+    pub message_data: Vec<u8>,
+}
+
+impl Emsg {
+    /// Creates a box carrying a HESP event.
+    #[must_use]
+    pub fn hesp(event: &HespEvent, timescale: u32, time: EmsgTime, id: u32) -> Self {
+        Self {
+            scheme_id_uri: HespEvent::SCHEME_ID_URI.to_owned(),
+            value: event.value().to_owned(),
+            timescale,
+            time,
+            event_duration: 0,
+            id,
+            message_data: event.message_data(),
+        }
+    }
+
+    /// Parses a complete `emsg` box, including its header.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut reader = Reader(data);
+        let size = reader.u32()?;
+        if reader.take(4)? != BOX_TYPE {
+            return Err(Error::InvalidEmsg("box type is not 'emsg'"));
+        }
+        let size = match size {
+            0 => data.len(),
+            1 => {
+                let size = reader.u64()?;
+                usize::try_from(size).map_err(|_| Error::InvalidEmsg("box is too large"))?
+            }
+            size => size as usize,
+        };
+        let header = data.len() - reader.0.len();
+        if size < header || size > data.len() {
+            return Err(Error::InvalidEmsg("box size does not match the data"));
+        }
+        reader.0 = &data[header..size];
+        let version = reader.u8()?;
+        reader.take(3)?;
+        match version {
+            0 => {
+                let scheme_id_uri = reader.string()?;
+                let value = reader.string()?;
+                Ok(Self {
+                    scheme_id_uri,
+                    value,
+                    timescale: reader.u32()?,
+                    time: EmsgTime::Delta(reader.u32()?),
+                    event_duration: reader.u32()?,
+                    id: reader.u32()?,
+                    message_data: reader.0.to_vec(),
+                })
+            }
+            1 => {
+                let timescale = reader.u32()?;
+                let time = EmsgTime::Absolute(reader.u64()?);
+                let event_duration = reader.u32()?;
+                let id = reader.u32()?;
+                Ok(Self {
+                    scheme_id_uri: reader.string()?,
+                    value: reader.string()?,
+                    timescale,
+                    time,
+                    event_duration,
+                    id,
+                    message_data: reader.0.to_vec(),
+                })
+            }
+            version => Err(Error::UnsupportedEmsgVersion(version)),
+        }
+    }
+
+    /// The box version, which follows from the kind of [`EmsgTime`].
+    #[must_use]
+    pub const fn version(&self) -> u8 {
+        match self.time {
+            EmsgTime::Delta(_) => 0,
+            EmsgTime::Absolute(_) => 1,
+        }
+    }
+
+    /// Decodes the message data as a [`HespEvent`] if the box is of the HESP scheme.
+    pub fn message(&self) -> Result<EmsgMessage<'_>> {
+        if self.scheme_id_uri == HespEvent::SCHEME_ID_URI {
+            if let Some(event) = HespEvent::decode(&self.value, &self.message_data)? {
+                return Ok(EmsgMessage::Hesp(event));
+            }
+        }
+        Ok(EmsgMessage::Raw(&self.message_data))
+    }
+
+    /// Serializes the box, including its header.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = vec![self.version(), 0, 0, 0];
+        match self.time {
+            EmsgTime::Delta(delta) => {
+                put_string(&mut body, &self.scheme_id_uri);
+                put_string(&mut body, &self.value);
+                body.extend(self.timescale.to_be_bytes());
+                body.extend(delta.to_be_bytes());
+                body.extend(self.event_duration.to_be_bytes());
+                body.extend(self.id.to_be_bytes());
+            }
+            EmsgTime::Absolute(time) => {
+                body.extend(self.timescale.to_be_bytes());
+                body.extend(time.to_be_bytes());
+                body.extend(self.event_duration.to_be_bytes());
+                body.extend(self.id.to_be_bytes());
+                put_string(&mut body, &self.scheme_id_uri);
+                put_string(&mut body, &self.value);
+            }
+        }
+        body.extend(&self.message_data);
+        let mut data = Vec::with_capacity(body.len() + 16);
+        if let Ok(size) = u32::try_from(body.len() + 8) {
+            data.extend(size.to_be_bytes());
+            data.extend(BOX_TYPE);
+        } else {
+            data.extend(1u32.to_be_bytes());
+            data.extend(BOX_TYPE);
+            data.extend((body.len() as u64 + 16).to_be_bytes());
+        }
+        data.extend(body);
+        data
+    }
+}
+
+fn put_string(data: &mut Vec<u8>, value: &str) {
+    data.extend(value.as_bytes());
+    data.push(0);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    const fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::InvalidEmsg("box is truncated"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self
+            .0
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or(Error::InvalidEmsg("string is not null-terminated"))?;
+        let value = std::str::from_utf8(self.take(len)?)
+            .map_err(|_| Error::InvalidEmsg("string is not valid UTF-8"))?
+            .to_owned();
+        self.take(1)?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::event::{InitData, ManifestUpdate};
+
+    use super::*;
+
+    #[test]
+    fn round_trip_hesp_events() -> anyhow::Result<()> {
+        let update = HespEvent::from(ManifestUpdate {
+            url: Some(Url::parse("https://example.com/manifest.json")?),
+        });
+        let init_data = HespEvent::from(InitData {
+            index: 3,
+            offset: 1024,
+        });
+
+        for emsg in [
+            Emsg::hesp(&update, 90_000, EmsgTime::Delta(0), 1),
+            Emsg::hesp(
+                &init_data,
+                90_000,
+                EmsgTime::Absolute(u64::from(u32::MAX) + 1),
+                2,
+            ),
+        ] {
+            let parsed = Emsg::parse(&emsg.to_bytes())?;
+            assert_eq!(parsed, emsg);
+            assert!(matches!(parsed.message()?, EmsgMessage::Hesp(_)));
+        }
+        let parsed = Emsg::parse(&Emsg::hesp(&update, 1, EmsgTime::Delta(0), 1).to_bytes())?;
+        assert_eq!(parsed.message()?, EmsgMessage::Hesp(update));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_version_0() -> anyhow::Result<()> {
+        let mut data = vec![0, 0, 0, 0];
+        data.extend(b"emsg\0\0\0\0urn:example\0v\0");
+        data.extend([
+            0, 0, 0, 10, 0, 0, 0, 20, 0, 0, 0, 30, 0, 0, 0, 40, 0xde, 0xad,
+        ]);
+        data[3] = data.len().try_into()?;
+
+        let emsg = Emsg::parse(&data)?;
+
+        assert_eq!(emsg.scheme_id_uri, "urn:example");
+        assert_eq!(emsg.value, "v");
+        assert_eq!(emsg.timescale, 10);
+        assert_eq!(emsg.time, EmsgTime::Delta(20));
+        assert_eq!(emsg.event_duration, 30);
+        assert_eq!(emsg.id, 40);
+        assert_eq!(emsg.message()?, EmsgMessage::Raw(&[0xde, 0xad]));
+        assert_eq!(emsg.to_bytes(), data);
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_boxes() {
+        let emsg = Emsg::hesp(
+            &HespEvent::from(InitData {
+                index: 0,
+                offset: 0,
+            }),
+            1,
+            EmsgTime::Delta(0),
+            0,
+        );
+        let data = emsg.to_bytes();
+
+        assert!(matches!(
+            Emsg::parse(&data[..data.len() - 1]),
+            Err(Error::InvalidEmsg(_))
+        ));
+        let mut moof = data.clone();
+        moof[4..8].copy_from_slice(b"moof");
+        assert!(matches!(Emsg::parse(&moof), Err(Error::InvalidEmsg(_))));
+        let mut version = data;
+        version[8] = 2;
+        assert!(matches!(
+            Emsg::parse(&version),
+            Err(Error::UnsupportedEmsgVersion(2))
+        ));
+    }
+}
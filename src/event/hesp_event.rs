@@ -0,0 +1,56 @@
+use crate::event::{InitData, ManifestUpdate, SCHEME_ID_URI};
+use crate::Result;
+
+/// An in-band event of the HESP scheme.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum HespEvent {
+    InitData(InitData),
+    ManifestUpdate(ManifestUpdate),
+}
+
+impl HespEvent {
+    pub const SCHEME_ID_URI: &'static str = SCHEME_ID_URI;
+
+    /// Decodes the JSON message data of an event with the given `value`.
+    ///
+    /// Returns `None` for values the HESP scheme does not define.
+    pub fn decode(value: &str, message_data: &[u8]) -> Result<Option<Self>> {
+        Ok(match value {
+            InitData::VALUE => Some(Self::InitData(serde_json::from_slice(message_data)?)),
+            ManifestUpdate::VALUE => {
+                Some(Self::ManifestUpdate(serde_json::from_slice(message_data)?))
+            }
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> &'static str {
+        match self {
+            Self::InitData(_) => InitData::VALUE,
+            Self::ManifestUpdate(_) => ManifestUpdate::VALUE,
+        }
+    }
+
+    /// Encodes the event as JSON message data.
+    #[must_use]
+    pub fn message_data(&self) -> Vec<u8> {
+        match self {
+            Self::InitData(data) => serde_json::to_vec(data),
+            Self::ManifestUpdate(update) => serde_json::to_vec(update),
+        }
+        .expect("event payloads always serialize")
+    }
+}
+
+impl From<InitData> for HespEvent {
+    fn from(data: InitData) -> Self {
+        Self::InitData(data)
+    }
+}
+
+impl From<ManifestUpdate> for HespEvent {
+    fn from(update: ManifestUpdate) -> Self {
+        Self::ManifestUpdate(update)
+    }
+}
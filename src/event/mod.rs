@@ -1,6 +1,10 @@
+pub use emsg::*;
+pub use hesp_event::HespEvent;
 pub use init_data::InitData;
 pub use manifest_update::ManifestUpdate;
 
+mod emsg;
+mod hesp_event;
 mod init_data;
 mod manifest_update;
 
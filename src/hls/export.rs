@@ -0,0 +1,472 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use super::media::{media_playlist, Rendition};
+use super::{Hls, MediaPlaylist, VERSION};
+use crate::{
+    AudioSwitchingSet, AudioTrack, InitializableTrack, Manifest, MediaType, SwitchingSet, Track,
+    VideoTrack,
+};
+
+/// Renders `manifest` as HLS playlists.
+///
+/// Every video and audio track gets a media playlist that spans all presentations, separated by
+/// discontinuities. The multivariant playlist offers each video track in combination with each
+/// audio group, where an audio group holds the tracks with the same codecs and bandwidth rank
+/// across switching sets.
+/// Metadata tracks are not carried over.
+#[must_use]
+pub fn to_hls(manifest: &Manifest) -> Hls {
+    let mut videos: Vec<Variant<&VideoTrack>> = Vec::new();
+    let mut audios: Vec<Variant<(&AudioSwitchingSet, &AudioTrack)>> = Vec::new();
+    let presentations = manifest
+        .presentations()
+        .sorted_by_key(|presentation| presentation.time_bounds().start_time());
+    for presentation in presentations {
+        for set in presentation.video() {
+            for track in set.tracks() {
+                let rendition = Rendition {
+                    presentation,
+                    track: track as &dyn InitializableTrack,
+                    protection: set.protection.as_ref(),
+                };
+                Variant::push(&mut videos, track, track, rendition);
+            }
+        }
+        for set in presentation.audio() {
+            for track in set.tracks() {
+                let rendition = Rendition {
+                    presentation,
+                    track: track as &dyn InitializableTrack,
+                    protection: set.protection.as_ref(),
+                };
+                Variant::push(&mut audios, track, (set, track), rendition);
+            }
+        }
+    }
+
+    let mut playlist = String::new();
+    writeln!(playlist, "#EXTM3U").unwrap();
+    writeln!(playlist, "#EXT-X-VERSION:{VERSION}").unwrap();
+    writeln!(playlist, "#EXT-X-INDEPENDENT-SEGMENTS").unwrap();
+    let groups = audios
+        .iter()
+        .into_group_map_by(|audio| {
+            let (set, track) = audio.track;
+            let rank = set
+                .tracks()
+                .filter(|other| other.bandwidth < track.bandwidth)
+                .count();
+            (track.codecs.as_str(), rank)
+        })
+        .into_iter()
+        .sorted_by_key(|(_, audios)| audios[0].index)
+        .map(|((codecs, rank), audios)| (format!("audio-{codecs}-{rank}"), audios))
+        .collect_vec();
+    for (group_id, audios) in &groups {
+        for (index, audio) in audios.iter().enumerate() {
+            let (set, _) = audio.track;
+            let mut attributes = vec![
+                "TYPE=AUDIO".to_owned(),
+                format!("GROUP-ID=\"{group_id}\""),
+                format!("NAME=\"{}\"", audio_name(set)),
+            ];
+            attributes.push(format!("LANGUAGE=\"{}\"", set.language.code()));
+            let default = if index == 0 { "YES" } else { "NO" };
+            attributes.push(format!("DEFAULT={default}"));
+            attributes.push("AUTOSELECT=YES".to_owned());
+            if let Some(channels) = audio.track.1.channels.or(set.channels) {
+                attributes.push(format!("CHANNELS=\"{channels}\""));
+            }
+            attributes.push(format!("URI=\"{}\"", audio.uri));
+            writeln!(playlist, "#EXT-X-MEDIA:{}", attributes.join(",")).unwrap();
+        }
+    }
+    for video in &videos {
+        let track = video.track;
+        if groups.is_empty() {
+            write_stream_inf(&mut playlist, &video_stream(track), &video.uri);
+        }
+        for (group_id, audios) in &groups {
+            let mut stream = video_stream(track);
+            stream.add_audio(group_id, audios.iter().map(|audio| audio.track.1));
+            write_stream_inf(&mut playlist, &stream, &video.uri);
+        }
+    }
+    if videos.is_empty() {
+        for audio in &audios {
+            let (_, track) = audio.track;
+            let stream = Stream {
+                bandwidth: track.bandwidth,
+                average_bandwidth: track.average_bandwidth,
+                codecs: vec![track.codecs.to_string()],
+                attributes: Vec::new(),
+            };
+            write_stream_inf(&mut playlist, &stream, &audio.uri);
+        }
+    }
+
+    let media_playlists = videos
+        .iter()
+        .map(|video| video.media_playlist(manifest))
+        .chain(audios.iter().map(|audio| audio.media_playlist(manifest)))
+        .collect();
+    Hls {
+        multivariant_playlist: playlist,
+        media_playlists,
+    }
+}
+
+/// A track that is matched across presentations by its media type, switching set id and id.
+struct Variant<'a, T> {
+    index: usize,
+    uri: String,
+    track: T,
+    renditions: Vec<Rendition<'a>>,
+}
+
+impl<'a, T> Variant<'a, T> {
+    fn push(variants: &mut Vec<Self>, track: &dyn Track, value: T, rendition: Rendition<'a>) {
+        let uid = track.uid();
+        let uri = playlist_uri(uid.media_type(), uid.switching_set_id(), uid.track_id());
+        match variants.iter_mut().find(|variant| variant.uri == uri) {
+            Some(variant) => variant.renditions.push(rendition),
+            None => variants.push(Self {
+                index: variants.len(),
+                uri,
+                track: value,
+                renditions: vec![rendition],
+            }),
+        }
+    }
+
+    fn media_playlist(&self, manifest: &Manifest) -> MediaPlaylist {
+        MediaPlaylist {
+            uri: self.uri.clone(),
+            playlist: media_playlist(manifest, &self.renditions),
+        }
+    }
+}
+
+fn playlist_uri(media_type: MediaType, switching_set_id: &str, track_id: &str) -> String {
+    format!("{media_type}_{switching_set_id}_{track_id}.m3u8")
+}
+
+fn audio_name(set: &AudioSwitchingSet) -> String {
+    set.label.clone().unwrap_or_else(|| set.language.code())
+}
+
+/// The attributes of an `EXT-X-STREAM-INF` tag.
+struct Stream {
+    bandwidth: u64,
+    average_bandwidth: Option<u64>,
+    codecs: Vec<String>,
+    attributes: Vec<String>,
+}
+
+impl Stream {
+    fn add_audio<'a>(&mut self, group_id: &str, tracks: impl IntoIterator<Item = &'a AudioTrack>) {
+        let tracks = tracks.into_iter().collect_vec();
+        self.bandwidth += tracks
+            .iter()
+            .map(|track| track.bandwidth)
+            .max()
+            .unwrap_or(0);
+        self.average_bandwidth = self.average_bandwidth.map(|average| {
+            average
+                + tracks
+                    .iter()
+                    .map(|track| track.average_bandwidth.unwrap_or(track.bandwidth))
+                    .max()
+                    .unwrap_or(0)
+        });
+        for track in tracks {
+            if !self
+                .codecs
+                .iter()
+                .any(|codecs| codecs == track.codecs.as_str())
+            {
+                self.codecs.push(track.codecs.to_string());
+            }
+        }
+        self.attributes.push(format!("AUDIO=\"{group_id}\""));
+    }
+}
+
+fn video_stream(track: &VideoTrack) -> Stream {
+    Stream {
+        bandwidth: track.bandwidth,
+        average_bandwidth: track.average_bandwidth,
+        codecs: vec![track.codecs.to_string()],
+        attributes: vec![
+            format!(
+                "RESOLUTION={}x{}",
+                track.resolution.width(),
+                track.resolution.height()
+            ),
+            format!("FRAME-RATE={:.3}", track.frame_rate.to_f64()),
+        ],
+    }
+}
+
+fn write_stream_inf(playlist: &mut String, stream: &Stream, uri: &str) {
+    let mut attributes = vec![format!("BANDWIDTH={}", stream.bandwidth)];
+    if let Some(average_bandwidth) = stream.average_bandwidth {
+        attributes.push(format!("AVERAGE-BANDWIDTH={average_bandwidth}"));
+    }
+    attributes.push(format!("CODECS=\"{}\"", stream.codecs.join(",")));
+    attributes.extend(stream.attributes.iter().cloned());
+    writeln!(playlist, "#EXT-X-STREAM-INF:{}", attributes.join(",")).unwrap();
+    writeln!(playlist, "{uri}").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+
+    const FAIRPLAY: &str = r#"
+        "protection": {
+            "type": "cbcs",
+            "systems": [
+                {
+                    "schemeId": "94ce86fb-07ff-4f43-adb8-93d2fa968ca2",
+                    "uri": "skd://key",
+                    "keyformat": "com.apple.streamingkeydelivery",
+                    "keyformatversions": "1"
+                }
+            ]
+        },"#;
+
+    fn presentation(id: &str, time_bounds: &str, segment: &str) -> String {
+        format!(
+            r#"
+            {{
+                "id": "{id}",
+                "timeBounds": {time_bounds},
+                "video": [
+                    {{
+                        "id": "main",
+                        "frameRate": {{"value": 30000, "scale": 1001}},
+                        "codecs": "avc1.4d001f",
+                        {FAIRPLAY}
+                        "continuationPattern": "video/{{segmentId}}.mp4",
+                        "initializationPattern": "video/init-{{initId}}.mp4",
+                        "tracks": [
+                            {{
+                                "id": "720p",
+                                "bandwidth": 3000000,
+                                "averageBandwidth": 2500000,
+                                "resolution": {{"width": 1280, "height": 720}},
+                                "segmentDuration": {{"value": 2}},
+                                "segments": [{segment}]
+                            }}
+                        ]
+                    }}
+                ],
+                "audio": [
+                    {{
+                        "id": "en",
+                        "language": "eng",
+                        "codecs": "mp4a.40.2",
+                        "sampleRate": 48000,
+                        "channels": 2,
+                        "continuationPattern": "audio/en/{{segmentId}}.mp4",
+                        "initializationPattern": "audio/en/init-{{initId}}.mp4",
+                        "tracks": [
+                            {{
+                                "id": "128k",
+                                "bandwidth": 128000,
+                                "segmentDuration": {{"value": 2}},
+                                "segments": [{segment}]
+                            }}
+                        ]
+                    }},
+                    {{
+                        "id": "nl",
+                        "language": "nld",
+                        "label": "Nederlands",
+                        "codecs": "mp4a.40.2",
+                        "sampleRate": 48000,
+                        "continuationPattern": "audio/nl/{{segmentId}}.mp4",
+                        "initializationPattern": "audio/nl/init-{{initId}}.mp4",
+                        "tracks": [
+                            {{
+                                "id": "96k",
+                                "bandwidth": 96000,
+                                "segmentDuration": {{"value": 2}},
+                                "segments": [{segment}]
+                            }}
+                        ]
+                    }}
+                ]
+            }}"#
+        )
+    }
+
+    #[test]
+    fn vod_playlists() -> anyhow::Result<()> {
+        let data = format!(
+            r#"
+            {{
+                "creationDate": "2021-03-31T08:00:00.000Z",
+                "fallbackPollRate": 300,
+                "manifestVersion": "2.0.0",
+                "streamType": "vod",
+                "presentations": [{},{}]
+            }}"#,
+            presentation(
+                "0",
+                r#"{"startTime": 0, "endTime": 5}"#,
+                r#"{"id": 1, "timeBounds": {"startTime": 0}}"#
+            ),
+            presentation(
+                "1",
+                r#"{"startTime": 5, "endTime": 9}"#,
+                r#"{"id": 1, "timeBounds": {"startTime": 5}}"#
+            ),
+        );
+        let manifest =
+            Manifest::from_json(Url::parse("http://localhost/vod/manifest.json")?, &data)?;
+
+        let hls = to_hls(&manifest);
+
+        let multivariant = hls.multivariant_playlist();
+        assert!(
+            multivariant.contains(
+                r#"#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="audio-mp4a.40.2-0",NAME="Nederlands",LANGUAGE="nld","#
+            ),
+            "{multivariant}"
+        );
+        assert!(multivariant.contains(r#"CHANNELS="2",URI="audio_en_128k.m3u8""#));
+        assert!(multivariant.contains(r#"DEFAULT=NO,AUTOSELECT=YES,URI="audio_nl_96k.m3u8""#));
+        assert_eq!(multivariant.matches("#EXT-X-STREAM-INF").count(), 1);
+        assert!(multivariant.contains(concat!(
+            r#"#EXT-X-STREAM-INF:BANDWIDTH=3128000,AVERAGE-BANDWIDTH=2628000,"#,
+            r#"CODECS="avc1.4d001f,mp4a.40.2",RESOLUTION=1280x720,FRAME-RATE=29.970,"#,
+            "AUDIO=\"audio-mp4a.40.2-0\"\nvideo_main_720p.m3u8\n",
+        )));
+        assert_eq!(hls.media_playlists().len(), 3);
+        let video = hls
+            .media_playlist("video_main_720p.m3u8")
+            .unwrap()
+            .playlist();
+        assert_eq!(
+            video,
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-VERSION:7\n",
+                "#EXT-X-TARGETDURATION:2\n",
+                "#EXT-X-MEDIA-SEQUENCE:1\n",
+                "#EXT-X-PLAYLIST-TYPE:VOD\n",
+                "#EXT-X-KEY:METHOD=SAMPLE-AES,URI=\"skd://key\",",
+                "KEYFORMAT=\"com.apple.streamingkeydelivery\",KEYFORMATVERSIONS=\"1\"\n",
+                "#EXT-X-MAP:URI=\"video/init-0.mp4\"\n",
+                "#EXTINF:2.000,\nvideo/1.mp4\n",
+                "#EXTINF:2.000,\nvideo/2.mp4\n",
+                "#EXTINF:1.000,\nvideo/3.mp4\n",
+                "#EXT-X-DISCONTINUITY\n",
+                "#EXT-X-MAP:URI=\"video/init-0.mp4\"\n",
+                "#EXTINF:2.000,\nvideo/1.mp4\n",
+                "#EXTINF:2.000,\nvideo/2.mp4\n",
+                "#EXT-X-ENDLIST\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn live_media_playlist() -> anyhow::Result<()> {
+        let data = format!(
+            r#"
+            {{
+                "availabilityDuration": {{"value": 6}},
+                "creationDate": "2021-03-31T08:00:00.000Z",
+                "fallbackPollRate": 10,
+                "manifestVersion": "2.0.0",
+                "streamType": "live",
+                "activePresentation": "0",
+                "currentTime": {{"value": 3600}},
+                "presentations": [{}]
+            }}"#,
+            presentation(
+                "0",
+                r#"{"startTime": 0}"#,
+                r#"{"id": 1795, "timeBounds": {"startTime": 3590}}"#
+            ),
+        );
+        let manifest =
+            Manifest::from_json(Url::parse("http://localhost/live/manifest.json")?, &data)?;
+
+        let hls = to_hls(&manifest);
+
+        let audio = hls.media_playlist("audio_nl_96k.m3u8").unwrap().playlist();
+        assert_eq!(
+            audio,
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-VERSION:7\n",
+                "#EXT-X-TARGETDURATION:2\n",
+                "#EXT-X-MEDIA-SEQUENCE:1797\n",
+                "#EXT-X-DISCONTINUITY-SEQUENCE:0\n",
+                "#EXT-X-MAP:URI=\"audio/nl/init-0.mp4\"\n",
+                "#EXT-X-PROGRAM-DATE-TIME:2021-03-31T07:59:54.000Z\n",
+                "#EXTINF:2.000,\naudio/nl/1797.mp4\n",
+                "#EXTINF:2.000,\naudio/nl/1798.mp4\n",
+                "#EXTINF:2.000,\naudio/nl/1799.mp4\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn live_media_sequence_continues_across_presentations() -> anyhow::Result<()> {
+        let data = format!(
+            r#"
+            {{
+                "availabilityDuration": {{"value": 6}},
+                "creationDate": "2021-03-31T08:00:00.000Z",
+                "fallbackPollRate": 10,
+                "manifestVersion": "2.0.0",
+                "streamType": "live",
+                "activePresentation": "1",
+                "currentTime": {{"value": 3600}},
+                "presentations": [{},{}]
+            }}"#,
+            presentation(
+                "0",
+                r#"{"startTime": 0, "endTime": 3594}"#,
+                r#"{"id": 1795, "timeBounds": {"startTime": 3588}}"#
+            ),
+            presentation(
+                "1",
+                r#"{"startTime": 3594}"#,
+                r#"{"id": 1, "timeBounds": {"startTime": 3594}}"#
+            ),
+        );
+        let manifest =
+            Manifest::from_json(Url::parse("http://localhost/live/manifest.json")?, &data)?;
+
+        let hls = to_hls(&manifest);
+
+        let audio = hls.media_playlist("audio_nl_96k.m3u8").unwrap().playlist();
+        assert_eq!(
+            audio,
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-VERSION:7\n",
+                "#EXT-X-TARGETDURATION:2\n",
+                "#EXT-X-MEDIA-SEQUENCE:1798\n",
+                "#EXT-X-DISCONTINUITY-SEQUENCE:1\n",
+                "#EXT-X-MAP:URI=\"audio/nl/init-0.mp4\"\n",
+                "#EXT-X-PROGRAM-DATE-TIME:2021-03-31T07:59:54.000Z\n",
+                "#EXTINF:2.000,\naudio/nl/1.mp4\n",
+                "#EXTINF:2.000,\naudio/nl/2.mp4\n",
+                "#EXTINF:2.000,\naudio/nl/3.mp4\n",
+            )
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt::Write;
+
+use chrono::{SecondsFormat, TimeDelta, Utc};
+
+use super::VERSION;
+use crate::{
+    segment_times, InitializableTrack, LiveStream, Manifest, Presentation, ProtectionScheme,
+    ProtectionSystemAttributes, SegmentId, StreamType, SwitchingSetProtection, UnsignedScaledValue,
+};
+
+/// A track as it appears in a single presentation.
+#[derive(Clone, Copy)]
+pub struct Rendition<'a> {
+    pub presentation: &'a Presentation,
+    pub track: &'a dyn InitializableTrack,
+    pub protection: Option<&'a SwitchingSetProtection>,
+}
+
+struct MediaSegment {
+    id: SegmentId,
+    start: UnsignedScaledValue,
+    end: UnsignedScaledValue,
+}
+
+/// Renders the media playlist of a track that spans `renditions`, one per presentation.
+pub fn media_playlist(manifest: &Manifest, renditions: &[Rendition]) -> String {
+    let live = match manifest.stream_type() {
+        StreamType::Live(live) => Some(live),
+        StreamType::Vod => None,
+    };
+    let parts: Vec<_> = renditions
+        .iter()
+        .enumerate()
+        .map(|(index, rendition)| (index, rendition, media_segments(rendition, live)))
+        .filter(|(_, _, segments)| !segments.is_empty())
+        .collect();
+    let target_duration = parts
+        .iter()
+        .flat_map(|(_, _, segments)| segments)
+        .filter_map(|segment| segment.end.checked_sub(segment.start))
+        .map(|duration| duration.to_f64().ceil())
+        .fold(1.0, f64::max);
+    // each rendition before the first listed one ended with a discontinuity
+    let (media_sequence, discontinuity_sequence) =
+        parts.first().map_or((0, 0), |(index, _, segments)| {
+            (media_sequence(renditions, *index, segments[0].id), *index)
+        });
+
+    let mut playlist = String::new();
+    writeln!(playlist, "#EXTM3U").unwrap();
+    writeln!(playlist, "#EXT-X-VERSION:{VERSION}").unwrap();
+    writeln!(playlist, "#EXT-X-TARGETDURATION:{target_duration}").unwrap();
+    writeln!(playlist, "#EXT-X-MEDIA-SEQUENCE:{media_sequence}").unwrap();
+    if live.is_some() {
+        writeln!(
+            playlist,
+            "#EXT-X-DISCONTINUITY-SEQUENCE:{discontinuity_sequence}"
+        )
+        .unwrap();
+    } else {
+        writeln!(playlist, "#EXT-X-PLAYLIST-TYPE:VOD").unwrap();
+    }
+    let mut key = None;
+    for (part, (_, rendition, segments)) in parts.iter().enumerate() {
+        if part > 0 {
+            writeln!(playlist, "#EXT-X-DISCONTINUITY").unwrap();
+        }
+        let rendition_key = rendition.protection.and_then(key_tag);
+        if rendition_key != key {
+            let tag = rendition_key.as_deref().unwrap_or("#EXT-X-KEY:METHOD=NONE");
+            writeln!(playlist, "{tag}").unwrap();
+            key = rendition_key;
+        }
+        let track = rendition.track;
+        let map = track
+            .initialization_pattern()
+            .clone()
+            .into_full_pattern()
+            .replace("{initId}", &track.start_sequence_number().to_string());
+        writeln!(playlist, "#EXT-X-MAP:URI=\"{map}\"").unwrap();
+        if let Some(live) = live {
+            let start = segments[0].start;
+            writeln!(
+                playlist,
+                "#EXT-X-PROGRAM-DATE-TIME:{}",
+                program_date_time(manifest, live, start)
+            )
+            .unwrap();
+        }
+        let pattern = track.continuation_pattern().clone().into_full_pattern();
+        for segment in segments {
+            let duration = segment
+                .end
+                .checked_sub(segment.start)
+                .unwrap_or_default()
+                .to_f64();
+            writeln!(playlist, "#EXTINF:{duration:.3},").unwrap();
+            writeln!(
+                playlist,
+                "{}",
+                pattern.replace("{segmentId}", &segment.id.to_string())
+            )
+            .unwrap();
+        }
+    }
+    if live.is_none() {
+        writeln!(playlist, "#EXT-X-ENDLIST").unwrap();
+    }
+    playlist
+}
+
+/// Lists the segments of a rendition that can be fetched.
+///
+/// Segments after the last listed one are extrapolated with the segment duration of the track,
+/// up to the end of the presentation or, for live streams, up to the current time.
+fn media_segments(rendition: &Rendition, live: Option<&LiveStream>) -> Vec<MediaSegment> {
+    let track = rendition.track;
+    let bounds = rendition.presentation.time_bounds();
+    let end = match (live, bounds.end_time()) {
+        (Some(live), Some(end)) => Some(end.min(live.current_time)),
+        (Some(live), None) => Some(live.current_time),
+        (None, end) => end,
+    };
+    let available_from = live
+        .and_then(|live| {
+            live.current_time
+                .checked_sub(live.availability_duration.into())
+        })
+        .unwrap_or_default();
+    let mut segments: Vec<_> = segment_times(track.segments(), track.segment_duration())
+        .map(|(id, start, end)| MediaSegment { id, start, end })
+        .collect();
+    if let (Some(end), Some(duration)) = (end, track.segment_duration()) {
+        let duration = UnsignedScaledValue::from(duration);
+        let mut next = segments.last().map_or_else(
+            || {
+                (
+                    track.start_segment_id(),
+                    bounds.start_time().unwrap_or_default(),
+                )
+            },
+            |last| (last.id.next(), last.end),
+        );
+        while next.1 < end && duration.value != 0 {
+            let Some(segment_end) = next.1.checked_add(duration) else {
+                break;
+            };
+            segments.push(MediaSegment {
+                id: next.0,
+                start: next.1,
+                end: segment_end,
+            });
+            next = (next.0.next(), segment_end);
+        }
+    }
+    segments.retain(|segment| segment.end > available_from);
+    match (end, live) {
+        (Some(end), Some(_)) => segments.retain(|segment| segment.end <= end),
+        (Some(end), None) => {
+            segments.retain(|segment| segment.start < end);
+            for segment in &mut segments {
+                segment.end = segment.end.min(end);
+            }
+        }
+        (None, _) => {}
+    }
+    segments
+}
+
+/// The media sequence number of the segment `segment_id` of `renditions[index]`.
+///
+/// Segment ids restart in every presentation, so the segments of the earlier presentations are
+/// counted in front of it. The sequence starts at the first segment id of the first presentation.
+fn media_sequence(renditions: &[Rendition], index: usize, segment_id: SegmentId) -> u64 {
+    let first = renditions
+        .first()
+        .map_or(0, |rendition| u64::from(first_segment_id(rendition)));
+    let earlier: u64 = renditions[..index].iter().map(segment_count).sum();
+    let skipped = u64::from(segment_id).saturating_sub(first_segment_id(&renditions[index]).into());
+    first + earlier + skipped
+}
+
+/// The id of the segment at the start of the presentation, also when it is no longer listed.
+fn first_segment_id(rendition: &Rendition) -> SegmentId {
+    let track = rendition.track;
+    let Some((id, start, _)) = segment_times(track.segments(), track.segment_duration()).next()
+    else {
+        return track.start_segment_id();
+    };
+    let presentation_start = rendition
+        .presentation
+        .time_bounds()
+        .start_time()
+        .unwrap_or_default();
+    let skipped = track
+        .segment_duration()
+        .map(UnsignedScaledValue::from)
+        .filter(|duration| duration.value != 0)
+        .and_then(|duration| start.checked_sub(presentation_start)?.checked_div(duration))
+        .map_or(0, UnsignedScaledValue::floor);
+    u64::from(id).saturating_sub(skipped).into()
+}
+
+/// The number of segments of a rendition over its whole presentation.
+fn segment_count(rendition: &Rendition) -> u64 {
+    media_segments(rendition, None).last().map_or(0, |last| {
+        (u64::from(last.id) + 1).saturating_sub(first_segment_id(rendition).into())
+    })
+}
+
+fn key_tag(protection: &SwitchingSetProtection) -> Option<String> {
+    let fairplay = protection
+        .systems()
+        .iter()
+        .find_map(|system| match system.attributes() {
+            ProtectionSystemAttributes::Fairplay(fairplay) => Some(fairplay),
+            ProtectionSystemAttributes::Generic { .. } => None,
+        })?;
+    let method = match protection.scheme() {
+        ProtectionScheme::Cbcs => "SAMPLE-AES",
+        ProtectionScheme::Cenc => "SAMPLE-AES-CTR",
+    };
+    Some(format!(
+        "#EXT-X-KEY:METHOD={method},URI=\"{}\",KEYFORMAT=\"{}\",KEYFORMATVERSIONS=\"{}\"",
+        fairplay.uri, fairplay.keyformat, fairplay.keyformatversions
+    ))
+}
+
+/// The wall clock time of `time`, relative to the `currentTime` at the `creationDate`.
+fn program_date_time(manifest: &Manifest, live: &LiveStream, time: UnsignedScaledValue) -> String {
+    let offset = millis(time) - millis(live.current_time);
+    let date_time = i64::try_from(offset)
+        .ok()
+        .and_then(TimeDelta::try_milliseconds)
+        .and_then(|offset| manifest.creation_date.checked_add_signed(offset))
+        .unwrap_or(manifest.creation_date);
+    date_time
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn millis(value: UnsignedScaledValue) -> i128 {
+    i128::from(value.value) * 1000 / i128::from(u64::from(value.scale))
+}
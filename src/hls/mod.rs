@@ -0,0 +1,53 @@
+pub use export::to_hls;
+
+mod export;
+mod media;
+
+pub const VERSION: u8 = 7;
+
+/// The HLS rendition of a manifest: one multivariant playlist referring to a media playlist per
+/// video and audio track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hls {
+    multivariant_playlist: String,
+    media_playlists: Vec<MediaPlaylist>,
+}
+
+impl Hls {
+    #[must_use]
+    pub fn multivariant_playlist(&self) -> &str {
+        &self.multivariant_playlist
+    }
+
+    #[must_use]
+    pub fn media_playlists(&self) -> &[MediaPlaylist] {
+        &self.media_playlists
+    }
+
+    #[must_use]
+    pub fn media_playlist(&self, uri: &str) -> Option<&MediaPlaylist> {
+        self.media_playlists
+            .iter()
+            .find(|playlist| playlist.uri == uri)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaPlaylist {
+    uri: String,
+    playlist: String,
+}
+
+impl MediaPlaylist {
+    /// The URI by which the multivariant playlist refers to this playlist, relative to the
+    /// location of the manifest.
+    #[must_use]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    #[must_use]
+    pub fn playlist(&self) -> &str {
+        &self.playlist
+    }
+}
@@ -3,8 +3,14 @@ extern crate core;
 pub use error::{Error, Result};
 pub use model::*;
 
+pub mod clock;
+pub mod cmaf;
+pub mod dash;
 pub mod data;
 mod error;
 pub mod event;
+pub mod hls;
+pub mod lint;
 mod model;
+pub mod poller;
 pub mod util;
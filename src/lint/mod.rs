@@ -0,0 +1,266 @@
+use std::fmt;
+
+pub use rules::*;
+
+use crate::{Manifest, Severity};
+
+mod rules;
+
+/// A conformance rule that flags manifests which are valid but suspicious.
+pub trait Lint {
+    /// The kebab-case name identifying the rule in its diagnostics.
+    fn name(&self) -> &'static str;
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic>;
+}
+
+/// A problem reported by a [`Lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    rule: &'static str,
+    severity: Severity,
+    location: String,
+    message: String,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for the entity at `location`, which is a presentation id, a
+    /// `presentation/mediaType/switchingSet` path or a track uid, or empty for the manifest.
+    #[must_use]
+    pub const fn new(
+        rule: &'static str,
+        severity: Severity,
+        location: String,
+        message: String,
+    ) -> Self {
+        Self {
+            rule,
+            severity,
+            location,
+            message,
+        }
+    }
+
+    #[must_use]
+    pub const fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    #[must_use]
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.severity, self.rule)?;
+        if !self.location.is_empty() {
+            write!(f, " {}", self.location)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Runs a set of [`Lint`] rules, by default all rules of this crate.
+pub struct Linter {
+    rules: Vec<Box<dyn Lint + Send + Sync>>,
+}
+
+impl Linter {
+    /// A linter without any rules.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    #[must_use]
+    pub fn rule(mut self, rule: impl Lint + Send + Sync + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Disables the rule with the given name.
+    #[must_use]
+    pub fn without(mut self, name: &str) -> Self {
+        self.rules.retain(|rule| rule.name() != name);
+        self
+    }
+
+    #[must_use]
+    pub fn rules(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    #[must_use]
+    pub fn lint(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.check(manifest))
+            .collect()
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::empty()
+            .rule(AverageBandwidth)
+            .rule(FrameRates)
+            .rule(SegmentAlignment)
+            .rule(AlignIds)
+            .rule(PresentationOverlap)
+            .rule(JsSafeIntegers)
+    }
+}
+
+/// Checks `manifest` against the default rules.
+#[must_use]
+pub fn lint(manifest: &Manifest) -> Vec<Diagnostic> {
+    Linter::default().lint(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::Track;
+
+    const MANIFEST: &str = r#"
+        {
+            "manifestVersion": "2.0.0",
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "streamType": "vod",
+            "presentations": [
+                {
+                    "id": "0",
+                    "timeBounds": {"startTime": 0, "endTime": 10},
+                    "video": [{
+                        "id": "main",
+                        "alignId": "a",
+                        "codecs": "avc1.4d001f",
+                        "continuationPattern": "content-{segmentId}.mp4",
+                        "initializationPattern": "init-{initId}.mp4",
+                        "tracks": [
+                            {
+                                "id": "720p",
+                                "bandwidth": 3000000,
+                                "averageBandwidth": 4000000,
+                                "frameRate": {"value": 25},
+                                "resolution": {"width": 1280, "height": 720},
+                                "segmentDuration": {"value": 2},
+                                "startSegmentId": 1,
+                                "segments": [{"id": 1}, {"id": 2}]
+                            },
+                            {
+                                "id": "1080p",
+                                "bandwidth": 9007199254740993,
+                                "frameRate": {"value": 30},
+                                "resolution": {"width": 1920, "height": 1080},
+                                "segmentDuration": {"value": 2},
+                                "startSegmentId": 2,
+                                "segments": [{"id": 2}]
+                            }
+                        ]
+                    }]
+                },
+                {
+                    "id": "1",
+                    "timeBounds": {"startTime": 5}
+                }
+            ]
+        }"#;
+
+    fn manifest() -> anyhow::Result<Manifest> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        Ok(Manifest::from_json(location, MANIFEST)?)
+    }
+
+    #[test]
+    fn default_rules() -> anyhow::Result<()> {
+        let diagnostics = lint(&manifest()?);
+
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule(), diagnostic.location()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("average-bandwidth", "0/video/main/720p"),
+                ("frame-rate", "0/video/main"),
+                ("segment-alignment", "0/video/main"),
+                ("segment-alignment", "0/video/main"),
+                ("align-id", "0/video/main"),
+                ("presentation-overlap", "1"),
+                ("js-safe-integer", "0/video/main/1080p"),
+            ]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity() == Severity::Warning));
+        assert_eq!(
+            diagnostics[6].to_string(),
+            "warning [js-safe-integer] 0/video/main/1080p: \
+             bandwidth 9007199254740993 cannot be represented by a JavaScript Number"
+        );
+        assert_eq!(
+            diagnostics[2].message(),
+            "tracks have different start segments: 1080p 2, 720p 1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn custom_rules() -> anyhow::Result<()> {
+        struct NoLabels;
+
+        impl Lint for NoLabels {
+            fn name(&self) -> &'static str {
+                "no-labels"
+            }
+
+            fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+                manifest
+                    .video_tracks()
+                    .map(|track| {
+                        Diagnostic::new(
+                            self.name(),
+                            Severity::Error,
+                            track.uid().to_string(),
+                            "track has no label".to_owned(),
+                        )
+                    })
+                    .collect()
+            }
+        }
+
+        let linter = Linter::default()
+            .without("js-safe-integer")
+            .without("segment-alignment")
+            .rule(NoLabels);
+
+        assert!(!linter.rules().contains(&"js-safe-integer"));
+        let diagnostics = linter.lint(&manifest()?);
+        assert_eq!(diagnostics.len(), 6);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.rule() == "no-labels")
+                .count(),
+            2
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use itertools::Itertools;
+use serde_json::Value;
+
+use super::{Diagnostic, Lint};
+use crate::util::{Entity, MAX_SAFE_INTEGER, MIN_SAFE_INTEGER};
+use crate::{
+    Initialization, Manifest, Presentation, Severity, SwitchingSet, Track, UnsignedScaledValue,
+};
+
+/// Flags tracks whose average bandwidth exceeds their peak bandwidth.
+pub struct AverageBandwidth;
+
+impl Lint for AverageBandwidth {
+    fn name(&self) -> &'static str {
+        "average-bandwidth"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        manifest
+            .presentations()
+            .flat_map(Presentation::tracks)
+            .filter_map(|track| {
+                let bandwidth = track.bandwidth()?;
+                let average = track.average_bandwidth()?;
+                (average > bandwidth).then(|| {
+                    warning(
+                        self,
+                        track.uid().to_string(),
+                        format!("averageBandwidth {average} exceeds bandwidth {bandwidth}"),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags video switching sets whose tracks have different frame rates.
+pub struct FrameRates;
+
+impl Lint for FrameRates {
+    fn name(&self) -> &'static str {
+        "frame-rate"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        manifest
+            .presentations()
+            .flat_map(|presentation| presentation.video().map(move |set| (presentation, set)))
+            .filter_map(|(presentation, set)| {
+                let frame_rates = set
+                    .tracks()
+                    .map(|track| UnsignedScaledValue::from(track.frame_rate()))
+                    .sorted()
+                    .dedup()
+                    .collect_vec();
+                (frame_rates.len() > 1).then(|| {
+                    warning(
+                        self,
+                        set_location(presentation, set),
+                        format!(
+                            "tracks have different frame rates: {}",
+                            frame_rates.iter().map(|rate| rate.to_f64()).join(", ")
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags switching sets whose tracks list a different number of segments, or start at a
+/// different segment.
+pub struct SegmentAlignment;
+
+impl Lint for SegmentAlignment {
+    fn name(&self) -> &'static str {
+        "segment-alignment"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for set in manifest.presentations().flat_map(switching_sets) {
+            let tracks = set
+                .tracks
+                .iter()
+                .sorted_by_key(|track| track.uid().track_id())
+                .collect_vec();
+            let mut check = |what: &str, value: &dyn Fn(&dyn Track) -> u64| {
+                if tracks.iter().map(|track| value(**track)).all_equal() {
+                    return;
+                }
+                let values = tracks
+                    .iter()
+                    .map(|track| format!("{} {}", track.uid().track_id(), value(**track)))
+                    .join(", ");
+                diagnostics.push(warning(
+                    self,
+                    set.location.clone(),
+                    format!("tracks have different {what}: {values}"),
+                ));
+            };
+            check("start segments", &|track| track.start_segment_id().into());
+            check("segment counts", &|track| track.segments().len() as u64);
+        }
+        diagnostics
+    }
+}
+
+/// Flags `alignId`s that no other switching set shares, so they align with nothing.
+pub struct AlignIds;
+
+impl Lint for AlignIds {
+    fn name(&self) -> &'static str {
+        "align-id"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let sets = manifest
+            .presentations()
+            .flat_map(switching_sets)
+            .collect_vec();
+        let counts = sets.iter().filter_map(|set| set.align_id).counts();
+        sets.iter()
+            .filter_map(|set| {
+                let align_id = set.align_id?;
+                (counts[align_id] == 1).then(|| {
+                    warning(
+                        self,
+                        set.location.clone(),
+                        format!("no other switching set has alignId {align_id}"),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags presentations whose time bounds overlap those of another presentation.
+pub struct PresentationOverlap;
+
+impl Lint for PresentationOverlap {
+    fn name(&self) -> &'static str {
+        "presentation-overlap"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let presentations = manifest
+            .presentations()
+            .sorted_by_key(|presentation| presentation.time_bounds().start_time())
+            .collect_vec();
+        presentations
+            .iter()
+            .tuple_combinations()
+            .filter(|(a, b)| overlap(a, b))
+            .map(|(a, b)| {
+                warning(
+                    self,
+                    b.id().to_owned(),
+                    format!("timeBounds overlap those of presentation {}", a.id()),
+                )
+            })
+            .collect()
+    }
+}
+
+fn overlap(a: &Presentation, b: &Presentation) -> bool {
+    let (a, b) = (a.time_bounds(), b.time_bounds());
+    let before = |start: Option<UnsignedScaledValue>, end: Option<UnsignedScaledValue>| {
+        end.is_none_or(|end| start.unwrap_or(UnsignedScaledValue::ZERO) < end)
+    };
+    before(a.start_time(), b.end_time()) && before(b.start_time(), a.end_time())
+}
+
+/// Flags numbers that a JavaScript player cannot represent exactly.
+pub struct JsSafeIntegers;
+
+impl Lint for JsSafeIntegers {
+    fn name(&self) -> &'static str {
+        "js-safe-integer"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if let Ok(value) = serde_json::to_value(manifest) {
+            self.walk(&value, "", "", &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+impl JsSafeIntegers {
+    /// Checks all numbers in `value`, reporting them at the innermost entity that contains them.
+    fn walk(&self, value: &Value, location: &str, field: &str, diagnostics: &mut Vec<Diagnostic>) {
+        match value {
+            Value::Number(number) => {
+                // numbers beyond `i64` are unsigned integers above the limit, or floats
+                let safe = number.as_i64().map_or_else(
+                    || number.is_f64(),
+                    |number| (MIN_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&number),
+                );
+                if !safe {
+                    diagnostics.push(warning(
+                        self,
+                        location.to_owned(),
+                        format!("{field} {number} cannot be represented by a JavaScript Number"),
+                    ));
+                }
+            }
+            Value::Array(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    let id = value.get("id").and_then(Value::as_str);
+                    match (field, id) {
+                        ("presentations", Some(id)) => self.walk(value, id, "", diagnostics),
+                        ("audio" | "video" | "metadata" | "tracks", Some(id)) => {
+                            let location = if field == "tracks" {
+                                format!("{location}/{id}")
+                            } else {
+                                format!("{location}/{field}/{id}")
+                            };
+                            self.walk(value, &location, "", diagnostics);
+                        }
+                        _ => {
+                            let field = format!("{field}[{index}]");
+                            self.walk(value, location, &field, diagnostics);
+                        }
+                    }
+                }
+            }
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    let field = if field.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{field}.{key}")
+                    };
+                    self.walk(value, location, &field, diagnostics);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A switching set of any media type.
+struct SwitchingSetView<'a> {
+    location: String,
+    align_id: Option<&'a str>,
+    tracks: Vec<&'a dyn Track>,
+}
+
+fn switching_sets(presentation: &Presentation) -> Vec<SwitchingSetView<'_>> {
+    let audio = presentation
+        .audio()
+        .map(|set| view(presentation, set, set.align_id.as_deref()));
+    let video = presentation
+        .video()
+        .map(|set| view(presentation, set, set.align_id.as_deref()));
+    let metadata = presentation
+        .metadata()
+        .map(|set| view(presentation, set, set.align_id.as_deref()));
+    audio.chain(video).chain(metadata).collect()
+}
+
+fn view<'a, S: SwitchingSet>(
+    presentation: &Presentation,
+    set: &'a S,
+    align_id: Option<&'a str>,
+) -> SwitchingSetView<'a> {
+    SwitchingSetView {
+        location: set_location(presentation, set),
+        align_id,
+        tracks: set.tracks().map(|track| track as &dyn Track).collect(),
+    }
+}
+
+fn set_location<S: SwitchingSet>(presentation: &Presentation, set: &S) -> String {
+    format!("{}/{}/{}", presentation.id(), set.media_type(), set.id())
+}
+
+fn warning(rule: &impl Lint, location: String, message: String) -> Diagnostic {
+    Diagnostic::new(rule.name(), Severity::Warning, location, message)
+}
@@ -19,7 +19,7 @@ impl Address {
         }
         if let Some(Uri::Relative(path)) = &base_url {
             manifest_location.join(path)?;
-        };
+        }
         Ok(Self {
             manifest_location,
             uri: base_url,
@@ -49,7 +49,7 @@ impl Address {
     }
 
     #[must_use]
-    pub fn url(&self) -> Cow<Url> {
+    pub fn url(&self) -> Cow<'_, Url> {
         match &self.uri {
             Some(Uri::Absolute(url)) => Cow::Borrowed(url),
             Some(Uri::Relative(path)) => Cow::Owned(self.manifest_location.join(path).unwrap()),
@@ -70,7 +70,7 @@ impl Address {
     pub fn set_uri(&mut self, uri: Option<Uri>) -> Result<()> {
         if let Some(Uri::Relative(path)) = &uri {
             self.manifest_location.join(path)?;
-        };
+        }
         self.uri = uri;
         Ok(())
     }
@@ -0,0 +1,316 @@
+use crate::data::{AudioSwitchingSetData, AudioTrackData};
+use crate::util::{UInt, Uri};
+use crate::{
+    AudioMimeType, Error, Language, Result, SamplesPerFrame, ScaledDuration, ScaledValue, Segment,
+    SegmentId, SwitchingSetProtection,
+};
+
+#[derive(Debug, Clone)]
+pub struct AudioSwitchingSetBuilder {
+    id: String,
+    language: Language,
+    tracks: Vec<AudioTrackBuilder>,
+    align_id: Option<String>,
+    base_url: Option<Uri>,
+    channels: Option<u64>,
+    codecs: Option<String>,
+    continuation_pattern: Option<String>,
+    samples_per_frame: Option<SamplesPerFrame>,
+    initialization_pattern: Option<String>,
+    label: Option<String>,
+    media_time_offset: Option<ScaledValue>,
+    mime_type: Option<AudioMimeType>,
+    protection: Option<SwitchingSetProtection>,
+    sample_rate: Option<u64>,
+}
+
+impl AudioSwitchingSetBuilder {
+    #[must_use]
+    pub fn new(id: impl Into<String>, language: Language) -> Self {
+        Self {
+            id: id.into(),
+            language,
+            tracks: Vec::new(),
+            align_id: None,
+            base_url: None,
+            channels: None,
+            codecs: None,
+            continuation_pattern: None,
+            samples_per_frame: None,
+            initialization_pattern: None,
+            label: None,
+            media_time_offset: None,
+            mime_type: None,
+            protection: None,
+            sample_rate: None,
+        }
+    }
+
+    #[must_use]
+    pub fn track(mut self, track: AudioTrackBuilder) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    #[must_use]
+    pub fn align_id(mut self, align_id: impl Into<String>) -> Self {
+        self.align_id = Some(align_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub const fn channels(mut self, channels: u64) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    #[must_use]
+    pub fn codecs(mut self, codecs: impl Into<String>) -> Self {
+        self.codecs = Some(codecs.into());
+        self
+    }
+
+    #[must_use]
+    pub fn continuation_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.continuation_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn samples_per_frame(mut self, samples_per_frame: SamplesPerFrame) -> Self {
+        self.samples_per_frame = Some(samples_per_frame);
+        self
+    }
+
+    #[must_use]
+    pub fn initialization_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.initialization_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(mut self, media_time_offset: ScaledValue) -> Self {
+        self.media_time_offset = Some(media_time_offset);
+        self
+    }
+
+    #[must_use]
+    pub fn mime_type(mut self, mime_type: AudioMimeType) -> Self {
+        self.mime_type = Some(mime_type);
+        self
+    }
+
+    #[must_use]
+    pub fn protection(mut self, protection: SwitchingSetProtection) -> Self {
+        self.protection = Some(protection);
+        self
+    }
+
+    #[must_use]
+    pub const fn sample_rate(mut self, sample_rate: u64) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+}
+
+impl TryFrom<AudioSwitchingSetBuilder> for AudioSwitchingSetData {
+    type Error = Error;
+
+    fn try_from(input: AudioSwitchingSetBuilder) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            language: input.language,
+            tracks: input
+                .tracks
+                .into_iter()
+                .map(AudioTrackData::try_from)
+                .collect::<Result<_>>()?,
+            align_id: input.align_id,
+            base_url: input.base_url,
+            channels: input.channels.map(UInt::from),
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            samples_per_frame: input.samples_per_frame,
+            initialization_pattern: input.initialization_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            mime_type: input.mime_type,
+            protection: input.protection,
+            sample_rate: input.sample_rate.map(UInt::from),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioTrackBuilder {
+    id: String,
+    bandwidth: u64,
+    segments: Vec<Segment>,
+    start_segment_id: SegmentId,
+    start_sequence_number: u64,
+    average_bandwidth: Option<u64>,
+    base_url: Option<Uri>,
+    channels: Option<u64>,
+    codecs: Option<String>,
+    continuation_pattern: Option<String>,
+    samples_per_frame: Option<SamplesPerFrame>,
+    label: Option<String>,
+    initialization_pattern: Option<String>,
+    media_time_offset: Option<ScaledValue>,
+    sample_rate: Option<u64>,
+    segment_duration: Option<ScaledDuration>,
+}
+
+impl AudioTrackBuilder {
+    #[must_use]
+    pub fn new(id: impl Into<String>, bandwidth: u64) -> Self {
+        Self {
+            id: id.into(),
+            bandwidth,
+            segments: Vec::new(),
+            start_segment_id: SegmentId::default(),
+            start_sequence_number: 0,
+            average_bandwidth: None,
+            base_url: None,
+            channels: None,
+            codecs: None,
+            continuation_pattern: None,
+            samples_per_frame: None,
+            label: None,
+            initialization_pattern: None,
+            media_time_offset: None,
+            sample_rate: None,
+            segment_duration: None,
+        }
+    }
+
+    #[must_use]
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    #[must_use]
+    pub fn segments<I: IntoIterator<Item = Segment>>(mut self, segments: I) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    #[must_use]
+    pub const fn start_segment_id(mut self, start_segment_id: SegmentId) -> Self {
+        self.start_segment_id = start_segment_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn start_sequence_number(mut self, start_sequence_number: u64) -> Self {
+        self.start_sequence_number = start_sequence_number;
+        self
+    }
+
+    #[must_use]
+    pub const fn average_bandwidth(mut self, average_bandwidth: u64) -> Self {
+        self.average_bandwidth = Some(average_bandwidth);
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub const fn channels(mut self, channels: u64) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    #[must_use]
+    pub fn codecs(mut self, codecs: impl Into<String>) -> Self {
+        self.codecs = Some(codecs.into());
+        self
+    }
+
+    #[must_use]
+    pub fn continuation_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.continuation_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn samples_per_frame(mut self, samples_per_frame: SamplesPerFrame) -> Self {
+        self.samples_per_frame = Some(samples_per_frame);
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub fn initialization_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.initialization_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(mut self, media_time_offset: ScaledValue) -> Self {
+        self.media_time_offset = Some(media_time_offset);
+        self
+    }
+
+    #[must_use]
+    pub const fn sample_rate(mut self, sample_rate: u64) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    #[must_use]
+    pub const fn segment_duration(mut self, segment_duration: ScaledDuration) -> Self {
+        self.segment_duration = Some(segment_duration);
+        self
+    }
+}
+
+impl TryFrom<AudioTrackBuilder> for AudioTrackData {
+    type Error = Error;
+
+    fn try_from(input: AudioTrackBuilder) -> Result<Self> {
+        #[allow(deprecated)]
+        Ok(Self {
+            id: input.id,
+            bandwidth: input.bandwidth.into(),
+            segments: input.segments.try_into()?,
+            start_segment_id: input.start_segment_id,
+            active_segment_id: None,
+            start_sequence_number: input.start_sequence_number.into(),
+            active_sequence_number: None,
+            average_bandwidth: input.average_bandwidth.map(UInt::from),
+            base_url: input.base_url,
+            channels: input.channels.map(UInt::from),
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            samples_per_frame: input.samples_per_frame,
+            label: input.label,
+            initialization_pattern: input.initialization_pattern,
+            media_time_offset: input.media_time_offset,
+            sample_rate: input.sample_rate.map(UInt::from),
+            segment_duration: input.segment_duration,
+        })
+    }
+}
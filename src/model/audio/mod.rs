@@ -1,7 +1,9 @@
+pub use builder::*;
 pub use mime::AudioMimeType;
 pub use switching_set::*;
 pub use track::AudioTrack;
 
+mod builder;
 mod mime;
 mod switching_set;
 mod track;
@@ -10,6 +10,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct AudioSwitchingSet {
     pub(crate) id: String,
+    presentation_id: String,
     pub(crate) language: Language,
     pub(crate) tracks: EntityMap<AudioTrack>,
     pub(crate) align_id: Option<String>,
@@ -21,6 +22,58 @@ pub struct AudioSwitchingSet {
 
 impl AudioSwitchingSet {
     const MEDIA_TYPE: MediaType = MediaType::Audio;
+
+    pub(crate) fn reparent(&mut self, presentation_id: &str) {
+        presentation_id.clone_into(&mut self.presentation_id);
+        for track in &mut self.tracks {
+            track.reparent(presentation_id, &self.id);
+        }
+    }
+
+    #[must_use]
+    pub const fn language(&self) -> Language {
+        self.language
+    }
+
+    pub const fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    #[must_use]
+    pub const fn channels(&self) -> Option<u64> {
+        self.channels
+    }
+
+    pub const fn set_channels(&mut self, channels: Option<u64>) {
+        self.channels = channels;
+    }
+
+    #[must_use]
+    pub fn align_id(&self) -> Option<&str> {
+        self.align_id.as_deref()
+    }
+
+    pub fn set_align_id(&mut self, align_id: Option<String>) {
+        self.align_id = align_id;
+    }
+
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    #[must_use]
+    pub const fn protection(&self) -> Option<&SwitchingSetProtection> {
+        self.protection.as_ref()
+    }
+
+    pub fn set_protection(&mut self, protection: Option<SwitchingSetProtection>) {
+        self.protection = protection;
+    }
 }
 
 impl Entity for AudioSwitchingSet {
@@ -36,7 +89,7 @@ impl SwitchingSet for AudioSwitchingSet {
         Self::MEDIA_TYPE
     }
 
-    fn tracks(&self) -> EntityIter<AudioTrack> {
+    fn tracks(&self) -> EntityIter<'_, AudioTrack> {
         self.tracks.iter()
     }
 
@@ -48,10 +101,19 @@ impl SwitchingSet for AudioSwitchingSet {
         self.tracks.get_mut(id)
     }
 
-    fn tracks_mut(&mut self) -> EntityIterMut<AudioTrack> {
+    fn tracks_mut(&mut self) -> EntityIterMut<'_, AudioTrack> {
         self.tracks.iter_mut()
     }
 
+    fn insert_track(&mut self, mut track: AudioTrack) -> Result<()> {
+        track.reparent(&self.presentation_id, &self.id);
+        self.tracks.insert(track)
+    }
+
+    fn remove_track(&mut self, id: &str) -> Option<AudioTrack> {
+        self.tracks.remove(id)
+    }
+
     fn mime_type(&self) -> &str {
         self.mime_type.as_ref()
     }
@@ -107,6 +169,7 @@ impl AudioSwitchingSet {
             .into_entities()?;
         Ok(Self {
             id: data.id,
+            presentation_id: presentation_id.to_owned(),
             language: data.language,
             tracks,
             align_id: data.align_id,
@@ -151,4 +214,51 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn read_and_update_properties() -> anyhow::Result<()> {
+        let data = r#"
+            {
+                "id": "main",
+                "language": "nld",
+                "alignId": "audio",
+                "channels": 2,
+                "codecs": "mp4a.40.2",
+                "continuationPattern": "content-{segmentId}.mp4",
+                "initializationPattern": "init-{initId}.mp4",
+                "sampleRate": 48000,
+                "tracks": [
+                    {
+                        "bandwidth": 96000,
+                        "id": "96k",
+                        "label": "Stereo",
+                        "segments": [],
+                        "segmentDuration": {"value": 2}
+                    }
+                ]
+            }"#;
+        let address = Address::new(Url::parse("http://localhost")?, None)?;
+        let data = serde_json::from_str::<AudioSwitchingSetData>(data)?;
+        let mut set = AudioSwitchingSet::new("p1", &address, data)?;
+
+        assert_eq!(set.language(), Language::Nld);
+        assert_eq!(set.align_id(), Some("audio"));
+        assert_eq!(set.channels(), Some(2));
+        assert!(set.protection().is_none());
+        let track = set.track("96k").unwrap();
+        assert_eq!(track.sample_rate(), 48000);
+        assert_eq!(track.samples_per_frame(), SamplesPerFrame::default());
+        assert_eq!(track.label(), Some("Stereo"));
+        assert_eq!(track.codecs().as_str(), "mp4a.40.2");
+
+        set.set_language(Language::Eng);
+        let track = set.track_mut("96k").unwrap();
+        track.set_codecs("mp4a.40.5".parse()?);
+        track.set_label(None);
+        assert_eq!(set.language(), Language::Eng);
+        let track = set.track("96k").unwrap();
+        assert_eq!(track.codecs().as_str(), "mp4a.40.5");
+        assert_eq!(track.label(), None);
+        Ok(())
+    }
 }
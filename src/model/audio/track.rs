@@ -1,7 +1,7 @@
 use crate::data::AudioTrackData;
 use crate::util::Entity;
 use crate::{
-    Address, AudioMimeType, ContinuationPattern, Error, FrameRate, Initialization,
+    Address, AudioMimeType, Codecs, ContinuationPattern, Error, FrameRate, Initialization,
     InitializationPattern, MediaType, Result, SamplesPerFrame, ScaledDuration, ScaledValue,
     Segment, SegmentId, Segments, Track, TrackUid,
 };
@@ -19,7 +19,7 @@ pub struct AudioTrack {
     pub(crate) active_sequence_number: Option<u64>,
     pub(crate) average_bandwidth: Option<u64>,
     pub(crate) channels: Option<u64>,
-    pub(crate) codecs: String,
+    pub(crate) codecs: Codecs,
     pub(crate) continuation_pattern: ContinuationPattern,
     pub(crate) samples_per_frame: SamplesPerFrame,
     pub(crate) label: Option<String>,
@@ -33,10 +33,98 @@ pub struct AudioTrack {
 impl AudioTrack {
     const MEDIA_TYPE: MediaType = MediaType::Audio;
 
+    pub(crate) fn reparent(&mut self, presentation_id: &str, switching_set_id: &str) {
+        self.uid = TrackUid::new(
+            presentation_id.to_owned(),
+            Self::MEDIA_TYPE,
+            switching_set_id.to_owned(),
+            self.uid.track_id().to_owned(),
+        );
+    }
+
     #[must_use]
     pub const fn start_sequence_number(&self) -> u64 {
         self.start_sequence_number
     }
+
+    pub const fn set_start_sequence_number(&mut self, start_sequence_number: u64) {
+        self.start_sequence_number = start_sequence_number;
+    }
+
+    #[must_use]
+    pub const fn codecs(&self) -> &Codecs {
+        &self.codecs
+    }
+
+    pub fn set_codecs(&mut self, codecs: Codecs) {
+        self.codecs = codecs;
+    }
+
+    #[must_use]
+    pub const fn channels(&self) -> Option<u64> {
+        self.channels
+    }
+
+    pub const fn set_channels(&mut self, channels: Option<u64>) {
+        self.channels = channels;
+    }
+
+    #[must_use]
+    pub const fn sample_rate(&self) -> u64 {
+        self.sample_rate
+    }
+
+    pub const fn set_sample_rate(&mut self, sample_rate: u64) {
+        self.sample_rate = sample_rate;
+    }
+
+    #[must_use]
+    pub const fn samples_per_frame(&self) -> SamplesPerFrame {
+        self.samples_per_frame
+    }
+
+    pub const fn set_samples_per_frame(&mut self, samples_per_frame: SamplesPerFrame) {
+        self.samples_per_frame = samples_per_frame;
+    }
+
+    pub const fn set_bandwidth(&mut self, bandwidth: u64) {
+        self.bandwidth = bandwidth;
+    }
+
+    pub const fn set_average_bandwidth(&mut self, average_bandwidth: Option<u64>) {
+        self.average_bandwidth = average_bandwidth;
+    }
+
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(&self) -> ScaledValue {
+        self.media_time_offset
+    }
+
+    pub const fn set_media_time_offset(&mut self, media_time_offset: ScaledValue) {
+        self.media_time_offset = media_time_offset;
+    }
+
+    pub const fn set_start_segment_id(&mut self, start_segment_id: SegmentId) {
+        self.start_segment_id = start_segment_id;
+    }
+
+    pub fn set_segment_duration(&mut self, segment_duration: Option<ScaledDuration>) -> Result<()> {
+        if segment_duration.is_none() {
+            self.segments
+                .ensure_time_bounds_defined(self.uid.track_id())?;
+        }
+        self.segment_duration = segment_duration;
+        Ok(())
+    }
 }
 
 impl Entity for AudioTrack {
@@ -54,6 +142,16 @@ impl Track for AudioTrack {
         &self.segments
     }
 
+    fn push_segment(&mut self, segment: Segment) -> Result<()> {
+        let id = self.uid.track_id();
+        self.segments.push(segment, self.segment_duration, id)
+    }
+
+    fn remove_segments_before(&mut self, segment_id: SegmentId) {
+        self.segments
+            .remove_before(segment_id, self.segment_duration);
+    }
+
     fn start_segment_id(&self) -> SegmentId {
         self.start_segment_id
     }
@@ -67,6 +165,10 @@ impl Track for AudioTrack {
         self.segment_duration
     }
 
+    fn media_time_offset(&self) -> ScaledValue {
+        self.media_time_offset
+    }
+
     fn average_bandwidth(&self) -> Option<u64> {
         self.average_bandwidth
     }
@@ -127,7 +229,8 @@ impl AudioTrack {
         let address = switching_set_address.join(data.base_url)?;
         let codecs = data
             .codecs
-            .ok_or_else(|| Error::MissingCodecs(id.clone()))?;
+            .ok_or_else(|| Error::MissingCodecs(id.clone()))?
+            .try_into()?;
         let continuation_pattern = data
             .continuation_pattern
             .ok_or_else(|| Error::MissingContinuationPattern(id.clone()))?;
@@ -0,0 +1,155 @@
+use uuid::Uuid;
+
+use crate::{Codec, FrameRate, Initialization, ProtectionScheme, Resolution, Track, TrackRef};
+
+/// What a player can decode and display, used to drop the tracks it cannot play:
+///
+/// ```ignore
+/// manifest.filter_tracks(|track| capabilities.supports(track))?;
+/// ```
+///
+/// Every limit is optional; `None` means anything is supported.
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackCapabilities {
+    pub max_resolution: Option<Resolution>,
+    pub max_frame_rate: Option<FrameRate>,
+    /// Supported RFC 6381 codecs; every codec of an audio or video track must be covered by one.
+    ///
+    /// A complete codec also covers lower levels and tiers of the same profile, e.g.
+    /// `avc1.640028` covers `avc1.64001f` and `mp4a.40` covers `mp4a.40.2`. Anything else is
+    /// matched as a prefix, e.g. `avc1` or `hvc1.2`.
+    pub codecs: Option<Vec<String>>,
+    pub protection_schemes: Option<Vec<ProtectionScheme>>,
+    /// Supported DRM systems; protected switching sets need at least one of them.
+    pub drm_systems: Option<Vec<Uuid>>,
+    pub max_bandwidth: Option<u64>,
+}
+
+impl PlaybackCapabilities {
+    #[must_use]
+    pub fn supports(&self, track: TrackRef) -> bool {
+        let codecs = match track {
+            TrackRef::Video(_, video) => {
+                if !self.supports_video(video.resolution(), video.frame_rate()) {
+                    return false;
+                }
+                Some(video.codecs())
+            }
+            TrackRef::Audio(_, audio) => Some(audio.codecs()),
+            TrackRef::Metadata(..) => None,
+        };
+        let codecs_supported = codecs.is_none_or(|codecs| {
+            codecs
+                .as_str()
+                .split(',')
+                .all(|codec| self.supports_codec(codec.trim()))
+        });
+        codecs_supported
+            && self.supports_bandwidth(track.track())
+            && self.supports_protection(track)
+    }
+
+    fn supports_video(&self, resolution: &Resolution, frame_rate: FrameRate) -> bool {
+        let resolution_supported = self.max_resolution.as_ref().is_none_or(|max| {
+            resolution.width() <= max.width() && resolution.height() <= max.height()
+        });
+        let frame_rate_supported = self
+            .max_frame_rate
+            .is_none_or(|max| frame_rate.to_f64() <= max.to_f64());
+        resolution_supported && frame_rate_supported
+    }
+
+    fn supports_codec(&self, codec: &str) -> bool {
+        self.codecs
+            .as_ref()
+            .is_none_or(|codecs| codecs.iter().any(|supported| covers(supported, codec)))
+    }
+
+    fn supports_bandwidth(&self, track: &dyn Track) -> bool {
+        match (self.max_bandwidth, track.bandwidth()) {
+            (Some(max), Some(bandwidth)) => bandwidth <= max,
+            _ => true,
+        }
+    }
+
+    fn supports_protection(&self, track: TrackRef) -> bool {
+        let Some(protection) = track.protection() else {
+            return true;
+        };
+        let scheme_supported = self
+            .protection_schemes
+            .as_ref()
+            .is_none_or(|schemes| schemes.contains(&protection.scheme()));
+        let system_supported = self.drm_systems.as_ref().is_none_or(|systems| {
+            protection
+                .systems()
+                .iter()
+                .any(|system| systems.contains(&system.scheme_id()))
+        });
+        scheme_supported && system_supported
+    }
+}
+
+fn covers(supported: &str, codec: &str) -> bool {
+    let (Ok(supported_codec), Ok(parsed)) = (supported.parse(), codec.parse()) else {
+        return codec.starts_with(supported);
+    };
+    match (supported_codec, parsed) {
+        (Codec::Avc(max), Codec::Avc(avc)) => avc.profile == max.profile && avc.level <= max.level,
+        (Codec::Hevc(max), Codec::Hevc(hevc)) => {
+            hevc.profile_space == max.profile_space
+                && hevc.profile == max.profile
+                && hevc.tier <= max.tier
+                && hevc.level <= max.level
+        }
+        (Codec::Av1(max), Codec::Av1(av1)) => {
+            av1.profile == max.profile
+                && av1.tier <= max.tier
+                && av1.level <= max.level
+                && av1.bit_depth <= max.bit_depth
+        }
+        (Codec::Mp4a(max), Codec::Mp4a(mp4a)) => {
+            mp4a.object_type == max.object_type
+                && max.audio_object_type.is_none_or(|audio_object_type| {
+                    mp4a.audio_object_type == Some(audio_object_type)
+                })
+        }
+        (supported, parsed) => supported == parsed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_codecs_by_profile_and_level() {
+        let capabilities = PlaybackCapabilities {
+            codecs: Some(
+                ["avc1.640028", "hvc1.2", "mp4a.40", "ec-3"]
+                    .map(str::to_owned)
+                    .to_vec(),
+            ),
+            ..PlaybackCapabilities::default()
+        };
+
+        for codec in [
+            "avc1.64001f",
+            "avc1.640028",
+            "hvc1.2.4.L120.B0",
+            "mp4a.40.5",
+            "ec-3",
+        ] {
+            assert!(capabilities.supports_codec(codec), "{codec}");
+        }
+        for codec in [
+            "avc1.640029",
+            "avc1.4d001f",
+            "hvc1.1.6.L93.B0",
+            "mp4a.69",
+            "ac-3",
+        ] {
+            assert!(!capabilities.supports_codec(codec), "{codec}");
+        }
+    }
+}
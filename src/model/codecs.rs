@@ -0,0 +1,417 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// A list of codecs as described by [RFC 6381], e.g. `avc1.64001f,mp4a.40.2`.
+///
+/// The well-known codecs are parsed into their profile, level and tier; other codecs are kept
+/// as-is. The original string is preserved so serializing a `Codecs` gives back its input.
+///
+/// [RFC 6381]: https://datatracker.ietf.org/doc/html/rfc6381#section-3.3
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Codecs {
+    value: String,
+    codecs: Vec<Codec>,
+}
+
+impl Codecs {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    pub fn codecs(&self) -> &[Codec] {
+        &self.codecs
+    }
+
+    /// The first video codec in the list.
+    #[must_use]
+    pub fn video(&self) -> Option<&Codec> {
+        self.codecs.iter().find(|codec| codec.is_video())
+    }
+
+    /// The first audio codec in the list.
+    #[must_use]
+    pub fn audio(&self) -> Option<&Codec> {
+        self.codecs.iter().find(|codec| codec.is_audio())
+    }
+}
+
+impl FromStr for Codecs {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        input.to_owned().try_into()
+    }
+}
+
+impl TryFrom<String> for Codecs {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        let codecs = value
+            .split(',')
+            .map(|codec| codec.trim().parse())
+            .collect::<Result<_>>()?;
+        Ok(Self { value, codecs })
+    }
+}
+
+impl From<Codecs> for String {
+    fn from(codecs: Codecs) -> Self {
+        codecs.value
+    }
+}
+
+impl AsRef<str> for Codecs {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for Codecs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Codec {
+    /// `avc1` or `avc3`
+    Avc(Avc),
+    /// `hvc1` or `hev1`
+    Hevc(Hevc),
+    /// `av01`
+    Av1(Av1),
+    /// `mp4a`
+    Mp4a(Mp4a),
+    /// `ac-3`
+    Ac3,
+    /// `ec-3`
+    Ec3,
+    /// `opus` or `Opus`
+    Opus,
+    /// `fLaC`
+    Flac,
+    /// A codec this crate does not interpret.
+    Other(String),
+}
+
+impl Codec {
+    #[must_use]
+    pub const fn is_video(&self) -> bool {
+        matches!(self, Self::Avc(_) | Self::Hevc(_) | Self::Av1(_))
+    }
+
+    #[must_use]
+    pub const fn is_audio(&self) -> bool {
+        matches!(
+            self,
+            Self::Mp4a(_) | Self::Ac3 | Self::Ec3 | Self::Opus | Self::Flac
+        )
+    }
+}
+
+impl FromStr for Codec {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let invalid = || Error::InvalidCodecs(input.to_owned());
+        let (sample_entry, parameters) = match input.split_once('.') {
+            Some((sample_entry, parameters)) => (sample_entry, Some(parameters)),
+            None => (input, None),
+        };
+        let codec = match (sample_entry, parameters) {
+            ("avc1" | "avc3", Some(parameters)) => Avc::parse(parameters).map(Self::Avc),
+            ("hvc1" | "hev1", Some(parameters)) => Hevc::parse(parameters).map(Self::Hevc),
+            ("av01", Some(parameters)) => Av1::parse(parameters).map(Self::Av1),
+            ("mp4a", Some(parameters)) => Mp4a::parse(parameters).map(Self::Mp4a),
+            ("ac-3", None) => Some(Self::Ac3),
+            ("ec-3", None) => Some(Self::Ec3),
+            ("opus" | "Opus", None) => Some(Self::Opus),
+            ("fLaC", None) => Some(Self::Flac),
+            _ if is_other_sample_entry(sample_entry) && !input.contains(char::is_whitespace) => {
+                Some(Self::Other(input.to_owned()))
+            }
+            _ => None,
+        };
+        codec.ok_or_else(invalid)
+    }
+}
+
+const KNOWN_SAMPLE_ENTRIES: [&str; 11] = [
+    "avc1", "avc3", "hvc1", "hev1", "av01", "mp4a", "ac-3", "ec-3", "opus", "Opus", "fLaC",
+];
+
+/// Sample entries are four characters, e.g. `vp09` or `stpp`.
+fn is_other_sample_entry(input: &str) -> bool {
+    input.len() == 4
+        && input.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !KNOWN_SAMPLE_ENTRIES.contains(&input)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Tier {
+    Main,
+    High,
+}
+
+/// H.264 parameters, e.g. `64001f` for High profile level 3.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Avc {
+    pub profile: u8,
+    pub constraints: u8,
+    pub level: u8,
+}
+
+impl Avc {
+    fn parse(input: &str) -> Option<Self> {
+        if input.len() != 6 {
+            return None;
+        }
+        Some(Self {
+            profile: hex(input.get(0..2)?)?,
+            constraints: hex(input.get(2..4)?)?,
+            level: hex(input.get(4..6)?)?,
+        })
+    }
+}
+
+/// H.265 parameters, e.g. `1.6.L93.B0` for Main profile, main tier level 3.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hevc {
+    /// 0 when absent, 1 to 3 for the `A`, `B` and `C` prefixes.
+    pub profile_space: u8,
+    pub profile: u8,
+    /// The compatibility flags as written, i.e. in reverse bit order.
+    pub compatibility_flags: u32,
+    pub tier: Tier,
+    pub level: u8,
+    pub constraints: [u8; 6],
+}
+
+impl Hevc {
+    fn parse(input: &str) -> Option<Self> {
+        let mut parts = input.split('.');
+        let profile = parts.next()?;
+        let (profile_space, profile) = match profile.as_bytes().first()? {
+            prefix @ b'A'..=b'C' => (prefix - b'A' + 1, &profile[1..]),
+            _ => (0, profile),
+        };
+        let compatibility_flags = u32::from_str_radix(parts.next()?, 16).ok()?;
+        let level = parts.next()?;
+        let tier = match level.as_bytes().first()? {
+            b'L' => Tier::Main,
+            b'H' => Tier::High,
+            _ => return None,
+        };
+        let mut constraints = [0; 6];
+        for constraint in &mut constraints {
+            if let Some(part) = parts.next() {
+                *constraint = hex(part)?;
+            }
+        }
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            profile_space,
+            profile: decimal(profile)?,
+            compatibility_flags,
+            tier,
+            level: decimal(&level[1..])?,
+            constraints,
+        })
+    }
+}
+
+/// AV1 parameters, e.g. `0.05M.08` for Main profile, level 3.1, main tier, 8 bits.
+///
+/// The optional color parameters that may follow are validated but not kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Av1 {
+    pub profile: u8,
+    pub level: u8,
+    pub tier: Tier,
+    pub bit_depth: u8,
+}
+
+impl Av1 {
+    fn parse(input: &str) -> Option<Self> {
+        let parts: Vec<_> = input.split('.').collect();
+        let [profile, level_tier, bit_depth, ref color @ ..] = parts[..] else {
+            return None;
+        };
+        if !matches!(color.len(), 0 | 6) || color.iter().any(|part| decimal(part).is_none()) {
+            return None;
+        }
+        let (level, tier) = level_tier.split_at_checked(2)?;
+        let tier = match tier {
+            "M" => Tier::Main,
+            "H" => Tier::High,
+            _ => return None,
+        };
+        let bit_depth = decimal(bit_depth)?;
+        if !matches!(bit_depth, 8 | 10 | 12) {
+            return None;
+        }
+        Some(Self {
+            profile: decimal(profile)?,
+            level: decimal(level)?,
+            tier,
+            bit_depth,
+        })
+    }
+}
+
+/// MPEG-4 audio parameters, e.g. `40.2` for AAC-LC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mp4a {
+    /// The object type indication, e.g. `0x40` for MPEG-4 audio.
+    pub object_type: u8,
+    /// The audio object type, e.g. 2 for AAC-LC or 5 for HE-AAC.
+    pub audio_object_type: Option<u8>,
+}
+
+impl Mp4a {
+    fn parse(input: &str) -> Option<Self> {
+        let (object_type, audio_object_type) = match input.split_once('.') {
+            Some((object_type, audio_object_type)) => {
+                (object_type, Some(decimal(audio_object_type)?))
+            }
+            None => (input, None),
+        };
+        Some(Self {
+            object_type: hex(object_type)?,
+            audio_object_type,
+        })
+    }
+}
+
+fn hex(input: &str) -> Option<u8> {
+    if input.is_empty() || input.len() > 2 {
+        return None;
+    }
+    u8::from_str_radix(input, 16).ok()
+}
+
+fn decimal(input: &str) -> Option<u8> {
+    if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    input.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_video_codecs() -> anyhow::Result<()> {
+        let avc: Codecs = "avc1.64001f".parse()?;
+        assert_eq!(
+            avc.codecs(),
+            [Codec::Avc(Avc {
+                profile: 100,
+                constraints: 0,
+                level: 31,
+            })]
+        );
+
+        let hevc: Codecs = "hvc1.1.6.L93.B0".parse()?;
+        assert_eq!(
+            hevc.video(),
+            Some(&Codec::Hevc(Hevc {
+                profile_space: 0,
+                profile: 1,
+                compatibility_flags: 6,
+                tier: Tier::Main,
+                level: 93,
+                constraints: [0xb0, 0, 0, 0, 0, 0],
+            }))
+        );
+
+        let av1: Codecs = "av01.0.05M.08".parse()?;
+        assert_eq!(
+            av1.video(),
+            Some(&Codec::Av1(Av1 {
+                profile: 0,
+                level: 5,
+                tier: Tier::Main,
+                bit_depth: 8,
+            }))
+        );
+        assert!("av01.0.04M.10.0.112.09.16.09.0".parse::<Codecs>().is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_audio_codecs() -> anyhow::Result<()> {
+        let codecs: Codecs = "mp4a.40.2, ec-3,opus".parse()?;
+
+        assert_eq!(
+            codecs.codecs(),
+            [
+                Codec::Mp4a(Mp4a {
+                    object_type: 0x40,
+                    audio_object_type: Some(2),
+                }),
+                Codec::Ec3,
+                Codec::Opus,
+            ]
+        );
+        assert_eq!(codecs.video(), None);
+        assert_eq!(codecs.to_string(), "mp4a.40.2, ec-3,opus");
+        Ok(())
+    }
+
+    #[test]
+    fn keep_other_codecs() -> anyhow::Result<()> {
+        let codecs: Codecs = "vp09.00.10.08,stpp.ttml.im1t".parse()?;
+
+        assert_eq!(
+            codecs.codecs(),
+            [
+                Codec::Other("vp09.00.10.08".to_owned()),
+                Codec::Other("stpp.ttml.im1t".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_codecs() {
+        for input in [
+            "",
+            "avc1",
+            "avc1.64001",
+            "avc1.64001g",
+            "hvc1.1.6.X93",
+            "av01.0.05M.09",
+            "av01.0.05",
+            "mp4a.zz",
+            "ec-3.1",
+            "h.264",
+            "avc1.64001f,",
+        ] {
+            assert!(
+                matches!(input.parse::<Codecs>(), Err(Error::InvalidCodecs(_))),
+                "{input} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn serialize_as_string() -> anyhow::Result<()> {
+        let codecs: Codecs = serde_json::from_str(r#""avc1.4d001f""#)?;
+
+        assert_eq!(serde_json::to_string(&codecs)?, r#""avc1.4d001f""#);
+        assert!(serde_json::from_str::<Codecs>(r#""avc1""#).is_err());
+        Ok(())
+    }
+}
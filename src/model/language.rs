@@ -492,3 +492,206 @@ pub enum Language {
     Zxx,
     Zza,
 }
+
+impl Language {
+    /// The ISO 639-2 code of the language, e.g. `eng`.
+    #[must_use]
+    pub fn code(self) -> String {
+        format!("{self:?}").to_lowercase()
+    }
+
+    /// Looks up the language of a two-letter ISO 639-1 code, e.g. `en`.
+    pub(crate) fn from_iso_639_1(code: &str) -> Option<Self> {
+        ISO_639_1
+            .iter()
+            .find(|(alpha2, _)| *alpha2 == code)
+            .map(|(_, language)| *language)
+    }
+}
+
+/// The ISO 639-1 codes with their ISO 639-2 language.
+const ISO_639_1: [(&str, Language); 183] = [
+    ("aa", Language::Aar),
+    ("ab", Language::Abk),
+    ("ae", Language::Ave),
+    ("af", Language::Afr),
+    ("ak", Language::Aka),
+    ("am", Language::Amh),
+    ("an", Language::Arg),
+    ("ar", Language::Ara),
+    ("as", Language::Asm),
+    ("av", Language::Ava),
+    ("ay", Language::Aym),
+    ("az", Language::Aze),
+    ("ba", Language::Bak),
+    ("be", Language::Bel),
+    ("bg", Language::Bul),
+    ("bi", Language::Bis),
+    ("bm", Language::Bam),
+    ("bn", Language::Ben),
+    ("bo", Language::Bod),
+    ("br", Language::Bre),
+    ("bs", Language::Bos),
+    ("ca", Language::Cat),
+    ("ce", Language::Che),
+    ("ch", Language::Cha),
+    ("co", Language::Cos),
+    ("cr", Language::Cre),
+    ("cs", Language::Ces),
+    ("cu", Language::Chu),
+    ("cv", Language::Chv),
+    ("cy", Language::Cym),
+    ("da", Language::Dan),
+    ("de", Language::Deu),
+    ("dv", Language::Div),
+    ("dz", Language::Dzo),
+    ("ee", Language::Ewe),
+    ("el", Language::Ell),
+    ("en", Language::Eng),
+    ("eo", Language::Epo),
+    ("es", Language::Spa),
+    ("et", Language::Est),
+    ("eu", Language::Eus),
+    ("fa", Language::Fas),
+    ("ff", Language::Ful),
+    ("fi", Language::Fin),
+    ("fj", Language::Fij),
+    ("fo", Language::Fao),
+    ("fr", Language::Fra),
+    ("fy", Language::Fry),
+    ("ga", Language::Gle),
+    ("gd", Language::Gla),
+    ("gl", Language::Glg),
+    ("gn", Language::Grn),
+    ("gu", Language::Guj),
+    ("gv", Language::Glv),
+    ("ha", Language::Hau),
+    ("he", Language::Heb),
+    ("hi", Language::Hin),
+    ("ho", Language::Hmo),
+    ("hr", Language::Hrv),
+    ("ht", Language::Hat),
+    ("hu", Language::Hun),
+    ("hy", Language::Hye),
+    ("hz", Language::Her),
+    ("ia", Language::Ina),
+    ("id", Language::Ind),
+    ("ie", Language::Ile),
+    ("ig", Language::Ibo),
+    ("ii", Language::Iii),
+    ("ik", Language::Ipk),
+    ("io", Language::Ido),
+    ("is", Language::Isl),
+    ("it", Language::Ita),
+    ("iu", Language::Iku),
+    ("ja", Language::Jpn),
+    ("jv", Language::Jav),
+    ("ka", Language::Kat),
+    ("kg", Language::Kon),
+    ("ki", Language::Kik),
+    ("kj", Language::Kua),
+    ("kk", Language::Kaz),
+    ("kl", Language::Kal),
+    ("km", Language::Khm),
+    ("kn", Language::Kan),
+    ("ko", Language::Kor),
+    ("kr", Language::Kau),
+    ("ks", Language::Kas),
+    ("ku", Language::Kur),
+    ("kv", Language::Kom),
+    ("kw", Language::Cor),
+    ("ky", Language::Kir),
+    ("la", Language::Lat),
+    ("lb", Language::Ltz),
+    ("lg", Language::Lug),
+    ("li", Language::Lim),
+    ("ln", Language::Lin),
+    ("lo", Language::Lao),
+    ("lt", Language::Lit),
+    ("lu", Language::Lub),
+    ("lv", Language::Lav),
+    ("mg", Language::Mlg),
+    ("mh", Language::Mah),
+    ("mi", Language::Mri),
+    ("mk", Language::Mkd),
+    ("ml", Language::Mal),
+    ("mn", Language::Mon),
+    ("mr", Language::Mar),
+    ("ms", Language::Msa),
+    ("mt", Language::Mlt),
+    ("my", Language::Mya),
+    ("na", Language::Nau),
+    ("nb", Language::Nob),
+    ("nd", Language::Nde),
+    ("ne", Language::Nep),
+    ("ng", Language::Ndo),
+    ("nl", Language::Nld),
+    ("nn", Language::Nno),
+    ("no", Language::Nor),
+    ("nr", Language::Nbl),
+    ("nv", Language::Nav),
+    ("ny", Language::Nya),
+    ("oc", Language::Oci),
+    ("oj", Language::Oji),
+    ("om", Language::Orm),
+    ("or", Language::Ori),
+    ("os", Language::Oss),
+    ("pa", Language::Pan),
+    ("pi", Language::Pli),
+    ("pl", Language::Pol),
+    ("ps", Language::Pus),
+    ("pt", Language::Por),
+    ("qu", Language::Que),
+    ("rm", Language::Roh),
+    ("rn", Language::Run),
+    ("ro", Language::Ron),
+    ("ru", Language::Rus),
+    ("rw", Language::Kin),
+    ("sa", Language::San),
+    ("sc", Language::Srd),
+    ("sd", Language::Snd),
+    ("se", Language::Sme),
+    ("sg", Language::Sag),
+    ("si", Language::Sin),
+    ("sk", Language::Slk),
+    ("sl", Language::Slv),
+    ("sm", Language::Smo),
+    ("sn", Language::Sna),
+    ("so", Language::Som),
+    ("sq", Language::Sqi),
+    ("sr", Language::Srp),
+    ("ss", Language::Ssw),
+    ("st", Language::Sot),
+    ("su", Language::Sun),
+    ("sv", Language::Swe),
+    ("sw", Language::Swa),
+    ("ta", Language::Tam),
+    ("te", Language::Tel),
+    ("tg", Language::Tgk),
+    ("th", Language::Tha),
+    ("ti", Language::Tir),
+    ("tk", Language::Tuk),
+    ("tl", Language::Tgl),
+    ("tn", Language::Tsn),
+    ("to", Language::Ton),
+    ("tr", Language::Tur),
+    ("ts", Language::Tso),
+    ("tt", Language::Tat),
+    ("tw", Language::Twi),
+    ("ty", Language::Tah),
+    ("ug", Language::Uig),
+    ("uk", Language::Ukr),
+    ("ur", Language::Urd),
+    ("uz", Language::Uzb),
+    ("ve", Language::Ven),
+    ("vi", Language::Vie),
+    ("vo", Language::Vol),
+    ("wa", Language::Wln),
+    ("wo", Language::Wol),
+    ("xh", Language::Xho),
+    ("yi", Language::Yid),
+    ("yo", Language::Yor),
+    ("za", Language::Zha),
+    ("zh", Language::Zho),
+    ("zu", Language::Zul),
+];
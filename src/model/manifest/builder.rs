@@ -0,0 +1,204 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use url::Url;
+
+use crate::data::{ManifestData, PresentationData};
+use crate::util::Uri;
+use crate::{LiveStream, Manifest, PresentationBuilder, Result, StreamType};
+
+#[derive(Debug, Clone)]
+pub struct ManifestBuilder {
+    location: Url,
+    creation_date: DateTime<FixedOffset>,
+    fallback_poll_rate: u64,
+    presentations: Vec<PresentationBuilder>,
+    stream_type: StreamType,
+    content_base_url: Option<Uri>,
+}
+
+impl ManifestBuilder {
+    #[must_use]
+    pub fn new(location: Url, fallback_poll_rate: u64) -> Self {
+        Self {
+            location,
+            creation_date: Utc::now().into(),
+            fallback_poll_rate,
+            presentations: Vec::new(),
+            stream_type: StreamType::Vod,
+            content_base_url: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn creation_date(mut self, creation_date: DateTime<FixedOffset>) -> Self {
+        self.creation_date = creation_date;
+        self
+    }
+
+    #[must_use]
+    pub fn presentation(mut self, presentation: PresentationBuilder) -> Self {
+        self.presentations.push(presentation);
+        self
+    }
+
+    #[must_use]
+    pub fn live(mut self, live: LiveStream) -> Self {
+        self.stream_type = StreamType::Live(live);
+        self
+    }
+
+    #[must_use]
+    pub fn vod(mut self) -> Self {
+        self.stream_type = StreamType::Vod;
+        self
+    }
+
+    #[must_use]
+    pub fn content_base_url(mut self, content_base_url: Uri) -> Self {
+        self.content_base_url = Some(content_base_url);
+        self
+    }
+
+    pub fn build(self) -> Result<Manifest> {
+        let data = ManifestData {
+            creation_date: self.creation_date.into(),
+            fallback_poll_rate: self.fallback_poll_rate.into(),
+            presentations: self
+                .presentations
+                .into_iter()
+                .map(PresentationData::try_from)
+                .collect::<Result<_>>()?,
+            stream_type: self.stream_type,
+            content_base_url: self.content_base_url,
+        };
+        Manifest::new(self.location, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::Entity;
+    use crate::{
+        AudioSwitchingSetBuilder, AudioTrackBuilder, Error, FrameRate, Language, Resolution, Scale,
+        ScaledDuration, Segment, SegmentId, TimeBounds, Track, UnsignedScaledValue,
+        VideoSwitchingSetBuilder, VideoTrackBuilder,
+    };
+
+    use super::*;
+
+    fn scale() -> Scale {
+        Scale::try_from(90_000u64).unwrap()
+    }
+
+    fn video() -> VideoSwitchingSetBuilder {
+        VideoSwitchingSetBuilder::new("main-video")
+            .frame_rate(FrameRate::new(25, Scale::ONE))
+            .continuation_pattern("content-{segmentId}.mp4")
+            .initialization_pattern("init-{initId}.mp4")
+            .track(
+                VideoTrackBuilder::new("720p", 3_000_000, Resolution::new(1280, 720))
+                    .codecs("avc1.4d001f")
+                    .segment_duration(ScaledDuration::new(180_000, scale()))
+                    .segment(Segment::new(
+                        SegmentId::from(10),
+                        Some(TimeBounds::new(Some(0), None, scale()).unwrap()),
+                    )),
+            )
+    }
+
+    fn audio() -> AudioSwitchingSetBuilder {
+        AudioSwitchingSetBuilder::new("main-audio", Language::Eng)
+            .codecs("mp4a.40.2")
+            .sample_rate(48_000)
+            .continuation_pattern("content-{segmentId}.mp4")
+            .initialization_pattern("init-{initId}.mp4")
+            .track(
+                AudioTrackBuilder::new("96kbps", 96_000)
+                    .segment_duration(ScaledDuration::new(180_000, scale())),
+            )
+    }
+
+    #[test]
+    fn build_live_manifest() -> anyhow::Result<()> {
+        let manifest = Manifest::builder(Url::parse("http://localhost/manifest.json")?, 300)
+            .presentation(
+                PresentationBuilder::new("0", TimeBounds::new(Some(0), None, scale())?)
+                    .video(video())
+                    .audio(audio()),
+            )
+            .live(LiveStream {
+                availability_duration: ScaledDuration::new(1500, Scale::ONE),
+                active_presentation: "0".to_owned(),
+                time_source: None,
+                current_time: UnsignedScaledValue::new(90_000, scale()),
+            })
+            .build()?;
+
+        assert_eq!(manifest.active_presentation().map(Entity::id), Some("0"));
+        assert_eq!(manifest.video_tracks().count(), 1);
+        assert_eq!(manifest.audio_tracks().count(), 1);
+        let track = manifest.video_tracks().next().unwrap();
+        assert_eq!(
+            track
+                .continuation_pattern()
+                .segment(SegmentId::from(11))
+                .as_str(),
+            "http://localhost/content-11.mp4"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn build_checks_track_invariants() -> anyhow::Result<()> {
+        let result = Manifest::builder(Url::parse("http://localhost")?, 300)
+            .presentation(
+                PresentationBuilder::new("0", TimeBounds::new(Some(0), None, scale())?).video(
+                    VideoSwitchingSetBuilder::new("main-video").track(VideoTrackBuilder::new(
+                        "720p",
+                        1,
+                        Resolution::new(1, 1),
+                    )),
+                ),
+            )
+            .build();
+
+        assert!(matches!(result, Err(Error::MissingCodecs(id)) if id == "720p"));
+        Ok(())
+    }
+
+    #[test]
+    fn build_checks_segment_ids() -> anyhow::Result<()> {
+        let result = Manifest::builder(Url::parse("http://localhost")?, 300)
+            .presentation(
+                PresentationBuilder::new("0", TimeBounds::new(Some(0), None, scale())?).audio(
+                    AudioSwitchingSetBuilder::new("main-audio", Language::Eng).track(
+                        AudioTrackBuilder::new("96kbps", 96_000)
+                            .segment(Segment::new(SegmentId::from(1), None))
+                            .segment(Segment::new(SegmentId::from(3), None)),
+                    ),
+                ),
+            )
+            .build();
+
+        assert!(matches!(result, Err(Error::InvalidSegmentIds(..))));
+        Ok(())
+    }
+
+    #[test]
+    fn build_checks_active_presentation() -> anyhow::Result<()> {
+        let result = Manifest::builder(Url::parse("http://localhost")?, 300)
+            .presentation(PresentationBuilder::new(
+                "0",
+                TimeBounds::new(Some(0), None, Scale::ONE)?,
+            ))
+            .live(LiveStream {
+                availability_duration: ScaledDuration::new(1500, Scale::ONE),
+                active_presentation: "1".to_owned(),
+                time_source: None,
+                current_time: UnsignedScaledValue::ZERO,
+            })
+            .build();
+
+        assert!(matches!(result, Err(Error::InvalidActivePresentationId(id)) if id == "1"));
+        Ok(())
+    }
+}
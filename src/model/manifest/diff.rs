@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use crate::util::Entity;
+use crate::{
+    LiveStream, Manifest, Presentation, SegmentId, StreamType, TimeBounds, Track, TrackUid,
+    UnsignedScaledValue,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestChange {
+    FallbackPollRateChanged {
+        old: u64,
+        new: u64,
+    },
+    StreamEnded,
+    ActivePresentationChanged {
+        old: String,
+        new: String,
+    },
+    CurrentTimeChanged {
+        old: UnsignedScaledValue,
+        new: UnsignedScaledValue,
+    },
+    PresentationAdded(String),
+    PresentationRemoved(String),
+    TimeBoundsChanged {
+        presentation_id: String,
+        old: TimeBounds,
+        new: TimeBounds,
+    },
+    TrackAdded(TrackUid),
+    TrackRemoved(TrackUid),
+    SegmentsAdded {
+        track_uid: TrackUid,
+        first: SegmentId,
+        last: SegmentId,
+    },
+    SegmentsRemoved {
+        track_uid: TrackUid,
+        first: SegmentId,
+        last: SegmentId,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    changes: Vec<ManifestChange>,
+}
+
+impl ManifestDiff {
+    pub(super) fn new(old: &Manifest, new: &Manifest) -> Self {
+        let mut diff = Self::default();
+        if old.fallback_poll_rate != new.fallback_poll_rate {
+            diff.push(ManifestChange::FallbackPollRateChanged {
+                old: old.fallback_poll_rate,
+                new: new.fallback_poll_rate,
+            });
+        }
+        diff.diff_stream_type(&old.stream_type, &new.stream_type);
+        for presentation in old.presentations() {
+            match new.presentation(presentation.id()) {
+                Some(newer) => diff.diff_presentation(presentation, newer),
+                None => diff.push(ManifestChange::PresentationRemoved(
+                    presentation.id().to_owned(),
+                )),
+            }
+        }
+        for presentation in new.presentations() {
+            if old.presentation(presentation.id()).is_none() {
+                diff.push(ManifestChange::PresentationAdded(
+                    presentation.id().to_owned(),
+                ));
+            }
+        }
+        diff
+    }
+
+    #[must_use]
+    pub fn changes(&self) -> &[ManifestChange] {
+        &self.changes
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ManifestChange> {
+        self.changes.iter()
+    }
+
+    fn push(&mut self, change: ManifestChange) {
+        self.changes.push(change);
+    }
+
+    fn diff_stream_type(&mut self, old: &StreamType, new: &StreamType) {
+        match (old, new) {
+            (StreamType::Live(old), StreamType::Live(new)) => self.diff_live(old, new),
+            (StreamType::Live(_), StreamType::Vod) => self.push(ManifestChange::StreamEnded),
+            _ => {}
+        }
+    }
+
+    fn diff_live(&mut self, old: &LiveStream, new: &LiveStream) {
+        if old.active_presentation != new.active_presentation {
+            self.push(ManifestChange::ActivePresentationChanged {
+                old: old.active_presentation.clone(),
+                new: new.active_presentation.clone(),
+            });
+        }
+        if old.current_time != new.current_time {
+            self.push(ManifestChange::CurrentTimeChanged {
+                old: old.current_time,
+                new: new.current_time,
+            });
+        }
+    }
+
+    fn diff_presentation(&mut self, old: &Presentation, new: &Presentation) {
+        if old.time_bounds() != new.time_bounds() {
+            self.push(ManifestChange::TimeBoundsChanged {
+                presentation_id: old.id().to_owned(),
+                old: old.time_bounds(),
+                new: new.time_bounds(),
+            });
+        }
+        let new_tracks: HashMap<&TrackUid, &dyn Track> =
+            new.tracks().map(|track| (track.uid(), track)).collect();
+        for track in old.tracks() {
+            match new_tracks.get(track.uid()) {
+                Some(newer) => self.diff_segments(track, *newer),
+                None => self.push(ManifestChange::TrackRemoved(track.uid().clone())),
+            }
+        }
+        for track in new.tracks() {
+            if old
+                .track(
+                    track.uid().media_type(),
+                    track.uid().switching_set_id(),
+                    track.uid().track_id(),
+                )
+                .is_none()
+            {
+                self.push(ManifestChange::TrackAdded(track.uid().clone()));
+            }
+        }
+    }
+
+    fn diff_segments(&mut self, old: &dyn Track, new: &dyn Track) {
+        let old_range = segment_range(old);
+        let new_range = segment_range(new);
+        for (first, last) in subtract(old_range, new_range) {
+            self.push(ManifestChange::SegmentsRemoved {
+                track_uid: old.uid().clone(),
+                first,
+                last,
+            });
+        }
+        for (first, last) in subtract(new_range, old_range) {
+            self.push(ManifestChange::SegmentsAdded {
+                track_uid: new.uid().clone(),
+                first,
+                last,
+            });
+        }
+    }
+}
+
+impl IntoIterator for ManifestDiff {
+    type Item = ManifestChange;
+    type IntoIter = std::vec::IntoIter<ManifestChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ManifestDiff {
+    type Item = &'a ManifestChange;
+    type IntoIter = std::slice::Iter<'a, ManifestChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+type SegmentRange = Option<(SegmentId, SegmentId)>;
+
+fn segment_range(track: &dyn Track) -> SegmentRange {
+    let segments = track.segments();
+    Some((segments.first()?.id(), segments.last()?.id()))
+}
+
+/// Returns the inclusive ranges of `a` that are not covered by `b`.
+fn subtract(a: SegmentRange, b: SegmentRange) -> Vec<(SegmentId, SegmentId)> {
+    let Some((a_first, a_last)) = a else {
+        return Vec::new();
+    };
+    let Some((b_first, b_last)) = b else {
+        return vec![(a_first, a_last)];
+    };
+    let mut result = Vec::new();
+    if a_first < b_first {
+        result.push((a_first, a_last.min(b_first - 1)));
+    }
+    if a_last > b_last {
+        result.push((a_first.max(b_last.next()), a_last));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MediaType;
+
+    use super::*;
+    use crate::model::manifest::fixtures::{live_manifest, presentation, segment};
+
+    fn manifest(current_time: u64, segments: &[u64], end_time: Option<u64>) -> Manifest {
+        let segments = segments.iter().map(|&id| segment(id, None));
+        live_manifest(
+            current_time,
+            1500,
+            "0",
+            [presentation("0", 0, end_time, segments)],
+        )
+    }
+
+    fn uid() -> TrackUid {
+        TrackUid::new(
+            "0".to_owned(),
+            MediaType::Video,
+            "main".to_owned(),
+            "720p".to_owned(),
+        )
+    }
+
+    #[test]
+    fn identical_manifests_have_empty_diff() {
+        let old = manifest(10, &[1, 2], None);
+
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn diff_segments_and_current_time() {
+        let old = manifest(10, &[1, 2, 3], None);
+        let new = manifest(14, &[2, 3, 4, 5], None);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.changes(),
+            [
+                ManifestChange::CurrentTimeChanged {
+                    old: 10.into(),
+                    new: 14.into(),
+                },
+                ManifestChange::SegmentsRemoved {
+                    track_uid: uid(),
+                    first: 1.into(),
+                    last: 1.into(),
+                },
+                ManifestChange::SegmentsAdded {
+                    track_uid: uid(),
+                    first: 4.into(),
+                    last: 5.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_closed_time_bounds() {
+        let old = manifest(10, &[1], None);
+        let new = manifest(10, &[1], Some(20));
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.changes(),
+            [ManifestChange::TimeBoundsChanged {
+                presentation_id: "0".to_owned(),
+                old: TimeBounds::new(Some(0), None, crate::Scale::ONE).unwrap(),
+                new: TimeBounds::new(Some(0), Some(20), crate::Scale::ONE).unwrap(),
+            }]
+        );
+    }
+}
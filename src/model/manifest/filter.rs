@@ -0,0 +1,249 @@
+use crate::util::{Entity, EntityMap};
+use crate::{
+    AudioSwitchingSet, AudioTrack, Error, Manifest, MetadataSwitchingSet, MetadataTrack,
+    Presentation, Result, StreamType, SwitchingSet, SwitchingSetProtection, Track,
+    VideoSwitchingSet, VideoTrack,
+};
+
+use super::validate_active;
+
+/// A track together with its switching set, as passed to [`Manifest::filter_tracks`].
+#[derive(Debug, Clone, Copy)]
+pub enum TrackRef<'a> {
+    Audio(&'a AudioSwitchingSet, &'a AudioTrack),
+    Video(&'a VideoSwitchingSet, &'a VideoTrack),
+    Metadata(&'a MetadataSwitchingSet, &'a MetadataTrack),
+}
+
+impl<'a> TrackRef<'a> {
+    #[must_use]
+    pub fn track(self) -> &'a dyn Track {
+        match self {
+            Self::Audio(_, track) => track,
+            Self::Video(_, track) => track,
+            Self::Metadata(_, track) => track,
+        }
+    }
+
+    #[must_use]
+    pub const fn protection(self) -> Option<&'a SwitchingSetProtection> {
+        match self {
+            Self::Audio(set, _) => set.protection(),
+            Self::Video(set, _) => set.protection(),
+            Self::Metadata(..) => None,
+        }
+    }
+}
+
+impl Manifest {
+    /// Removes the tracks for which `predicate` returns `false`, and then the switching sets
+    /// that are left without tracks.
+    ///
+    /// The remaining tracks and switching sets keep their order. Fails without changing the
+    /// manifest when the active presentation of a live stream would be left without audio and
+    /// video tracks.
+    pub fn filter_tracks<F>(&mut self, mut predicate: F) -> Result<()>
+    where
+        F: FnMut(TrackRef<'_>) -> bool,
+    {
+        let mut presentations = self.presentations.clone();
+        for presentation in &mut presentations {
+            presentation.filter_tracks(&mut predicate);
+        }
+        validate_active(&self.stream_type, &presentations)?;
+        if let StreamType::Live(live) = &self.stream_type {
+            let active = presentations.get(&live.active_presentation);
+            if active.is_some_and(|active| active.video().len() + active.audio().len() == 0) {
+                return Err(Error::NoActiveTracks(live.active_presentation.clone()));
+            }
+        }
+        self.presentations = presentations;
+        Ok(())
+    }
+}
+
+impl Presentation {
+    fn filter_tracks<F>(&mut self, predicate: &mut F)
+    where
+        F: FnMut(TrackRef<'_>) -> bool,
+    {
+        filter(
+            &mut self.audio,
+            |set| &mut set.tracks,
+            |set, track| predicate(TrackRef::Audio(set, track)),
+        );
+        filter(
+            &mut self.video,
+            |set| &mut set.tracks,
+            |set, track| predicate(TrackRef::Video(set, track)),
+        );
+        filter(
+            &mut self.metadata,
+            |set| &mut set.tracks,
+            |set, track| predicate(TrackRef::Metadata(set, track)),
+        );
+    }
+}
+
+fn filter<S, T, F>(sets: &mut EntityMap<S>, tracks: T, mut keep: F)
+where
+    S: SwitchingSet,
+    S::Track: Entity,
+    T: Fn(&mut S) -> &mut EntityMap<S::Track>,
+    F: FnMut(&S, &S::Track) -> bool,
+{
+    for set in sets.iter_mut() {
+        let removed: Vec<_> = set
+            .tracks()
+            .filter(|track| !keep(set, track))
+            .map(|track| track.id().to_owned())
+            .collect();
+        tracks(set).retain(|track| !removed.iter().any(|id| id == track.id()));
+    }
+    sets.retain(|set| set.tracks().len() > 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{PlaybackCapabilities, Resolution};
+
+    const MANIFEST: &str = r#"
+        {
+            "manifestVersion": "2.0.0",
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "streamType": "vod",
+            "presentations": [{
+                "id": "0",
+                "timeBounds": {"startTime": 0, "endTime": 10},
+                "video": [
+                    {
+                        "id": "main",
+                        "codecs": "avc1.4d001f",
+                        "frameRate": {"value": 25},
+                        "continuationPattern": "video/{segmentId}.mp4",
+                        "initializationPattern": "video/init-{initId}.mp4",
+                        "tracks": [
+                            {
+                                "id": "720p",
+                                "bandwidth": 3000000,
+                                "resolution": {"width": 1280, "height": 720},
+                                "segmentDuration": {"value": 2},
+                                "segments": [{"id": 0}]
+                            },
+                            {
+                                "id": "1080p",
+                                "bandwidth": 6000000,
+                                "resolution": {"width": 1920, "height": 1080},
+                                "segmentDuration": {"value": 2},
+                                "segments": [{"id": 0}]
+                            }
+                        ]
+                    },
+                    {
+                        "id": "fairplay",
+                        "codecs": "avc1.4d001f",
+                        "frameRate": {"value": 25},
+                        "protection": {
+                            "type": "cbcs",
+                            "systems": [{
+                                "schemeId": "94ce86fb-07ff-4f43-adb8-93d2fa968ca2",
+                                "uri": "skd://key",
+                                "keyformat": "com.apple.streamingkeydelivery",
+                                "keyformatversions": "1"
+                            }]
+                        },
+                        "continuationPattern": "fairplay/{segmentId}.mp4",
+                        "initializationPattern": "fairplay/init-{initId}.mp4",
+                        "tracks": [{
+                            "id": "720p",
+                            "bandwidth": 3000000,
+                            "resolution": {"width": 1280, "height": 720},
+                            "segmentDuration": {"value": 2},
+                            "segments": [{"id": 0}]
+                        }]
+                    }
+                ],
+                "audio": [
+                    {
+                        "id": "aac",
+                        "codecs": "mp4a.40.2",
+                        "language": "eng",
+                        "continuationPattern": "aac/{segmentId}.mp4",
+                        "initializationPattern": "aac/init-{initId}.mp4",
+                        "tracks": [{
+                            "id": "128k",
+                            "bandwidth": 128000,
+                            "sampleRate": 48000,
+                            "segmentDuration": {"value": 2},
+                            "segments": [{"id": 0}]
+                        }]
+                    },
+                    {
+                        "id": "dolby",
+                        "codecs": "ec-3",
+                        "language": "eng",
+                        "continuationPattern": "dolby/{segmentId}.mp4",
+                        "initializationPattern": "dolby/init-{initId}.mp4",
+                        "tracks": [{
+                            "id": "384k",
+                            "bandwidth": 384000,
+                            "sampleRate": 48000,
+                            "segmentDuration": {"value": 2},
+                            "segments": [{"id": 0}]
+                        }]
+                    }
+                ]
+            }]
+        }"#;
+
+    #[test]
+    fn drop_unsupported_tracks() -> anyhow::Result<()> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let mut manifest = Manifest::from_json(location, MANIFEST)?;
+        let capabilities = PlaybackCapabilities {
+            max_resolution: Some(Resolution::new(1280, 720)),
+            codecs: Some(vec!["avc1".to_owned(), "mp4a.40".to_owned()]),
+            drm_systems: Some(vec![Uuid::parse_str(
+                "edef8ba9-79d6-4ace-a3c8-27dcd51d21ed",
+            )?]),
+            ..PlaybackCapabilities::default()
+        };
+
+        manifest.filter_tracks(|track| capabilities.supports(track))?;
+
+        let presentation = manifest.presentation("0").unwrap();
+        let video: Vec<_> = presentation.video().map(Entity::id).collect();
+        assert_eq!(video, ["main"]);
+        let tracks: Vec<_> = presentation.video_tracks().map(Entity::id).collect();
+        assert_eq!(tracks, ["720p"]);
+        let audio: Vec<_> = presentation.audio().map(Entity::id).collect();
+        assert_eq!(audio, ["aac"]);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_manifest_when_active_presentation_loses_all_tracks() -> anyhow::Result<()> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let live = MANIFEST.replace(
+            r#""streamType": "vod","#,
+            r#""streamType": "live",
+            "activePresentation": "0",
+            "availabilityDuration": {"value": 60},
+            "currentTime": {"value": 4},"#,
+        );
+        let mut manifest = Manifest::from_json(location, &live)?;
+
+        let result = manifest.filter_tracks(|track| matches!(track, TrackRef::Metadata(..)));
+
+        assert!(matches!(result, Err(Error::NoActiveTracks(id)) if id == "0"));
+        let presentation = manifest.presentation("0").unwrap();
+        assert_eq!(presentation.video_tracks().count(), 3);
+        assert_eq!(presentation.audio_tracks().count(), 2);
+        Ok(())
+    }
+}
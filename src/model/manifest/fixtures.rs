@@ -0,0 +1,75 @@
+use chrono::DateTime;
+use url::Url;
+
+use crate::{
+    FrameRate, LiveStream, Manifest, ManifestBuilder, PresentationBuilder, Resolution, Scale,
+    ScaledDuration, Segment, TimeBounds, UnsignedScaledValue, VideoSwitchingSetBuilder,
+    VideoTrackBuilder,
+};
+
+/// Builds a live manifest created at `2021-03-31T08:00:00Z` from `presentations`.
+pub fn live_manifest(
+    current_time: u64,
+    availability_duration: u64,
+    active: &str,
+    presentations: impl IntoIterator<Item = PresentationBuilder>,
+) -> Manifest {
+    presentations
+        .into_iter()
+        .fold(
+            Manifest::builder(Url::parse("http://localhost").unwrap(), 300),
+            ManifestBuilder::presentation,
+        )
+        .creation_date(DateTime::parse_from_rfc3339("2021-03-31T08:00:00.000Z").unwrap())
+        .live(LiveStream {
+            availability_duration: ScaledDuration::new(availability_duration, Scale::ONE),
+            active_presentation: active.to_owned(),
+            time_source: None,
+            current_time: UnsignedScaledValue::new(current_time, Scale::ONE),
+        })
+        .build()
+        .unwrap()
+}
+
+/// Builds a VOD manifest from `presentations`.
+pub fn vod_manifest(presentations: impl IntoIterator<Item = PresentationBuilder>) -> Manifest {
+    presentations
+        .into_iter()
+        .fold(
+            Manifest::builder(Url::parse("http://localhost").unwrap(), 300),
+            ManifestBuilder::presentation,
+        )
+        .build()
+        .unwrap()
+}
+
+/// Builds a presentation with a single video track `main/720p` of two second segments.
+pub fn presentation(
+    id: &str,
+    start_time: u64,
+    end_time: Option<u64>,
+    segments: impl IntoIterator<Item = Segment>,
+) -> PresentationBuilder {
+    PresentationBuilder::new(
+        id,
+        TimeBounds::new(Some(start_time), end_time, Scale::ONE).unwrap(),
+    )
+    .video(
+        VideoSwitchingSetBuilder::new("main")
+            .codecs("avc1.4d001f")
+            .frame_rate(FrameRate::new(25, Scale::ONE))
+            .continuation_pattern("content-{segmentId}.mp4")
+            .initialization_pattern("init-{initId}.mp4")
+            .track(
+                VideoTrackBuilder::new("720p", 3_000_000, Resolution::new(1280, 720))
+                    .segment_duration(ScaledDuration::new(2, Scale::ONE))
+                    .segments(segments),
+            ),
+    )
+}
+
+/// Builds a segment, optionally with an explicit start time.
+pub fn segment(id: u64, start_time: Option<u64>) -> Segment {
+    let time_bounds = start_time.map(|start| TimeBounds::new(Some(start), None, Scale::ONE));
+    Segment::new(id.into(), time_bounds.transpose().unwrap())
+}
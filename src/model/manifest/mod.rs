@@ -2,7 +2,13 @@ use chrono::{DateTime, FixedOffset};
 use serde::Serialize;
 use url::Url;
 
+pub use builder::ManifestBuilder;
+pub use diff::{ManifestChange, ManifestDiff};
+pub use filter::TrackRef;
 pub use stream::*;
+pub use validation::{Severity, ValidationIssue, ValidationReport};
+pub use version::ManifestVersion;
+pub use window::{LiveWindow, TrackWindow};
 
 use crate::data::{ManifestData, ManifestDeserialize, ManifestSerialize};
 use crate::util::{Entity, EntityIter, EntityIterMut, EntityMap, FromEntities};
@@ -11,7 +17,17 @@ use crate::{
     VideoTrack,
 };
 
+mod builder;
+mod diff;
+mod filter;
+#[cfg(test)]
+mod fixtures;
+mod splice;
 mod stream;
+mod update;
+mod validation;
+mod version;
+mod window;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(into = "ManifestSerialize")]
@@ -23,6 +39,11 @@ pub struct Manifest {
 }
 
 impl Manifest {
+    #[must_use]
+    pub fn builder(location: Url, fallback_poll_rate: u64) -> ManifestBuilder {
+        ManifestBuilder::new(location, fallback_poll_rate)
+    }
+
     #[must_use]
     pub const fn stream_type(&self) -> &StreamType {
         &self.stream_type
@@ -67,12 +88,12 @@ impl Manifest {
     }
 
     #[must_use]
-    pub fn presentations(&self) -> EntityIter<Presentation> {
+    pub fn presentations(&self) -> EntityIter<'_, Presentation> {
         self.presentations.iter()
     }
 
     #[must_use]
-    pub fn presentations_mut(&mut self) -> EntityIterMut<Presentation> {
+    pub fn presentations_mut(&mut self) -> EntityIterMut<'_, Presentation> {
         self.presentations.iter_mut()
     }
 
@@ -84,6 +105,34 @@ impl Manifest {
         self.presentations.get_mut(id)
     }
 
+    /// Appends `presentation`, failing when its id is already taken.
+    pub fn insert_presentation(&mut self, presentation: Presentation) -> crate::Result<()> {
+        self.presentations.insert(presentation)
+    }
+
+    /// Inserts `presentation` at `index`, failing when its id is already taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the number of presentations.
+    pub fn insert_presentation_at(
+        &mut self,
+        index: usize,
+        presentation: Presentation,
+    ) -> crate::Result<()> {
+        self.presentations.insert_at(index, presentation)
+    }
+
+    /// Removes the presentation with `id`, failing when it is the active presentation.
+    pub fn remove_presentation(&mut self, id: &str) -> crate::Result<Option<Presentation>> {
+        if let StreamType::Live(live) = &self.stream_type {
+            if live.active_presentation == id {
+                return Err(Error::CannotRemoveActivePresentation(id.to_owned()));
+            }
+        }
+        Ok(self.presentations.remove(id))
+    }
+
     #[must_use]
     pub fn track(&self, track_uid: &TrackUid) -> Option<&dyn Track> {
         self.presentation(track_uid.presentation_id())?.track(
@@ -125,6 +174,11 @@ impl Manifest {
             )
     }
 
+    #[must_use]
+    pub fn diff(&self, newer: &Self) -> ManifestDiff {
+        ManifestDiff::new(self, newer)
+    }
+
     pub fn from_json(location: Url, json: &str) -> crate::Result<Self> {
         let deserializer = &mut serde_json::Deserializer::from_str(json);
         let data = match serde_path_to_error::deserialize(deserializer)? {
@@ -134,6 +188,19 @@ impl Manifest {
         };
         Self::new(location, data)
     }
+
+    /// Validates a manifest like [`Manifest::from_json`], but reports every problem instead of
+    /// only the first one.
+    #[must_use]
+    pub fn validate_json(location: Url, json: &str) -> ValidationReport {
+        validation::validate_json(location, json)
+    }
+
+    /// Serializes this manifest as JSON in the format of the given manifest `version`.
+    pub fn to_json_version(&self, version: ManifestVersion) -> crate::Result<String> {
+        let data = ManifestSerialize::new(self.clone(), version)?;
+        Ok(serde_json::to_string(&data)?)
+    }
 }
 
 fn validate_active(
@@ -161,6 +228,8 @@ fn validate_active(
 
 #[cfg(test)]
 mod tests {
+    use crate::{Segment, SwitchingSet};
+
     use super::*;
 
     #[test]
@@ -223,4 +292,98 @@ mod tests {
         );
         Ok(())
     }
+
+    const LIVE_MANIFEST: &str = r#"
+        {
+            "availabilityDuration": {"value": 60},
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "manifestVersion": "2.0.0",
+            "streamType": "live",
+            "activePresentation": "main",
+            "currentTime": {"value": 10},
+            "presentations": [{
+                "id": "main",
+                "timeBounds": {"startTime": 0},
+                "video": [{
+                    "id": "video",
+                    "codecs": "avc1.4d001f",
+                    "frameRate": {"value": 25},
+                    "continuationPattern": "content-{segmentId}.mp4",
+                    "initializationPattern": "init-{initId}.mp4",
+                    "tracks": [{
+                        "id": "720p",
+                        "bandwidth": 3000000,
+                        "resolution": {"width": 1280, "height": 720},
+                        "segmentDuration": {"value": 2},
+                        "segments": [{"id": 1}, {"id": 2}]
+                    }]
+                }]
+            }]
+        }"#;
+
+    const AD_PRESENTATION: &str = r#"
+        {
+            "id": "ad",
+            "timeBounds": {"startTime": 20, "endTime": 30},
+            "video": [{
+                "id": "ad-video",
+                "codecs": "avc1.4d001f",
+                "frameRate": {"value": 25},
+                "continuationPattern": "ad-{segmentId}.mp4",
+                "initializationPattern": "ad-init-{initId}.mp4",
+                "tracks": [{
+                    "id": "720p",
+                    "bandwidth": 2000000,
+                    "resolution": {"width": 1280, "height": 720},
+                    "segmentDuration": {"value": 2},
+                    "segments": [{"id": 0}]
+                }]
+            }]
+        }"#;
+
+    #[test]
+    fn edit_presentation_tree() -> anyhow::Result<()> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let mut manifest = Manifest::from_json(location.clone(), LIVE_MANIFEST)?;
+        let address = Address::new(location, None)?;
+        let ad = Presentation::new(&address, serde_json::from_str(AD_PRESENTATION)?)?;
+
+        assert!(matches!(
+            manifest.remove_presentation("main"),
+            Err(Error::CannotRemoveActivePresentation(id)) if id == "main"
+        ));
+        manifest.insert_presentation_at(0, ad.clone())?;
+        assert!(matches!(
+            manifest.insert_presentation(ad),
+            Err(Error::DuplicateId(id)) if id == "ad"
+        ));
+        let ids: Vec<_> = manifest.presentations().map(Entity::id).collect();
+        assert_eq!(ids, ["ad", "main"]);
+
+        let ad_video = manifest
+            .presentation_mut("ad")
+            .unwrap()
+            .remove_video_switching_set("ad-video")
+            .unwrap();
+        let main = manifest.presentation_mut("main").unwrap();
+        main.insert_video_switching_set(ad_video)?;
+        let uid: TrackUid = "main/video/ad-video/720p".parse()?;
+        assert_eq!(manifest.track(&uid).map(Track::uid), Some(&uid));
+
+        let track = manifest.track_mut(&uid).unwrap();
+        assert!(track.push_segment(Segment::new(2.into(), None)).is_err());
+        track.push_segment(Segment::new(1.into(), None))?;
+        assert_eq!(track.segments().len(), 2);
+
+        let set = manifest
+            .presentation_mut("main")
+            .unwrap()
+            .video_switching_set_mut("video")
+            .unwrap();
+        assert!(set.remove_track("720p").is_some());
+        assert!(set.tracks().next().is_none());
+        assert!(manifest.remove_presentation("ad")?.is_some());
+        Ok(())
+    }
 }
@@ -0,0 +1,421 @@
+use gcd::Gcd;
+
+use crate::util::{Entity, EntityMap};
+use crate::{
+    AudioTrack, Error, Manifest, MetadataTrack, Presentation, Result, ScaledValue, SegmentId,
+    Segments, StreamType, TimeBounds, Track, UnsignedScaledValue, VideoTrack,
+};
+
+impl Manifest {
+    /// Splices `presentation` into the timeline at `at`, e.g. to insert an ad.
+    ///
+    /// The presentation playing at `at` ends there, keeping only the segments that start before
+    /// `at`, and `presentation` is moved to start at `at`.
+    /// When it ends, a continuation of the interrupted presentation resumes `resume_offset`
+    /// after `at` in its content: the duration of `presentation` to catch up with a live
+    /// stream, or zero to continue where the content was interrupted. The continuation gets
+    /// the id of the interrupted presentation suffixed with the first free `-1`, `-2`, ...
+    /// and no events. The presentations after the interrupted one are moved along with the
+    /// continuation so the timeline stays contiguous.
+    ///
+    /// When the interrupted presentation is active, the active presentation becomes the one
+    /// that contains the current time. Returns the id of the continuation.
+    pub fn splice_presentation(
+        &mut self,
+        at: UnsignedScaledValue,
+        mut presentation: Presentation,
+        resume_offset: UnsignedScaledValue,
+    ) -> Result<String> {
+        if self.presentations.get(presentation.id()).is_some() {
+            return Err(Error::DuplicateId(presentation.id().to_owned()));
+        }
+        let interrupted = self
+            .presentations()
+            .find(|presentation| contains(presentation.time_bounds(), at))
+            .ok_or(Error::NoPresentationAt(at))?;
+        let ad_bounds = presentation.time_bounds();
+        let (Some(ad_start), Some(ad_duration)) = (ad_bounds.start_time(), ad_bounds.duration())
+        else {
+            return Err(Error::MissingPresentationDuration(
+                presentation.id().to_owned(),
+            ));
+        };
+        let ad_end = at
+            .checked_add(ad_duration.into())
+            .ok_or_else(|| Error::InvalidSegmentTimeBounds(presentation.id().to_owned()))?;
+        presentation
+            .move_to(ad_start, at)
+            .ok_or_else(|| Error::InvalidSegmentTimeBounds(presentation.id().to_owned()))?;
+
+        let resume_at = at
+            .checked_add(resume_offset)
+            .ok_or_else(|| Error::InvalidResumeOffset(interrupted.id().to_owned()))?;
+        let mut continuation = resume(interrupted, resume_at, ad_end)
+            .ok_or_else(|| Error::InvalidResumeOffset(interrupted.id().to_owned()))?;
+        let continuation_id = free_id(&self.presentations, interrupted.id(), presentation.id());
+        let interrupted_id = interrupted.id().to_owned();
+        let interrupted_bounds =
+            TimeBounds::from_values(interrupted.time_bounds().start_time(), Some(at))
+                .ok_or_else(|| Error::InvalidSplicePoint(interrupted_id.clone()))?;
+        let index = self.presentations.position(&interrupted_id).unwrap();
+        let later = self
+            .presentations()
+            .skip(index + 1)
+            .map(|later| {
+                let mut later = later.clone();
+                later.move_to(resume_at, ad_end).map(|()| later)
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| Error::InvalidSplicePoint(interrupted_id.clone()))?;
+
+        for (current, later) in self.presentations.iter_mut().skip(index + 1).zip(later) {
+            *current = later;
+        }
+        let interrupted = self.presentations.get_mut(&interrupted_id).unwrap();
+        interrupted.time_bounds = interrupted_bounds;
+        interrupted.truncate_segments(at);
+        let ad_id = presentation.id().to_owned();
+        self.presentations.insert_at(index + 1, presentation)?;
+        continuation.rename(continuation_id.clone());
+        self.presentations.insert_at(index + 2, continuation)?;
+
+        if let StreamType::Live(live) = &mut self.stream_type {
+            if live.active_presentation == interrupted_id && live.current_time >= at {
+                live.active_presentation = if live.current_time < ad_end {
+                    ad_id
+                } else {
+                    continuation_id.clone()
+                };
+            }
+        }
+        Ok(continuation_id)
+    }
+}
+
+impl Presentation {
+    /// Moves the presentation on the timeline so that `from` ends up at `to`.
+    fn move_to(&mut self, from: UnsignedScaledValue, to: UnsignedScaledValue) -> Option<()> {
+        let shift = |time: Option<UnsignedScaledValue>| match time {
+            Some(time) => time.checked_add(to)?.checked_sub(from).map(Some),
+            None => Some(None),
+        };
+        let start = shift(self.time_bounds.start_time())?;
+        let end = shift(self.time_bounds.end_time())?;
+        self.time_bounds = TimeBounds::from_values(start, end)?;
+        for track in self.video_tracks_mut() {
+            move_track(track, from, to)?;
+        }
+        for track in self.audio_tracks_mut() {
+            move_track(track, from, to)?;
+        }
+        for track in self.metadata_tracks_mut() {
+            move_track(track, from, to)?;
+        }
+        Some(())
+    }
+
+    /// Removes the segments of all tracks that start at or after `time`.
+    fn truncate_segments(&mut self, time: UnsignedScaledValue) {
+        for track in self.video_tracks_mut() {
+            truncate_at(track, time);
+        }
+        for track in self.audio_tracks_mut() {
+            truncate_at(track, time);
+        }
+        for track in self.metadata_tracks_mut() {
+            truncate_at(track, time);
+        }
+    }
+}
+
+/// Creates the continuation of `interrupted` that resumes its content at `resume_at` and
+/// starts at `start` on the timeline.
+fn resume(
+    interrupted: &Presentation,
+    resume_at: UnsignedScaledValue,
+    start: UnsignedScaledValue,
+) -> Option<Presentation> {
+    let end = interrupted.time_bounds().end_time();
+    if end.is_some_and(|end| resume_at > end) {
+        return None;
+    }
+    let mut continuation = interrupted.clone();
+    continuation.events = EntityMap::default();
+    continuation.time_bounds = TimeBounds::from_values(Some(resume_at), end)?;
+    for track in continuation.video_tracks_mut() {
+        skip_to(track, resume_at);
+    }
+    for track in continuation.audio_tracks_mut() {
+        skip_to(track, resume_at);
+    }
+    for track in continuation.metadata_tracks_mut() {
+        skip_to(track, resume_at);
+    }
+    continuation.move_to(resume_at, start)?;
+    Some(continuation)
+}
+
+fn skip_to<T: Timeline>(track: &mut T, time: UnsignedScaledValue) {
+    if let Some(segment_id) = track.segment_at(time) {
+        track.remove_segments_before(segment_id);
+        *track.start_segment_id_mut() = segment_id;
+    }
+}
+
+fn truncate_at<T: Timeline>(track: &mut T, time: UnsignedScaledValue) {
+    let segment_duration = track.segment_duration();
+    track.segments_mut().truncate_at(time, segment_duration);
+}
+
+fn move_track<T: Timeline>(
+    track: &mut T,
+    from: UnsignedScaledValue,
+    to: UnsignedScaledValue,
+) -> Option<()> {
+    track.segments_mut().shift(from, to)?;
+    let offset = track.media_time_offset_mut();
+    *offset = shift_offset(*offset, from, to)?;
+    Some(())
+}
+
+/// Keeps the media time of a track the same when its presentation time moves from `from` to `to`.
+fn shift_offset(
+    offset: ScaledValue,
+    from: UnsignedScaledValue,
+    to: UnsignedScaledValue,
+) -> Option<ScaledValue> {
+    let scales = [offset.scale, from.scale, to.scale].map(u64::from);
+    let scale = scales
+        .into_iter()
+        .try_fold(1u64, |lcm, scale| (lcm / lcm.gcd(scale)).checked_mul(scale))?;
+    let units = |value: i128, unit_scale: u64| value.checked_mul(i128::from(scale / unit_scale));
+    let value = units(offset.value.into(), scales[0])?
+        .checked_add(units(from.value.into(), scales[1])?)?
+        .checked_sub(units(to.value.into(), scales[2])?)?;
+    let gcd = i128::try_from(value.unsigned_abs().gcd(u128::from(scale))).ok()?;
+    Some(ScaledValue::new(
+        i64::try_from(value / gcd).ok()?,
+        u64::try_from(i128::from(scale) / gcd)
+            .ok()?
+            .try_into()
+            .ok()?,
+    ))
+}
+
+fn contains(time_bounds: TimeBounds, time: UnsignedScaledValue) -> bool {
+    time_bounds.start_time().is_none_or(|start| start <= time)
+        && time_bounds.end_time().is_none_or(|end| time < end)
+}
+
+fn free_id(presentations: &EntityMap<Presentation>, base: &str, taken: &str) -> String {
+    // at most `len + 1` of these ids are taken
+    (1..=presentations.len() + 2)
+        .map(|n| format!("{base}-{n}"))
+        .find(|id| id != taken && presentations.get(id).is_none())
+        .unwrap()
+}
+
+/// Timeline properties of a track that move along with its presentation.
+trait Timeline: Track {
+    fn segments_mut(&mut self) -> &mut Segments;
+    fn media_time_offset_mut(&mut self) -> &mut ScaledValue;
+    fn start_segment_id_mut(&mut self) -> &mut SegmentId;
+}
+
+macro_rules! impl_timeline {
+    ($($track:ty),*) => {$(
+        impl Timeline for $track {
+            fn segments_mut(&mut self) -> &mut Segments {
+                &mut self.segments
+            }
+
+            fn media_time_offset_mut(&mut self) -> &mut ScaledValue {
+                &mut self.media_time_offset
+            }
+
+            fn start_segment_id_mut(&mut self) -> &mut SegmentId {
+                &mut self.start_segment_id
+            }
+        }
+    )*};
+}
+
+impl_timeline!(VideoTrack, AudioTrack, MetadataTrack);
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::model::manifest::fixtures::{presentation, segment, vod_manifest};
+    use crate::{Address, Scale, Segment};
+
+    const MANIFEST: &str = r#"
+        {
+            "availabilityDuration": {"value": 60},
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "manifestVersion": "2.0.0",
+            "streamType": "live",
+            "activePresentation": "main",
+            "currentTime": {"value": 12},
+            "presentations": [{
+                "id": "main",
+                "timeBounds": {"startTime": 0},
+                "video": [{
+                    "id": "video",
+                    "codecs": "avc1.4d001f",
+                    "frameRate": {"value": 25},
+                    "continuationPattern": "content-{segmentId}.mp4",
+                    "initializationPattern": "init-{initId}.mp4",
+                    "tracks": [{
+                        "id": "720p",
+                        "bandwidth": 3000000,
+                        "resolution": {"width": 1280, "height": 720},
+                        "segmentDuration": {"value": 2},
+                        "segments": [
+                            {"id": 1, "timeBounds": {"startTime": 0}}, {"id": 2}, {"id": 3},
+                            {"id": 4}, {"id": 5}, {"id": 6}, {"id": 7}, {"id": 8}, {"id": 9},
+                            {"id": 10}, {"id": 11}, {"id": 12}
+                        ]
+                    }]
+                }]
+            }]
+        }"#;
+
+    const AD: &str = r#"
+        {
+            "id": "ad",
+            "timeBounds": {"startTime": 100, "endTime": 110},
+            "video": [{
+                "id": "video",
+                "codecs": "avc1.4d001f",
+                "frameRate": {"value": 25},
+                "continuationPattern": "ad-{segmentId}.mp4",
+                "initializationPattern": "ad-init-{initId}.mp4",
+                "tracks": [{
+                    "id": "720p",
+                    "bandwidth": 2000000,
+                    "resolution": {"width": 1280, "height": 720},
+                    "segmentDuration": {"value": 2},
+                    "segments": [{"id": 0, "timeBounds": {"startTime": 100}}]
+                }]
+            }]
+        }"#;
+
+    fn splice(resume_offset: u64) -> anyhow::Result<Manifest> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let mut manifest = Manifest::from_json(location.clone(), MANIFEST)?;
+        let ad = Presentation::new(&Address::new(location, None)?, serde_json::from_str(AD)?)?;
+
+        let id = manifest.splice_presentation(10.into(), ad, resume_offset.into())?;
+
+        assert_eq!(id, "main-1");
+        let ids: Vec<_> = manifest.presentations().map(Entity::id).collect();
+        assert_eq!(ids, ["main", "ad", "main-1"]);
+        let main = manifest.presentation("main").unwrap();
+        assert_eq!(main.time_bounds().end_time(), Some(10.into()));
+        let main_track = main.video_tracks().next().unwrap();
+        let last = main_track.segments().last().map(Segment::id);
+        assert_eq!(last, Some(5.into()));
+        let ad = manifest.presentation("ad").unwrap();
+        assert_eq!(
+            ad.time_bounds(),
+            TimeBounds::new(Some(10), Some(20), Scale::ONE)?
+        );
+        let ad_track = ad.video_tracks().next().unwrap();
+        assert_eq!(ad_track.media_time_offset(), ScaledValue::from(90));
+        assert_eq!(ad_track.segment_at(10.into()), Some(0.into()));
+        assert_eq!(manifest.active_presentation().map(Entity::id), Some("ad"));
+        Ok(manifest)
+    }
+
+    #[test]
+    fn splice_into_live_stream() -> anyhow::Result<()> {
+        let manifest = splice(10)?;
+
+        let continuation = manifest.presentation("main-1").unwrap();
+        assert_eq!(continuation.time_bounds().start_time(), Some(20.into()));
+        assert_eq!(continuation.time_bounds().end_time(), None);
+        let track = continuation.video_tracks().next().unwrap();
+        assert_eq!(track.uid().to_string(), "main-1/video/video/720p");
+        assert_eq!(track.start_segment_id(), 11.into());
+        assert_eq!(track.segment_at(20.into()), Some(11.into()));
+        assert_eq!(track.media_time_offset(), ScaledValue::from(0));
+        Ok(())
+    }
+
+    #[test]
+    fn resume_where_content_was_interrupted() -> anyhow::Result<()> {
+        let manifest = splice(0)?;
+
+        let continuation = manifest.presentation("main-1").unwrap();
+        let track = continuation.video_tracks().next().unwrap();
+        assert_eq!(track.start_segment_id(), 6.into());
+        assert_eq!(track.segment_at(20.into()), Some(6.into()));
+        assert_eq!(track.media_time_offset(), ScaledValue::from(-10));
+        Ok(())
+    }
+
+    #[test]
+    fn move_later_presentations_along() -> anyhow::Result<()> {
+        let segments = |start: u64, count: u64| {
+            (1..=count).map(move |id| segment(id, (id == 1).then_some(start)))
+        };
+        let mut manifest = vod_manifest([
+            presentation("main", 0, Some(24), segments(0, 12)),
+            presentation("next", 24, Some(30), segments(24, 3)),
+        ]);
+        let location = Url::parse("http://localhost")?;
+        let ad = Presentation::new(&Address::new(location, None)?, serde_json::from_str(AD)?)?;
+
+        manifest.splice_presentation(10.into(), ad, 0.into())?;
+
+        let ids: Vec<_> = manifest.presentations().map(Entity::id).collect();
+        assert_eq!(ids, ["main", "ad", "main-1", "next"]);
+        let continuation = manifest.presentation("main-1").unwrap();
+        assert_eq!(
+            continuation.time_bounds(),
+            TimeBounds::new(Some(20), Some(34), Scale::ONE)?
+        );
+        let next = manifest.presentation("next").unwrap();
+        assert_eq!(
+            next.time_bounds(),
+            TimeBounds::new(Some(34), Some(40), Scale::ONE)?
+        );
+        let track = next.video_tracks().next().unwrap();
+        assert_eq!(track.segment_at(34.into()), Some(1.into()));
+        assert_eq!(track.media_time_offset(), ScaledValue::from(-10));
+        Ok(())
+    }
+
+    #[test]
+    fn reject_ad_without_duration() -> anyhow::Result<()> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let mut manifest = Manifest::from_json(location.clone(), MANIFEST)?;
+        let ad = AD.replace(r#", "endTime": 110"#, "");
+        let ad = Presentation::new(&Address::new(location, None)?, serde_json::from_str(&ad)?)?;
+
+        let result = manifest.splice_presentation(10.into(), ad, 0.into());
+
+        assert!(matches!(result, Err(Error::MissingPresentationDuration(id)) if id == "ad"));
+        assert_eq!(manifest.presentations().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn reject_ad_with_taken_id() -> anyhow::Result<()> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let mut manifest = Manifest::from_json(location.clone(), MANIFEST)?;
+        let ad = AD.replace(r#""id": "ad""#, r#""id": "main""#);
+        let ad = Presentation::new(&Address::new(location, None)?, serde_json::from_str(&ad)?)?;
+
+        let result = manifest.splice_presentation(10.into(), ad, 0.into());
+
+        assert!(matches!(result, Err(Error::DuplicateId(id)) if id == "main"));
+        let main = manifest.presentation("main").unwrap();
+        assert_eq!(main.time_bounds().end_time(), None);
+        assert_eq!(manifest.presentations().len(), 1);
+        Ok(())
+    }
+}
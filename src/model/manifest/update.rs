@@ -0,0 +1,224 @@
+use std::mem;
+
+use crate::util::{Entity, EntityMap};
+use crate::{
+    AudioSwitchingSet, Manifest, MetadataSwitchingSet, Presentation, Segments, StreamType,
+    UnsignedScaledValue, VideoSwitchingSet,
+};
+
+impl Manifest {
+    /// Merges a newer revision of this (live) manifest into `self`.
+    ///
+    /// Presentations, switching sets and tracks that are still present are updated in place and
+    /// their segments are extended with the newly announced ones. Everything that is no longer
+    /// listed in `newer` is dropped. For live streams, segments that ended before the
+    /// availability window are pruned afterwards.
+    pub fn apply_update(&mut self, newer: Self) {
+        self.creation_date = newer.creation_date;
+        self.fallback_poll_rate = newer.fallback_poll_rate;
+        self.stream_type = newer.stream_type;
+        merge_entities(
+            &mut self.presentations,
+            newer.presentations,
+            merge_presentation,
+        );
+        if let StreamType::Live(live) = &self.stream_type {
+            if let Some(start) = live
+                .current_time
+                .checked_sub(live.availability_duration.into())
+            {
+                self.prune_segments(start);
+            }
+        }
+    }
+
+    fn prune_segments(&mut self, start: UnsignedScaledValue) {
+        for presentation in &mut self.presentations {
+            for track in presentation.video_tracks_mut() {
+                track.segments.prune_before(start, track.segment_duration);
+            }
+            for track in presentation.audio_tracks_mut() {
+                track.segments.prune_before(start, track.segment_duration);
+            }
+            for track in presentation.metadata_tracks_mut() {
+                track.segments.prune_before(start, track.segment_duration);
+            }
+        }
+    }
+}
+
+/// Rebuilds `old` in the order of `newer`, merging the entities that already existed.
+fn merge_entities<E: Entity>(old: &mut EntityMap<E>, newer: EntityMap<E>, merge: fn(&mut E, E)) {
+    let mut previous = mem::take(old);
+    for entity in newer {
+        let entity = match previous.remove(entity.id()) {
+            Some(mut existing) => {
+                merge(&mut existing, entity);
+                existing
+            }
+            None => entity,
+        };
+        old.insert(entity)
+            .expect("ids are unique within a validated manifest");
+    }
+}
+
+fn merge_presentation(old: &mut Presentation, newer: Presentation) {
+    old.time_bounds = newer.time_bounds;
+    old.events = newer.events;
+    merge_entities(&mut old.audio, newer.audio, merge_audio);
+    merge_entities(&mut old.metadata, newer.metadata, merge_metadata);
+    merge_entities(&mut old.video, newer.video, merge_video);
+}
+
+fn merge_audio(old: &mut AudioSwitchingSet, newer: AudioSwitchingSet) {
+    old.language = newer.language;
+    old.align_id = newer.align_id;
+    old.channels = newer.channels;
+    old.label = newer.label;
+    old.mime_type = newer.mime_type;
+    old.protection = newer.protection;
+    merge_entities(&mut old.tracks, newer.tracks, |old, newer| {
+        merge_track(old, newer, |track| &mut track.segments);
+    });
+}
+
+fn merge_metadata(old: &mut MetadataSwitchingSet, newer: MetadataSwitchingSet) {
+    old.language = newer.language;
+    old.scheme_id = newer.scheme_id;
+    old.align_id = newer.align_id;
+    old.label = newer.label;
+    old.mime_type = newer.mime_type;
+    merge_entities(&mut old.tracks, newer.tracks, |old, newer| {
+        merge_track(old, newer, |track| &mut track.segments);
+    });
+}
+
+fn merge_video(old: &mut VideoSwitchingSet, newer: VideoSwitchingSet) {
+    old.align_id = newer.align_id;
+    old.label = newer.label;
+    old.mime_type = newer.mime_type;
+    old.protection = newer.protection;
+    merge_entities(&mut old.tracks, newer.tracks, |old, newer| {
+        merge_track(old, newer, |track| &mut track.segments);
+    });
+}
+
+/// Replaces `old` by `newer` while keeping the segments of `old` that precede those of `newer`.
+fn merge_track<T>(old: &mut T, newer: T, segments: fn(&mut T) -> &mut Segments) {
+    let mut previous = mem::replace(old, newer);
+    let newer_segments = mem::take(segments(old));
+    let merged = segments(&mut previous);
+    merged.merge(newer_segments);
+    mem::swap(segments(old), merged);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::manifest::fixtures::{live_manifest, presentation, segment};
+    use crate::{MediaType, PresentationBuilder, Segment, SegmentId, TrackUid};
+
+    use super::*;
+
+    fn manifest(
+        current_time: u64,
+        active: &str,
+        presentations: impl IntoIterator<Item = PresentationBuilder>,
+    ) -> Manifest {
+        live_manifest(current_time, 6, active, presentations)
+    }
+
+    fn segment_ids(manifest: &Manifest, presentation_id: &str) -> Vec<SegmentId> {
+        let uid = TrackUid::new(
+            presentation_id.to_owned(),
+            MediaType::Video,
+            "main".to_owned(),
+            "720p".to_owned(),
+        );
+        manifest
+            .track(&uid)
+            .unwrap()
+            .segments()
+            .iter()
+            .map(Segment::id)
+            .collect()
+    }
+
+    #[test]
+    fn apply_update_appends_segments() {
+        let mut manifest = manifest(
+            4,
+            "0",
+            [presentation(
+                "0",
+                0,
+                None,
+                [segment(1, Some(0)), segment(2, None)],
+            )],
+        );
+        let newer = manifest_with_segments(6, [segment(3, Some(4))]);
+
+        manifest.apply_update(newer);
+
+        assert_eq!(segment_ids(&manifest, "0"), [1.into(), 2.into(), 3.into()]);
+    }
+
+    #[test]
+    fn apply_update_prunes_unavailable_segments() {
+        let mut manifest = manifest(
+            6,
+            "0",
+            [presentation(
+                "0",
+                0,
+                None,
+                [segment(1, Some(0)), segment(2, None)],
+            )],
+        );
+        let newer = manifest_with_segments(10, [segment(3, None), segment(4, None)]);
+
+        manifest.apply_update(newer);
+
+        assert_eq!(segment_ids(&manifest, "0"), [3.into(), 4.into()]);
+        let uid = "0/video/main/720p".parse().unwrap();
+        let first = &manifest.track(&uid).unwrap().segments()[0];
+        assert_eq!(first.time_bounds().unwrap().start_time(), Some(4.into()));
+    }
+
+    #[test]
+    fn apply_update_adds_and_removes_presentations() {
+        let newer = manifest(4, "1", [presentation("1", 0, None, [segment(1, None)])]);
+        let mut manifest = manifest(4, "0", [presentation("0", 0, None, [segment(1, None)])]);
+
+        manifest.apply_update(newer);
+
+        assert!(manifest.presentation("0").is_none());
+        assert_eq!(segment_ids(&manifest, "1"), [1.into()]);
+    }
+
+    #[test]
+    fn apply_update_keeps_the_order_of_newer() {
+        let newer = manifest(
+            4,
+            "0",
+            ["0", "1", "2"].map(|id| presentation(id, 0, None, [segment(1, None)])),
+        );
+        let mut manifest = manifest(
+            4,
+            "0",
+            ["0", "2"].map(|id| presentation(id, 0, None, [segment(1, None)])),
+        );
+
+        manifest.apply_update(newer);
+
+        let ids: Vec<_> = manifest.presentations().map(Entity::id).collect();
+        assert_eq!(ids, ["0", "1", "2"]);
+    }
+
+    fn manifest_with_segments(
+        current_time: u64,
+        segments: impl IntoIterator<Item = Segment>,
+    ) -> Manifest {
+        manifest(current_time, "0", [presentation("0", 0, None, segments)])
+    }
+}
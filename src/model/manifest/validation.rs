@@ -0,0 +1,645 @@
+use std::collections::HashSet;
+use std::{fmt, iter, mem};
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use url::Url;
+
+use super::validate_active;
+use crate::data::{
+    AudioSwitchingSetData, ManifestData, ManifestDeserialize, ManifestSerialize,
+    MetadataSwitchingSetData, PresentationData, VideoSwitchingSetData,
+};
+use crate::util::{FromEntities, Uri};
+use crate::{
+    Address, AudioSwitchingSet, Error, ManifestVersion, MetadataSwitchingSet, Presentation,
+    PresentationEvent, Result, StreamType, VideoSwitchingSet,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The manifest can be used, but should be fixed.
+    Warning,
+    /// The manifest cannot be used.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// A single problem found while validating a manifest.
+#[derive(Debug)]
+pub struct ValidationIssue {
+    path: String,
+    presentation_id: Option<String>,
+    switching_set_id: Option<String>,
+    track_id: Option<String>,
+    severity: Severity,
+    error: Error,
+}
+
+impl ValidationIssue {
+    /// The JSON path of the offending field, e.g. `presentations[0].video[1].tracks[2].codecs`.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn presentation_id(&self) -> Option<&str> {
+        self.presentation_id.as_deref()
+    }
+
+    #[must_use]
+    pub fn switching_set_id(&self) -> Option<&str> {
+        self.switching_set_id.as_deref()
+    }
+
+    #[must_use]
+    pub fn track_id(&self) -> Option<&str> {
+        self.track_id.as_deref()
+    }
+
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    #[must_use]
+    pub const fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}: {}", self.severity, self.error)
+        } else {
+            write!(f, "{} at {}: {}", self.severity, self.path, self.error)
+        }
+    }
+}
+
+/// All problems found in a manifest, in document order.
+///
+/// Validation continues past invalid entities: a presentation, switching set or track that
+/// cannot be read is reported and skipped, while its siblings are still validated.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    #[must_use]
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.with_severity(Severity::Warning)
+    }
+
+    /// Whether the manifest has no errors, in which case [`Manifest::from_json`] accepts it.
+    ///
+    /// [`Manifest::from_json`]: crate::Manifest::from_json
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.severity == severity)
+    }
+}
+
+impl IntoIterator for ValidationReport {
+    type Item = ValidationIssue;
+    type IntoIter = std::vec::IntoIter<ValidationIssue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.issues.into_iter()
+    }
+}
+
+pub(super) fn validate_json(location: Url, json: &str) -> ValidationReport {
+    let mut validator = Validator::default();
+    validator.manifest(location, json);
+    ValidationReport {
+        issues: validator.issues,
+    }
+}
+
+/// The entity an issue is reported for.
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    path: String,
+    presentation_id: Option<String>,
+    switching_set_id: Option<String>,
+    track_id: Option<String>,
+}
+
+impl Scope {
+    fn child(&self, key: &str, index: usize) -> Self {
+        Self {
+            path: self.path(&format!("{key}[{index}]")),
+            ..self.clone()
+        }
+    }
+
+    fn path(&self, field: &str) -> String {
+        if field.is_empty() || field == "." {
+            self.path.clone()
+        } else if self.path.is_empty() || field.starts_with('[') {
+            format!("{}{field}", self.path)
+        } else {
+            format!("{}.{field}", self.path)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    issues: Vec<ValidationIssue>,
+}
+
+impl Validator {
+    fn manifest(&mut self, location: Url, json: &str) {
+        let root = Scope::default();
+        let value = match serde_json::from_str(json) {
+            Ok(value) => value,
+            Err(error) => return self.report(&root, Severity::Error, Error::Json(error)),
+        };
+        let Some(mut value) = self.upgrade(&root, value) else {
+            return;
+        };
+        let presentations = take_array(&mut value, "presentations");
+        let Some(data) = self.deserialize::<ManifestData>(&root, value) else {
+            return;
+        };
+        let Some(address) = self.check(&root, Address::new(location, data.content_base_url)) else {
+            return;
+        };
+        let mut ids = HashSet::new();
+        let presentations: Vec<_> = presentations
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let mut scope = root.child("presentations", index);
+                scope.presentation_id = self.unique(&mut ids, &scope, &value);
+                let data = self.presentation(&scope, &address, value)?;
+                Some((scope, data))
+            })
+            .collect();
+        if let StreamType::Live(live) = &data.stream_type {
+            let active = presentations
+                .iter()
+                .find(|(_, presentation)| presentation.id == live.active_presentation);
+            let result = match active {
+                Some((_, presentation)) => Presentation::new(&address, presentation.clone())
+                    .and_then(|presentation| iter::once(Ok(presentation)).into_entities())
+                    .and_then(|presentations| validate_active(&data.stream_type, &presentations)),
+                None => Err(Error::InvalidActivePresentationId(
+                    live.active_presentation.clone(),
+                )),
+            };
+            if let Err(error) = result {
+                match (active, &error) {
+                    (Some((scope, _)), Error::MissingStartTime(_)) => {
+                        self.report(scope, Severity::Error, error);
+                    }
+                    _ => self.report(&root, Severity::Error, error),
+                }
+            }
+        }
+    }
+
+    /// Converts legacy manifests to the 2.0.0 format, so all versions are validated alike.
+    fn upgrade(&mut self, scope: &Scope, value: Value) -> Option<Value> {
+        let version = value
+            .get("manifestVersion")
+            .and_then(Value::as_str)
+            .and_then(|version| version.parse().ok());
+        if version == Some(ManifestVersion::V2_0_0) {
+            return Some(value);
+        }
+        let data = match self.deserialize(scope, value)? {
+            ManifestDeserialize::V1_0_0(data) => data.try_into(),
+            ManifestDeserialize::V1_1_0(data) => data.try_into(),
+            ManifestDeserialize::V2_0_0(data) => Ok(data),
+        };
+        let data: ManifestData = self.check(scope, data)?;
+        if let Some(version) = version {
+            let error = Error::DeprecatedManifestVersion(version);
+            self.report(scope, Severity::Warning, error);
+        }
+        let json = serde_json::to_value(ManifestSerialize::V2_0_0(data));
+        self.check(scope, json.map_err(Error::from))
+    }
+
+    fn presentation(
+        &mut self,
+        scope: &Scope,
+        manifest_address: &Address,
+        mut value: Value,
+    ) -> Option<PresentationData> {
+        let audio = take_array(&mut value, "audio");
+        let events = take_array(&mut value, "events");
+        let metadata = take_array(&mut value, "metadata");
+        let video = take_array(&mut value, "video");
+        let data = self.deserialize::<PresentationData>(scope, value)?;
+        let mut ids = HashSet::new();
+        for (index, value) in events.into_iter().enumerate() {
+            let scope = scope.child("events", index);
+            self.unique(&mut ids, &scope, &value);
+            self.deserialize::<PresentationEvent>(&scope, value);
+        }
+        let address = self.check(scope, manifest_address.join(data.base_url.clone()))?;
+        self.switching_sets::<AudioSwitchingSetData>(scope, &data.id, &address, audio);
+        self.switching_sets::<MetadataSwitchingSetData>(scope, &data.id, &address, metadata);
+        self.switching_sets::<VideoSwitchingSetData>(scope, &data.id, &address, video);
+        Some(data)
+    }
+
+    fn switching_sets<S: SwitchingSetData>(
+        &mut self,
+        scope: &Scope,
+        presentation_id: &str,
+        presentation_address: &Address,
+        values: Vec<Value>,
+    ) {
+        let mut ids = HashSet::new();
+        for (index, mut value) in values.into_iter().enumerate() {
+            let mut scope = scope.child(S::KEY, index);
+            scope.switching_set_id = self.unique(&mut ids, &scope, &value);
+            let tracks = take_array(&mut value, "tracks");
+            let Some(set) = self.deserialize::<S>(&scope, value) else {
+                continue;
+            };
+            let address = presentation_address.join(set.base_url());
+            if self.check(&scope, address).is_none() {
+                continue;
+            }
+            let mut track_ids = HashSet::new();
+            for (index, mut value) in tracks.into_iter().enumerate() {
+                let mut scope = scope.child("tracks", index);
+                scope.track_id = self.unique(&mut track_ids, &scope, &value);
+                let mut fields = HashSet::new();
+                while let Some(track) = self.deserialize(&scope, value.clone()) {
+                    let Err(error) = set
+                        .with_track(track)
+                        .validate(presentation_id, presentation_address)
+                    else {
+                        break;
+                    };
+                    let field = field(&error);
+                    self.report(&scope, Severity::Error, error);
+                    match placeholder::<S>(field) {
+                        Some(placeholder) if fields.insert(field) => value[field] = placeholder,
+                        _ => break,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reports a duplicate id and returns the id of the entity, if it has one.
+    fn unique(
+        &mut self,
+        ids: &mut HashSet<String>,
+        scope: &Scope,
+        value: &Value,
+    ) -> Option<String> {
+        let id = value.get("id").and_then(Value::as_str)?.to_owned();
+        if !ids.insert(id.clone()) {
+            self.report(scope, Severity::Error, Error::DuplicateId(id.clone()));
+        }
+        Some(id)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&mut self, scope: &Scope, value: Value) -> Option<T> {
+        self.check(
+            scope,
+            serde_path_to_error::deserialize(value).map_err(Error::from),
+        )
+    }
+
+    fn check<T>(&mut self, scope: &Scope, result: Result<T>) -> Option<T> {
+        result
+            .map_err(|error| self.report(scope, Severity::Error, error))
+            .ok()
+    }
+
+    fn report(&mut self, scope: &Scope, severity: Severity, error: Error) {
+        let (path, error) = match error {
+            Error::InvalidJson(error) => (
+                scope.path(&error.path().to_string()),
+                Error::Json(error.into_inner()),
+            ),
+            error => (scope.path(field(&error)), error),
+        };
+        self.issues.push(ValidationIssue {
+            path,
+            presentation_id: scope.presentation_id.clone(),
+            switching_set_id: scope.switching_set_id.clone(),
+            track_id: scope.track_id.clone(),
+            severity,
+            error,
+        });
+    }
+}
+
+/// Takes the entities out of an array, so their parent can be read without them.
+fn take_array(value: &mut Value, key: &str) -> Vec<Value> {
+    match value.get_mut(key) {
+        Some(Value::Array(array)) => mem::take(array),
+        _ => Vec::new(),
+    }
+}
+
+/// The field of an entity that causes `error`.
+fn field(error: &Error) -> &'static str {
+    match error {
+        Error::DuplicateId(_) => "id",
+        Error::MissingCodecs(_) | Error::InvalidCodecs(_) => "codecs",
+        Error::MissingContinuationPattern(_) | Error::InvalidPattern(_, "{segmentId}") => {
+            "continuationPattern"
+        }
+        Error::MissingInitializationPattern(_) | Error::InvalidPattern(_, _) => {
+            "initializationPattern"
+        }
+        Error::MissingFrameRate(_) => "frameRate",
+        Error::MissingSampleRate(_) => "sampleRate",
+        Error::MissingSegmentDuration(_) => "segmentDuration",
+        Error::InvalidSegmentIds(..) => "segments",
+        Error::MissingStartTime(_) => "timeBounds.startTime",
+        Error::InvalidActivePresentationId(_) => "activePresentation",
+        Error::ImpossibleCurrentTime(_) => "currentTime",
+        Error::UrlParseError(_) => "baseUrl",
+        _ => "",
+    }
+}
+
+/// A valid value for `field` of a track, so the fields after an invalid one get validated too.
+fn placeholder<S: SwitchingSetData>(field: &str) -> Option<Value> {
+    Some(match field {
+        "codecs" => S::CODECS.into(),
+        "continuationPattern" => "{segmentId}".into(),
+        "initializationPattern" => "{initId}".into(),
+        "frameRate" => json!({"value": 25}),
+        "sampleRate" => 48000.into(),
+        "segmentDuration" => json!({"value": 1}),
+        _ => return None,
+    })
+}
+
+/// The switching set data types, whose tracks are validated one by one.
+trait SwitchingSetData: DeserializeOwned + Clone {
+    const KEY: &'static str;
+    const CODECS: &'static str;
+    type Track: DeserializeOwned;
+
+    fn base_url(&self) -> Option<Uri>;
+
+    fn with_track(&self, track: Self::Track) -> Self;
+
+    fn validate(self, presentation_id: &str, presentation_address: &Address) -> Result<()>;
+}
+
+impl SwitchingSetData for AudioSwitchingSetData {
+    const KEY: &'static str = "audio";
+    const CODECS: &'static str = "mp4a.40.2";
+    type Track = crate::data::AudioTrackData;
+
+    fn base_url(&self) -> Option<Uri> {
+        self.base_url.clone()
+    }
+
+    fn with_track(&self, track: Self::Track) -> Self {
+        Self {
+            tracks: vec![track],
+            ..self.clone()
+        }
+    }
+
+    fn validate(self, presentation_id: &str, presentation_address: &Address) -> Result<()> {
+        AudioSwitchingSet::new(presentation_id, presentation_address, self).map(drop)
+    }
+}
+
+impl SwitchingSetData for MetadataSwitchingSetData {
+    const KEY: &'static str = "metadata";
+    const CODECS: &'static str = "";
+    type Track = crate::data::MetadataTrackData;
+
+    fn base_url(&self) -> Option<Uri> {
+        self.base_url.clone()
+    }
+
+    fn with_track(&self, track: Self::Track) -> Self {
+        Self {
+            tracks: vec![track],
+            ..self.clone()
+        }
+    }
+
+    fn validate(self, presentation_id: &str, presentation_address: &Address) -> Result<()> {
+        MetadataSwitchingSet::new(presentation_id, presentation_address, self).map(drop)
+    }
+}
+
+impl SwitchingSetData for VideoSwitchingSetData {
+    const KEY: &'static str = "video";
+    const CODECS: &'static str = "avc1.4d001f";
+    type Track = crate::data::VideoTrackData;
+
+    fn base_url(&self) -> Option<Uri> {
+        self.base_url.clone()
+    }
+
+    fn with_track(&self, track: Self::Track) -> Self {
+        Self {
+            tracks: vec![track],
+            ..self.clone()
+        }
+    }
+
+    fn validate(self, presentation_id: &str, presentation_address: &Address) -> Result<()> {
+        VideoSwitchingSet::new(presentation_id, presentation_address, self).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manifest;
+
+    fn location() -> Url {
+        Url::parse("https://example.com/manifest.json").unwrap()
+    }
+
+    const INVALID_MANIFEST: &str = r#"
+        {
+            "manifestVersion": "2.0.0",
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "streamType": "live",
+            "activePresentation": "2",
+            "availabilityDuration": {"value": 1500},
+            "currentTime": {"value": 0},
+            "presentations": [
+                {
+                    "id": "0",
+                    "timeBounds": {"startTime": 0},
+                    "video": [{
+                        "id": "main",
+                        "frameRate": {"value": 25},
+                        "continuationPattern": "content-{segmentId}.mp4",
+                        "initializationPattern": "init-{initId}.mp4",
+                        "tracks": [
+                            {
+                                "id": "720p",
+                                "bandwidth": 3000000,
+                                "resolution": {"width": 1280, "height": 720},
+                                "segmentDuration": {"value": 2},
+                                "segments": [{"id": 1}]
+                            },
+                            {
+                                "id": "1080p",
+                                "bandwidth": 6000000,
+                                "codecs": "avc1.64002a",
+                                "resolution": {"width": 1920, "height": 1080},
+                                "segmentDuration": {"value": 2},
+                                "segments": [{"id": 1}, {"id": 3}]
+                            },
+                            {
+                                "id": "720p",
+                                "bandwidth": 3000000,
+                                "codecs": "avc1.4d001f",
+                                "resolution": {"width": 1280, "height": 720},
+                                "segmentDuration": {"value": 2},
+                                "segments": [{"id": 1}]
+                            }
+                        ]
+                    }]
+                },
+                {
+                    "id": "1",
+                    "timeBounds": {"startTime": 10, "endTime": 5}
+                },
+                {
+                    "id": "2",
+                    "timeBounds": {"endTime": 20},
+                    "audio": [{
+                        "id": "main",
+                        "language": "eng",
+                        "codecs": "mp4a.40.2",
+                        "continuationPattern": "content.mp4",
+                        "initializationPattern": "init-{initId}.mp4",
+                        "tracks": [{
+                            "id": "96kbps",
+                            "bandwidth": 96000,
+                            "segmentDuration": {"value": 2},
+                            "segments": [{"id": 1}]
+                        }]
+                    }]
+                }
+            ]
+        }"#;
+
+    #[test]
+    fn report_all_errors() {
+        let report = Manifest::validate_json(location(), INVALID_MANIFEST);
+
+        let issues: Vec<_> = report
+            .issues()
+            .iter()
+            .map(|issue| (issue.severity(), issue.path()))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (
+                    Severity::Error,
+                    "presentations[0].video[0].tracks[0].codecs"
+                ),
+                (
+                    Severity::Error,
+                    "presentations[0].video[0].tracks[1].segments"
+                ),
+                (Severity::Error, "presentations[0].video[0].tracks[2].id"),
+                (Severity::Error, "presentations[1].timeBounds"),
+                (
+                    Severity::Error,
+                    "presentations[2].audio[0].tracks[0].sampleRate"
+                ),
+                (
+                    Severity::Error,
+                    "presentations[2].audio[0].tracks[0].continuationPattern"
+                ),
+                (Severity::Error, "presentations[2].timeBounds.startTime"),
+            ]
+        );
+        let issue = &report.issues()[0];
+        assert!(matches!(issue.error(), Error::MissingCodecs(id) if id == "720p"));
+        assert_eq!(issue.presentation_id(), Some("0"));
+        assert_eq!(issue.switching_set_id(), Some("main"));
+        assert_eq!(issue.track_id(), Some("720p"));
+        assert!(matches!(
+            report.issues()[2].error(),
+            Error::DuplicateId(id) if id == "720p"
+        ));
+        assert!(matches!(
+            report.issues()[5].error(),
+            Error::InvalidPattern(pattern, "{segmentId}") if pattern == "content.mp4"
+        ));
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn accept_valid_manifest() {
+        let report = Manifest::validate_json(
+            location(),
+            include_str!("../../../tests/v2_0_0-manifest.json"),
+        );
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn warn_about_legacy_manifest() {
+        let report = Manifest::validate_json(
+            location(),
+            include_str!("../../../tests/v1_1_0-manifest.json"),
+        );
+
+        assert!(report.is_valid());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].error(),
+            Error::DeprecatedManifestVersion(ManifestVersion::V1_1_0)
+        ));
+    }
+
+    #[test]
+    fn report_invalid_json() {
+        let report = Manifest::validate_json(location(), "{");
+
+        assert_eq!(report.errors().count(), 1);
+        assert!(matches!(report.issues()[0].error(), Error::Json(_)));
+    }
+}
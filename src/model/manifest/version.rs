@@ -0,0 +1,43 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// The versions of the HESP manifest format that can be read and written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum ManifestVersion {
+    V1_0_0,
+    V1_1_0,
+    #[default]
+    V2_0_0,
+}
+
+impl ManifestVersion {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1_0_0 => "1.0.0",
+            Self::V1_1_0 => "1.1.0",
+            Self::V2_0_0 => "2.0.0",
+        }
+    }
+}
+
+impl FromStr for ManifestVersion {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        match input {
+            "1.0.0" => Ok(Self::V1_0_0),
+            "1.1.0" => Ok(Self::V1_1_0),
+            "2.0.0" => Ok(Self::V2_0_0),
+            _ => Err(Error::InvalidManifestVersion(input.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ManifestVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
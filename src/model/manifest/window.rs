@@ -0,0 +1,234 @@
+use chrono::{DateTime, TimeZone};
+
+use crate::{
+    LiveStream, Manifest, Presentation, Scale, SegmentId, StreamType, Track, TrackUid,
+    UnsignedScaledValue,
+};
+
+/// The part of a live stream that can be fetched at a given wall clock time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveWindow {
+    start: UnsignedScaledValue,
+    live_edge: UnsignedScaledValue,
+    tracks: Vec<TrackWindow>,
+}
+
+impl LiveWindow {
+    fn new(manifest: &Manifest, live: &LiveStream, live_edge: UnsignedScaledValue) -> Self {
+        let start = live_edge
+            .checked_sub(live.availability_duration.into())
+            .unwrap_or(UnsignedScaledValue::ZERO);
+        let tracks = manifest
+            .presentations()
+            .flat_map(|presentation| {
+                presentation.tracks().filter_map(move |track| {
+                    TrackWindow::new(presentation, track, start, live_edge)
+                })
+            })
+            .collect();
+        Self {
+            start,
+            live_edge,
+            tracks,
+        }
+    }
+
+    /// The earliest media time that is still available.
+    #[must_use]
+    pub const fn start(&self) -> UnsignedScaledValue {
+        self.start
+    }
+
+    /// The media time that is being produced right now.
+    #[must_use]
+    pub const fn live_edge(&self) -> UnsignedScaledValue {
+        self.live_edge
+    }
+
+    #[must_use]
+    pub fn tracks(&self) -> &[TrackWindow] {
+        &self.tracks
+    }
+
+    #[must_use]
+    pub fn track(&self, track_uid: &TrackUid) -> Option<&TrackWindow> {
+        self.tracks
+            .iter()
+            .find(|window| window.track_uid() == track_uid)
+    }
+}
+
+/// The segments of a single track that are available within a [`LiveWindow`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackWindow {
+    track_uid: TrackUid,
+    first_segment: SegmentId,
+    last_segment: SegmentId,
+    start_time: UnsignedScaledValue,
+    end_time: UnsignedScaledValue,
+}
+
+impl TrackWindow {
+    fn new(
+        presentation: &Presentation,
+        track: &dyn Track,
+        start: UnsignedScaledValue,
+        live_edge: UnsignedScaledValue,
+    ) -> Option<Self> {
+        let bounds = presentation.time_bounds();
+        let mut end = live_edge;
+        if let Some(presentation_end) = bounds.end_time() {
+            end = end.min(presentation_end);
+        }
+        let first_listed = track.segment_time_bounds(track.segments().first()?.id())?;
+        let start = start.max(first_listed.start_time()?);
+        if start >= end {
+            return None;
+        }
+        let first_segment = track.segment_at(start)?;
+        let start_time = track.segment_time_bounds(first_segment)?.start_time()?;
+        let mut last_segment = track.segment_at(end).unwrap_or(first_segment);
+        if last_segment > first_segment
+            && track.segment_time_bounds(last_segment)?.start_time()? >= end
+        {
+            last_segment -= 1;
+        }
+        Some(Self {
+            track_uid: track.uid().clone(),
+            first_segment,
+            last_segment,
+            start_time,
+            end_time: end,
+        })
+    }
+
+    #[must_use]
+    pub const fn track_uid(&self) -> &TrackUid {
+        &self.track_uid
+    }
+
+    /// The earliest segment that is still available.
+    #[must_use]
+    pub const fn first_segment(&self) -> SegmentId {
+        self.first_segment
+    }
+
+    /// The segment that contains the live edge (or the end of the presentation).
+    #[must_use]
+    pub const fn last_segment(&self) -> SegmentId {
+        self.last_segment
+    }
+
+    /// The media time at which the first available segment starts.
+    #[must_use]
+    pub const fn start_time(&self) -> UnsignedScaledValue {
+        self.start_time
+    }
+
+    /// The media time up to which this track is available.
+    #[must_use]
+    pub const fn end_time(&self) -> UnsignedScaledValue {
+        self.end_time
+    }
+}
+
+impl Manifest {
+    /// Computes which segments of each track are available at the wall clock time `now`.
+    ///
+    /// The live edge is the `currentTime` of the stream advanced by the time that passed since
+    /// the `creationDate` of this manifest. Returns `None` for VOD streams.
+    #[must_use]
+    pub fn live_window<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<LiveWindow> {
+        let StreamType::Live(live) = &self.stream_type else {
+            return None;
+        };
+        let elapsed = now
+            .clone()
+            .signed_duration_since(self.creation_date)
+            .num_milliseconds();
+        let elapsed = UnsignedScaledValue::new(u64::try_from(elapsed).unwrap_or(0), Scale::MILLIS);
+        let live_edge = live.current_time.checked_add(elapsed)?;
+        Some(LiveWindow::new(self, live, live_edge))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::model::manifest::fixtures::{live_manifest, presentation, segment};
+    use crate::PresentationBuilder;
+
+    use super::*;
+
+    fn manifest(presentations: impl IntoIterator<Item = PresentationBuilder>) -> Manifest {
+        live_manifest(10, 6, "0", presentations)
+    }
+
+    fn now(time: &str) -> DateTime<chrono::FixedOffset> {
+        DateTime::parse_from_rfc3339(time).unwrap()
+    }
+
+    #[test]
+    fn live_window_follows_wall_clock() -> anyhow::Result<()> {
+        let manifest = manifest([presentation(
+            "0",
+            0,
+            None,
+            [segment(1, Some(0)), segment(2, None)],
+        )]);
+
+        let window = manifest
+            .live_window(&now("2021-03-31T08:00:03.500Z"))
+            .unwrap();
+
+        let halves = Scale::try_from(2u64)?;
+        assert_eq!(window.live_edge(), UnsignedScaledValue::new(27, halves));
+        assert_eq!(window.start(), UnsignedScaledValue::new(15, halves));
+        let track = window.track(&"0/video/main/720p".parse()?).unwrap();
+        assert_eq!(track.first_segment(), 4.into());
+        assert_eq!(track.start_time(), 6.into());
+        assert_eq!(track.last_segment(), 7.into());
+        assert_eq!(track.end_time(), window.live_edge());
+        Ok(())
+    }
+
+    #[test]
+    fn live_window_is_clamped_to_presentations() -> anyhow::Result<()> {
+        let manifest = manifest([
+            presentation(
+                "0",
+                0,
+                Some(8),
+                [
+                    segment(1, Some(0)),
+                    segment(2, None),
+                    segment(3, None),
+                    segment(4, None),
+                ],
+            ),
+            presentation("1", 8, None, [segment(1, Some(8))]),
+        ]);
+
+        let window = manifest
+            .live_window(&now("2021-03-31T08:00:00.000Z"))
+            .unwrap();
+
+        let old = window.track(&"0/video/main/720p".parse()?).unwrap();
+        assert_eq!(old.first_segment(), 3.into());
+        assert_eq!(old.last_segment(), 4.into());
+        assert_eq!(old.end_time(), 8.into());
+        let active = window.track(&"1/video/main/720p".parse()?).unwrap();
+        assert_eq!(active.first_segment(), 1.into());
+        assert_eq!(active.last_segment(), 1.into());
+        Ok(())
+    }
+
+    #[test]
+    fn vod_has_no_live_window() -> anyhow::Result<()> {
+        let manifest = Manifest::builder(Url::parse("http://localhost")?, 300).build()?;
+
+        assert_eq!(manifest.live_window(&chrono::Utc::now()), None);
+        Ok(())
+    }
+}
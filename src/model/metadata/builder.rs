@@ -0,0 +1,233 @@
+use crate::data::{MetadataSwitchingSetData, MetadataTrackData};
+use crate::util::{UInt, Uri};
+use crate::{Error, Language, Result, ScaledDuration, ScaledValue, Segment, SegmentId};
+
+#[derive(Debug, Clone)]
+pub struct MetadataSwitchingSetBuilder {
+    id: String,
+    mime_type: String,
+    tracks: Vec<MetadataTrackBuilder>,
+    scheme_id: String,
+    align_id: Option<String>,
+    base_url: Option<Uri>,
+    codecs: Option<String>,
+    continuation_pattern: Option<String>,
+    label: Option<String>,
+    language: Option<Language>,
+    media_time_offset: Option<ScaledValue>,
+}
+
+impl MetadataSwitchingSetBuilder {
+    #[must_use]
+    pub fn new(
+        id: impl Into<String>,
+        mime_type: impl Into<String>,
+        scheme_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            mime_type: mime_type.into(),
+            tracks: Vec::new(),
+            scheme_id: scheme_id.into(),
+            align_id: None,
+            base_url: None,
+            codecs: None,
+            continuation_pattern: None,
+            label: None,
+            language: None,
+            media_time_offset: None,
+        }
+    }
+
+    #[must_use]
+    pub fn track(mut self, track: MetadataTrackBuilder) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    #[must_use]
+    pub fn align_id(mut self, align_id: impl Into<String>) -> Self {
+        self.align_id = Some(align_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub fn codecs(mut self, codecs: impl Into<String>) -> Self {
+        self.codecs = Some(codecs.into());
+        self
+    }
+
+    #[must_use]
+    pub fn continuation_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.continuation_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(mut self, media_time_offset: ScaledValue) -> Self {
+        self.media_time_offset = Some(media_time_offset);
+        self
+    }
+}
+
+impl TryFrom<MetadataSwitchingSetBuilder> for MetadataSwitchingSetData {
+    type Error = Error;
+
+    fn try_from(input: MetadataSwitchingSetBuilder) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            mime_type: input.mime_type,
+            tracks: input
+                .tracks
+                .into_iter()
+                .map(MetadataTrackData::try_from)
+                .collect::<Result<_>>()?,
+            scheme_id: input.scheme_id,
+            align_id: input.align_id,
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            label: input.label,
+            language: input.language,
+            media_time_offset: input.media_time_offset,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetadataTrackBuilder {
+    id: String,
+    segments: Vec<Segment>,
+    start_segment_id: SegmentId,
+    average_bandwidth: Option<u64>,
+    bandwidth: Option<u64>,
+    base_url: Option<Uri>,
+    codecs: Option<String>,
+    continuation_pattern: Option<String>,
+    label: Option<String>,
+    media_time_offset: Option<ScaledValue>,
+    segment_duration: Option<ScaledDuration>,
+}
+
+impl MetadataTrackBuilder {
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            segments: Vec::new(),
+            start_segment_id: SegmentId::default(),
+            average_bandwidth: None,
+            bandwidth: None,
+            base_url: None,
+            codecs: None,
+            continuation_pattern: None,
+            label: None,
+            media_time_offset: None,
+            segment_duration: None,
+        }
+    }
+
+    #[must_use]
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    #[must_use]
+    pub fn segments<I: IntoIterator<Item = Segment>>(mut self, segments: I) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    #[must_use]
+    pub const fn start_segment_id(mut self, start_segment_id: SegmentId) -> Self {
+        self.start_segment_id = start_segment_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn average_bandwidth(mut self, average_bandwidth: u64) -> Self {
+        self.average_bandwidth = Some(average_bandwidth);
+        self
+    }
+
+    #[must_use]
+    pub const fn bandwidth(mut self, bandwidth: u64) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub fn codecs(mut self, codecs: impl Into<String>) -> Self {
+        self.codecs = Some(codecs.into());
+        self
+    }
+
+    #[must_use]
+    pub fn continuation_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.continuation_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(mut self, media_time_offset: ScaledValue) -> Self {
+        self.media_time_offset = Some(media_time_offset);
+        self
+    }
+
+    #[must_use]
+    pub const fn segment_duration(mut self, segment_duration: ScaledDuration) -> Self {
+        self.segment_duration = Some(segment_duration);
+        self
+    }
+}
+
+impl TryFrom<MetadataTrackBuilder> for MetadataTrackData {
+    type Error = Error;
+
+    fn try_from(input: MetadataTrackBuilder) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            segments: input.segments.try_into()?,
+            start_segment_id: input.start_segment_id,
+            average_bandwidth: input.average_bandwidth.map(UInt::from),
+            bandwidth: input.bandwidth.map(UInt::from),
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            segment_duration: input.segment_duration,
+        })
+    }
+}
@@ -1,5 +1,7 @@
+pub use builder::*;
 pub use switching_set::MetadataSwitchingSet;
 pub use track::MetadataTrack;
 
+mod builder;
 mod switching_set;
 mod track;
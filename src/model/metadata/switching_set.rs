@@ -5,6 +5,7 @@ use crate::{Address, Language, MediaType, MetadataTrack, Result, SwitchingSet};
 #[derive(Clone, Debug)]
 pub struct MetadataSwitchingSet {
     pub(crate) id: String,
+    presentation_id: String,
     pub(crate) language: Option<Language>,
     pub(crate) tracks: EntityMap<MetadataTrack>,
     pub(crate) scheme_id: String,
@@ -15,6 +16,49 @@ pub struct MetadataSwitchingSet {
 
 impl MetadataSwitchingSet {
     const MEDIA_TYPE: MediaType = MediaType::Metadata;
+
+    pub(crate) fn reparent(&mut self, presentation_id: &str) {
+        presentation_id.clone_into(&mut self.presentation_id);
+        for track in &mut self.tracks {
+            track.reparent(presentation_id, &self.id);
+        }
+    }
+
+    #[must_use]
+    pub const fn language(&self) -> Option<Language> {
+        self.language
+    }
+
+    pub const fn set_language(&mut self, language: Option<Language>) {
+        self.language = language;
+    }
+
+    #[must_use]
+    pub fn scheme_id(&self) -> &str {
+        &self.scheme_id
+    }
+
+    pub fn set_scheme_id(&mut self, scheme_id: String) {
+        self.scheme_id = scheme_id;
+    }
+
+    #[must_use]
+    pub fn align_id(&self) -> Option<&str> {
+        self.align_id.as_deref()
+    }
+
+    pub fn set_align_id(&mut self, align_id: Option<String>) {
+        self.align_id = align_id;
+    }
+
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
 }
 
 impl Entity for MetadataSwitchingSet {
@@ -30,7 +74,7 @@ impl SwitchingSet for MetadataSwitchingSet {
         Self::MEDIA_TYPE
     }
 
-    fn tracks(&self) -> EntityIter<MetadataTrack> {
+    fn tracks(&self) -> EntityIter<'_, MetadataTrack> {
         self.tracks.iter()
     }
 
@@ -42,10 +86,19 @@ impl SwitchingSet for MetadataSwitchingSet {
         self.tracks.get_mut(id)
     }
 
-    fn tracks_mut(&mut self) -> EntityIterMut<MetadataTrack> {
+    fn tracks_mut(&mut self) -> EntityIterMut<'_, MetadataTrack> {
         self.tracks.iter_mut()
     }
 
+    fn insert_track(&mut self, mut track: MetadataTrack) -> Result<()> {
+        track.reparent(&self.presentation_id, &self.id);
+        self.tracks.insert(track)
+    }
+
+    fn remove_track(&mut self, id: &str) -> Option<MetadataTrack> {
+        self.tracks.remove(id)
+    }
+
     fn mime_type(&self) -> &str {
         &self.mime_type
     }
@@ -76,6 +129,7 @@ impl MetadataSwitchingSet {
             .into_entities()?;
         Ok(Self {
             id: data.id,
+            presentation_id: presentation_id.to_owned(),
             language: data.language,
             tracks,
             scheme_id: data.scheme_id,
@@ -22,6 +22,63 @@ pub struct MetadataTrack {
 
 impl MetadataTrack {
     const MEDIA_TYPE: MediaType = MediaType::Metadata;
+
+    pub(crate) fn reparent(&mut self, presentation_id: &str, switching_set_id: &str) {
+        self.uid = TrackUid::new(
+            presentation_id.to_owned(),
+            Self::MEDIA_TYPE,
+            switching_set_id.to_owned(),
+            self.uid.track_id().to_owned(),
+        );
+    }
+
+    #[must_use]
+    pub fn codecs(&self) -> Option<&str> {
+        self.codecs.as_deref()
+    }
+
+    pub fn set_codecs(&mut self, codecs: Option<String>) {
+        self.codecs = codecs;
+    }
+
+    pub const fn set_bandwidth(&mut self, bandwidth: Option<u64>) {
+        self.bandwidth = bandwidth;
+    }
+
+    pub const fn set_average_bandwidth(&mut self, average_bandwidth: Option<u64>) {
+        self.average_bandwidth = average_bandwidth;
+    }
+
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(&self) -> ScaledValue {
+        self.media_time_offset
+    }
+
+    pub const fn set_media_time_offset(&mut self, media_time_offset: ScaledValue) {
+        self.media_time_offset = media_time_offset;
+    }
+
+    pub const fn set_start_segment_id(&mut self, start_segment_id: SegmentId) {
+        self.start_segment_id = start_segment_id;
+    }
+
+    pub fn set_segment_duration(&mut self, segment_duration: Option<ScaledDuration>) -> Result<()> {
+        if segment_duration.is_none() {
+            self.segments
+                .ensure_time_bounds_defined(self.uid.track_id())?;
+        }
+        self.segment_duration = segment_duration;
+        Ok(())
+    }
 }
 
 impl Entity for MetadataTrack {
@@ -39,6 +96,16 @@ impl Track for MetadataTrack {
         &self.segments
     }
 
+    fn push_segment(&mut self, segment: Segment) -> Result<()> {
+        let id = self.uid.track_id();
+        self.segments.push(segment, self.segment_duration, id)
+    }
+
+    fn remove_segments_before(&mut self, segment_id: SegmentId) {
+        self.segments
+            .remove_before(segment_id, self.segment_duration);
+    }
+
     fn start_segment_id(&self) -> SegmentId {
         self.start_segment_id
     }
@@ -50,6 +117,11 @@ impl Track for MetadataTrack {
     fn segment_duration(&self) -> Option<ScaledDuration> {
         self.segment_duration
     }
+
+    fn media_time_offset(&self) -> ScaledValue {
+        self.media_time_offset
+    }
+
     fn average_bandwidth(&self) -> Option<u64> {
         self.average_bandwidth
     }
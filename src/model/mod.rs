@@ -1,5 +1,7 @@
 pub use address::Address;
 pub use audio::*;
+pub use capabilities::PlaybackCapabilities;
+pub use codecs::*;
 pub use duration::ScaledDuration;
 pub use frame_rate::FrameRate;
 pub use language::Language;
@@ -11,6 +13,7 @@ pub use presentation::*;
 pub use resolution::Resolution;
 pub use scale::*;
 pub use segment::*;
+pub use startup::*;
 pub use switching_set::*;
 pub use time_bounds::TimeBounds;
 pub use time_source::TimeSource;
@@ -19,6 +22,8 @@ pub use video::*;
 
 mod address;
 mod audio;
+mod capabilities;
+mod codecs;
 mod duration;
 mod frame_rate;
 mod language;
@@ -30,6 +35,7 @@ mod presentation;
 mod resolution;
 mod scale;
 mod segment;
+mod startup;
 mod switching_set;
 mod time_bounds;
 mod time_source;
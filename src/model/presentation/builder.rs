@@ -0,0 +1,92 @@
+use crate::data::{
+    AudioSwitchingSetData, MetadataSwitchingSetData, PresentationData, VideoSwitchingSetData,
+};
+use crate::util::Uri;
+use crate::{
+    AudioSwitchingSetBuilder, Error, MetadataSwitchingSetBuilder, PresentationEvent, Result,
+    TimeBounds, VideoSwitchingSetBuilder,
+};
+
+#[derive(Debug, Clone)]
+pub struct PresentationBuilder {
+    id: String,
+    time_bounds: TimeBounds,
+    audio: Vec<AudioSwitchingSetBuilder>,
+    base_url: Option<Uri>,
+    events: Vec<PresentationEvent>,
+    metadata: Vec<MetadataSwitchingSetBuilder>,
+    video: Vec<VideoSwitchingSetBuilder>,
+}
+
+impl PresentationBuilder {
+    #[must_use]
+    pub fn new(id: impl Into<String>, time_bounds: TimeBounds) -> Self {
+        Self {
+            id: id.into(),
+            time_bounds,
+            audio: Vec::new(),
+            base_url: None,
+            events: Vec::new(),
+            metadata: Vec::new(),
+            video: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn audio(mut self, switching_set: AudioSwitchingSetBuilder) -> Self {
+        self.audio.push(switching_set);
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub fn event(mut self, event: PresentationEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    #[must_use]
+    pub fn metadata(mut self, switching_set: MetadataSwitchingSetBuilder) -> Self {
+        self.metadata.push(switching_set);
+        self
+    }
+
+    #[must_use]
+    pub fn video(mut self, switching_set: VideoSwitchingSetBuilder) -> Self {
+        self.video.push(switching_set);
+        self
+    }
+}
+
+impl TryFrom<PresentationBuilder> for PresentationData {
+    type Error = Error;
+
+    fn try_from(input: PresentationBuilder) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            time_bounds: input.time_bounds,
+            audio: input
+                .audio
+                .into_iter()
+                .map(AudioSwitchingSetData::try_from)
+                .collect::<Result<_>>()?,
+            base_url: input.base_url,
+            events: input.events,
+            metadata: input
+                .metadata
+                .into_iter()
+                .map(MetadataSwitchingSetData::try_from)
+                .collect::<Result<_>>()?,
+            video: input
+                .video
+                .into_iter()
+                .map(VideoSwitchingSetData::try_from)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
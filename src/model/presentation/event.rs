@@ -1,7 +1,9 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 use crate::util::Entity;
-use crate::Scale;
+use crate::{Result, Scale, TimeBounds, UnsignedScaledValue};
 
 #[derive(Deserialize, Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -13,6 +15,91 @@ pub struct PresentationEvent {
     encoding: PresentationEventEncoding,
 }
 
+impl PresentationEvent {
+    #[must_use]
+    pub const fn new(
+        id: String,
+        data: String,
+        time_bounds: PresentationEventTimeBounds,
+        encoding: PresentationEventEncoding,
+    ) -> Self {
+        Self {
+            data,
+            id,
+            time_bounds,
+            encoding,
+        }
+    }
+
+    /// Creates an event with `payload` encoded in the matching encoding.
+    #[must_use]
+    pub fn with_payload(
+        id: String,
+        payload: &EventPayload,
+        time_bounds: PresentationEventTimeBounds,
+    ) -> Self {
+        let (data, encoding) = match payload {
+            EventPayload::Text(text) => (text.clone(), PresentationEventEncoding::Identity),
+            EventPayload::Bytes(bytes) => (BASE64.encode(bytes), PresentationEventEncoding::Base64),
+            EventPayload::Json(value) => (value.to_string(), PresentationEventEncoding::Json),
+        };
+        Self::new(id, data, time_bounds, encoding)
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The payload of the event, encoded as described by [`PresentationEvent::encoding`].
+    #[must_use]
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Decodes the payload of the event.
+    pub fn payload(&self) -> Result<EventPayload> {
+        Ok(match self.encoding {
+            PresentationEventEncoding::Identity => EventPayload::Text(self.data.clone()),
+            PresentationEventEncoding::Base64 => EventPayload::Bytes(BASE64.decode(&self.data)?),
+            PresentationEventEncoding::Json => {
+                EventPayload::Json(serde_json::from_str(&self.data)?)
+            }
+        })
+    }
+
+    /// The start of the event on the timeline of a presentation with `presentation` bounds.
+    #[must_use]
+    pub fn start_time(&self, presentation: TimeBounds) -> Option<UnsignedScaledValue> {
+        presentation
+            .start_time()?
+            .checked_add(UnsignedScaledValue::new(
+                self.time_bounds.start_time_offset,
+                self.time_bounds.scale,
+            ))
+    }
+
+    /// The end of the event on the timeline of a presentation with `presentation` bounds.
+    #[must_use]
+    pub fn end_time(&self, presentation: TimeBounds) -> Option<UnsignedScaledValue> {
+        self.start_time(presentation)?
+            .checked_add(UnsignedScaledValue::new(
+                self.time_bounds.duration,
+                self.time_bounds.scale,
+            ))
+    }
+
+    #[must_use]
+    pub const fn time_bounds(&self) -> PresentationEventTimeBounds {
+        self.time_bounds
+    }
+
+    #[must_use]
+    pub const fn encoding(&self) -> PresentationEventEncoding {
+        self.encoding
+    }
+}
+
 impl Entity for PresentationEvent {
     fn id(&self) -> &str {
         &self.id
@@ -30,16 +117,94 @@ pub struct PresentationEventTimeBounds {
     scale: Scale,
 }
 
-#[derive(Deserialize, Debug, Serialize, Copy, Clone, Eq, PartialEq, Hash)]
+impl PresentationEventTimeBounds {
+    #[must_use]
+    pub const fn new(start_time_offset: u64, duration: u64, scale: Scale) -> Self {
+        Self {
+            start_time_offset,
+            duration,
+            scale,
+        }
+    }
+
+    /// The start of the event, relative to the start of its presentation.
+    #[must_use]
+    pub const fn start_time_offset(&self) -> u64 {
+        self.start_time_offset
+    }
+
+    #[must_use]
+    pub const fn duration(&self) -> u64 {
+        self.duration
+    }
+
+    #[must_use]
+    pub const fn scale(&self) -> Scale {
+        self.scale
+    }
+}
+
+/// The decoded payload of a [`PresentationEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventPayload {
+    Text(String),
+    Bytes(Vec<u8>),
+    Json(serde_json::Value),
+}
+
+#[derive(Deserialize, Debug, Serialize, Copy, Clone, Eq, PartialEq, Hash, Default)]
 #[serde(rename_all = "lowercase")]
-enum PresentationEventEncoding {
+pub enum PresentationEventEncoding {
+    #[default]
     Identity,
     Base64,
     Json,
 }
 
-impl Default for PresentationEventEncoding {
-    fn default() -> Self {
-        Self::Identity
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn decode_payloads() -> anyhow::Result<()> {
+        let events: Vec<PresentationEvent> = serde_json::from_str(
+            r#"[
+                {"id": "text", "data": "hello", "timeBounds": {}},
+                {"id": "bytes", "data": "AAEC", "encoding": "base64", "timeBounds": {}},
+                {"id": "json", "data": "{\"ad\": 1}", "encoding": "json", "timeBounds": {}},
+                {"id": "invalid", "data": "!", "encoding": "base64", "timeBounds": {}}
+            ]"#,
+        )?;
+
+        assert_eq!(events[0].payload()?, EventPayload::Text("hello".to_owned()));
+        assert_eq!(events[1].payload()?, EventPayload::Bytes(vec![0, 1, 2]));
+        assert_eq!(events[2].payload()?, EventPayload::Json(json!({"ad": 1})));
+        assert!(events[3].payload().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn encode_payload() -> anyhow::Result<()> {
+        let time_bounds = PresentationEventTimeBounds::new(5, 10, Scale::try_from(10u64)?);
+        let payload = EventPayload::Bytes(b"ad".to_vec());
+
+        let event = PresentationEvent::with_payload("ad".to_owned(), &payload, time_bounds);
+
+        assert_eq!(event.id(), "ad");
+        assert_eq!(event.data(), "YWQ=");
+        assert_eq!(event.encoding(), PresentationEventEncoding::Base64);
+        assert_eq!(event.payload()?, payload);
+        let presentation = TimeBounds::new(Some(20), None, Scale::ONE)?;
+        assert_eq!(
+            event.start_time(presentation),
+            Some(UnsignedScaledValue::new(41, Scale::try_from(2u64)?))
+        );
+        assert_eq!(
+            event.end_time(presentation),
+            Some(UnsignedScaledValue::new(43, Scale::try_from(2u64)?))
+        );
+        Ok(())
     }
 }
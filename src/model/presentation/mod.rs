@@ -1,3 +1,4 @@
+pub use builder::PresentationBuilder;
 pub use event::*;
 
 use crate::data::PresentationData;
@@ -8,6 +9,7 @@ use crate::{
     VideoSwitchingSet, VideoTrack,
 };
 
+mod builder;
 mod event;
 
 #[derive(Clone, Debug)]
@@ -49,27 +51,45 @@ impl Presentation {
         })
     }
 
+    pub(crate) fn rename(&mut self, id: String) {
+        self.id = id;
+        for set in &mut self.audio {
+            set.reparent(&self.id);
+        }
+        for set in &mut self.metadata {
+            set.reparent(&self.id);
+        }
+        for set in &mut self.video {
+            set.reparent(&self.id);
+        }
+    }
+
     #[must_use]
-    pub fn audio(&self) -> EntityIter<AudioSwitchingSet> {
+    pub fn audio(&self) -> EntityIter<'_, AudioSwitchingSet> {
         self.audio.iter()
     }
     #[must_use]
-    pub fn audio_mut(&mut self) -> EntityIterMut<AudioSwitchingSet> {
+    pub fn audio_mut(&mut self) -> EntityIterMut<'_, AudioSwitchingSet> {
         self.audio.iter_mut()
     }
 
     #[must_use]
-    pub fn metadata(&self) -> EntityIter<MetadataSwitchingSet> {
+    pub fn metadata(&self) -> EntityIter<'_, MetadataSwitchingSet> {
         self.metadata.iter()
     }
 
     #[must_use]
-    pub fn video(&self) -> EntityIter<VideoSwitchingSet> {
+    pub fn metadata_mut(&mut self) -> EntityIterMut<'_, MetadataSwitchingSet> {
+        self.metadata.iter_mut()
+    }
+
+    #[must_use]
+    pub fn video(&self) -> EntityIter<'_, VideoSwitchingSet> {
         self.video.iter()
     }
 
     #[must_use]
-    pub fn video_mut(&mut self) -> EntityIterMut<VideoSwitchingSet> {
+    pub fn video_mut(&mut self) -> EntityIterMut<'_, VideoSwitchingSet> {
         self.video.iter_mut()
     }
 
@@ -99,11 +119,74 @@ impl Presentation {
         self.audio.get_mut(switching_set_id)
     }
 
+    #[must_use]
+    pub fn metadata_switching_set(&self, switching_set_id: &str) -> Option<&MetadataSwitchingSet> {
+        self.metadata.get(switching_set_id)
+    }
+
+    #[must_use]
+    pub fn metadata_switching_set_mut(
+        &mut self,
+        switching_set_id: &str,
+    ) -> Option<&mut MetadataSwitchingSet> {
+        self.metadata.get_mut(switching_set_id)
+    }
+
+    /// Appends `switching_set` and moves its tracks to this presentation.
+    pub fn insert_video_switching_set(
+        &mut self,
+        mut switching_set: VideoSwitchingSet,
+    ) -> Result<()> {
+        switching_set.reparent(&self.id);
+        self.video.insert(switching_set)
+    }
+
+    pub fn insert_audio_switching_set(
+        &mut self,
+        mut switching_set: AudioSwitchingSet,
+    ) -> Result<()> {
+        switching_set.reparent(&self.id);
+        self.audio.insert(switching_set)
+    }
+
+    pub fn insert_metadata_switching_set(
+        &mut self,
+        mut switching_set: MetadataSwitchingSet,
+    ) -> Result<()> {
+        switching_set.reparent(&self.id);
+        self.metadata.insert(switching_set)
+    }
+
+    pub fn remove_video_switching_set(
+        &mut self,
+        switching_set_id: &str,
+    ) -> Option<VideoSwitchingSet> {
+        self.video.remove(switching_set_id)
+    }
+
+    pub fn remove_audio_switching_set(
+        &mut self,
+        switching_set_id: &str,
+    ) -> Option<AudioSwitchingSet> {
+        self.audio.remove(switching_set_id)
+    }
+
+    pub fn remove_metadata_switching_set(
+        &mut self,
+        switching_set_id: &str,
+    ) -> Option<MetadataSwitchingSet> {
+        self.metadata.remove(switching_set_id)
+    }
+
     #[must_use]
     pub const fn time_bounds(&self) -> TimeBounds {
         self.time_bounds
     }
 
+    pub const fn set_time_bounds(&mut self, time_bounds: TimeBounds) {
+        self.time_bounds = time_bounds;
+    }
+
     pub(super) fn validate_active(&self) -> Result<()> {
         if self.time_bounds.start_time().is_none() {
             return Err(Error::MissingStartTime(self.id.clone()));
@@ -184,7 +267,7 @@ impl Presentation {
 
     fn initializable_track_mut_iterator<'a, T, U>(
         map: &'a mut EntityMap<T>,
-    ) -> impl Iterator<Item = &mut dyn InitializableTrack>
+    ) -> impl Iterator<Item = &'a mut dyn InitializableTrack>
     where
         T: SwitchingSet<Track = U>,
         U: InitializableTrack + 'a,
@@ -292,6 +375,19 @@ impl Presentation {
     pub fn events(&self) -> EntityMap<PresentationEvent> {
         self.events.clone()
     }
+
+    pub fn insert_event(&mut self, event: PresentationEvent) -> Result<()> {
+        self.events.insert(event)
+    }
+
+    pub fn remove_event(&mut self, id: &str) -> Option<PresentationEvent> {
+        self.events.remove(id)
+    }
+
+    #[must_use]
+    pub fn event(&self, id: &str) -> Option<&PresentationEvent> {
+        self.events.get(id)
+    }
 }
 
 impl Entity for Presentation {
@@ -11,3 +11,42 @@ pub struct Resolution {
     #[serde(default, skip_serializing_if = "Scale::is_one")]
     sar_height: Scale,
 }
+
+impl Resolution {
+    #[must_use]
+    pub const fn new(width: u64, height: u64) -> Self {
+        Self {
+            width,
+            height,
+            sar_width: Scale::ONE,
+            sar_height: Scale::ONE,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_sample_aspect_ratio(mut self, sar_width: Scale, sar_height: Scale) -> Self {
+        self.sar_width = sar_width;
+        self.sar_height = sar_height;
+        self
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> u64 {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> u64 {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn sar_width(&self) -> Scale {
+        self.sar_width
+    }
+
+    #[must_use]
+    pub const fn sar_height(&self) -> Scale {
+        self.sar_height
+    }
+}
@@ -54,6 +54,7 @@ impl From<Scale> for u128 {
 
 impl Scale {
     pub const ONE: Self = Self(1);
+    pub const MILLIS: Self = Self(1000);
     #[must_use]
     pub fn is_one(&self) -> bool {
         *self == Self::ONE
@@ -1,9 +1,10 @@
 use std::fmt;
 
+use gcd::Gcd;
 use serde::{Deserialize, Serialize};
 
 use crate::util::{try_convert_i64_to_float, try_convert_u64_to_float, Int};
-use crate::{Error, Result, Scale};
+use crate::{Error, Result, Scale, UnsignedScaledValue};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Eq)]
 pub struct ScaledValue {
@@ -18,6 +19,29 @@ impl ScaledValue {
     pub const fn new(value: i64, scale: Scale) -> Self {
         Self { value, scale }
     }
+
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let scale_a = u64::from(self.scale);
+        let scale_b = u64::from(other.scale);
+        let scale = i128::from(scale_a / scale_a.gcd(scale_b)).checked_mul(scale_b.into())?;
+        let left = i128::from(self.value).checked_mul(scale / i128::from(scale_a))?;
+        let right = i128::from(other.value).checked_mul(scale / i128::from(scale_b))?;
+        let value = left.checked_add(right)?;
+        let gcd = i128::try_from(value.unsigned_abs().gcd(scale.unsigned_abs())).ok()?;
+        Some(Self::new(
+            i64::try_from(value / gcd).ok()?,
+            u64::try_from(scale / gcd).ok()?.try_into().ok()?,
+        ))
+    }
+}
+
+impl TryFrom<UnsignedScaledValue> for ScaledValue {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(input: UnsignedScaledValue) -> std::result::Result<Self, Self::Error> {
+        Ok(Self::new(input.value.try_into()?, input.scale))
+    }
 }
 
 impl PartialEq for ScaledValue {
@@ -53,3 +77,24 @@ impl fmt::Display for ScaledValue {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add() {
+        let a = ScaledValue::new(-5, Scale::try_from(4u64).unwrap());
+        let b = ScaledValue::new(2, Scale::try_from(6u64).unwrap());
+        let c = ScaledValue::new(-11, Scale::try_from(12u64).unwrap());
+        assert_eq!(a.checked_add(b), Some(c));
+    }
+
+    #[test]
+    fn add_with_large_scales() {
+        let a = ScaledValue::new(1, Scale::try_from(u64::MAX).unwrap());
+        let b = ScaledValue::new(1, Scale::try_from(u64::MAX - 1).unwrap());
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.checked_add(a), Some(ScaledValue::new(2, a.scale)));
+    }
+}
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
 use crate::util::UInt;
-use crate::{Error, Result, ScaledDuration, TimeBounds};
+use crate::{Error, Result, ScaledDuration, TimeBounds, UnsignedScaledValue};
 
 #[derive(
     Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default,
@@ -75,6 +75,11 @@ pub struct Segment {
 }
 
 impl Segment {
+    #[must_use]
+    pub const fn new(id: SegmentId, time_bounds: Option<TimeBounds>) -> Self {
+        Self { id, time_bounds }
+    }
+
     #[must_use]
     pub const fn id(&self) -> SegmentId {
         self.id
@@ -123,6 +128,141 @@ impl Segments {
         }
         Ok(())
     }
+
+    /// Appends the segments of a newer revision of the same track.
+    ///
+    /// Segments that are also listed in `newer` are replaced by their newer version.
+    /// When both lists cannot be joined without a gap in the segment ids, only `newer` is kept.
+    pub(crate) fn merge(&mut self, newer: Self) {
+        let Some(first) = newer.first().map(Segment::id) else {
+            return;
+        };
+        self.0.retain(|segment| segment.id < first);
+        match self.0.last() {
+            Some(last) if last.id.next() == first => self.0.extend(newer.0),
+            _ => *self = newer,
+        }
+    }
+
+    /// Removes all segments that end at or before `time`.
+    ///
+    /// When the removed segments determined the start time of the remaining ones, the first
+    /// remaining segment gets explicit time bounds so the timeline stays intact.
+    pub(crate) fn prune_before(
+        &mut self,
+        time: UnsignedScaledValue,
+        segment_duration: Option<ScaledDuration>,
+    ) {
+        let mut start = None;
+        let mut count = 0;
+        for (_, _, end) in segment_times(self, segment_duration) {
+            if end > time {
+                break;
+            }
+            start = Some(end);
+            count += 1;
+        }
+        self.drain_front(count, start);
+    }
+
+    /// Removes all segments before `segment_id`, keeping the timeline of the remaining ones.
+    pub(crate) fn remove_before(
+        &mut self,
+        segment_id: SegmentId,
+        segment_duration: Option<ScaledDuration>,
+    ) {
+        let start = segment_times(self, segment_duration)
+            .find(|&(id, _, _)| id == segment_id)
+            .map(|(_, start, _)| start);
+        let count = self.0.iter().take_while(|s| s.id < segment_id).count();
+        self.drain_front(count, start);
+    }
+
+    /// Removes all segments that start at or after `time`.
+    pub(crate) fn truncate_at(
+        &mut self,
+        time: UnsignedScaledValue,
+        segment_duration: Option<ScaledDuration>,
+    ) {
+        let count = segment_times(self, segment_duration).position(|(_, start, _)| start >= time);
+        if let Some(count) = count {
+            self.0.truncate(count);
+        }
+    }
+
+    /// Appends `segment`, which must directly follow the last segment.
+    ///
+    /// Without a `segment_duration` the segment must continue the timeline of the track.
+    pub(crate) fn push(
+        &mut self,
+        segment: Segment,
+        segment_duration: Option<ScaledDuration>,
+        track_id: &str,
+    ) -> Result<()> {
+        if let Some(last) = self.0.last() {
+            if last.id.next() != segment.id {
+                return Err(Error::InvalidSegmentIds(last.id, segment.id));
+            }
+        }
+        self.0.push(segment);
+        if segment_duration.is_none() {
+            if let Err(error) = self.ensure_time_bounds_defined(track_id) {
+                self.0.pop();
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the segments with time bounds from `from` to `to` on the presentation timeline.
+    pub(crate) fn shift(
+        &mut self,
+        from: UnsignedScaledValue,
+        to: UnsignedScaledValue,
+    ) -> Option<()> {
+        let shift = |time: Option<UnsignedScaledValue>| match time {
+            Some(time) => time.checked_add(to)?.checked_sub(from).map(Some),
+            None => Some(None),
+        };
+        for segment in &mut self.0 {
+            if let Some(bounds) = segment.time_bounds {
+                let start = shift(bounds.start_time())?;
+                let end = shift(bounds.end_time())?;
+                segment.time_bounds = Some(TimeBounds::from_values(start, end)?);
+            }
+        }
+        Some(())
+    }
+
+    fn drain_front(&mut self, count: usize, start: Option<UnsignedScaledValue>) {
+        if count == 0 {
+            return;
+        }
+        self.0.drain(..count);
+        if let (Some(first), Some(start)) = (self.0.first_mut(), start) {
+            if first.time_bounds.is_none() {
+                first.time_bounds = TimeBounds::new(Some(start.value), None, start.scale).ok();
+            }
+        }
+    }
+}
+
+/// Yields the id, start- and end-time of each segment for as long as they can be determined,
+/// either from explicit time bounds or by adding `segment_duration` to the previous end.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn segment_times(
+    segments: &[Segment],
+    segment_duration: Option<ScaledDuration>,
+) -> impl Iterator<Item = (SegmentId, UnsignedScaledValue, UnsignedScaledValue)> + '_ {
+    segments.iter().scan(None, move |previous_end, segment| {
+        let bounds = segment.time_bounds;
+        let start = bounds.and_then(|b| b.start_time()).or(*previous_end)?;
+        let end = bounds
+            .and_then(|b| b.end_time())
+            .or_else(|| start.checked_add(segment_duration?.into()))?;
+        *previous_end = Some(end);
+        Some((segment.id, start, end))
+    })
 }
 
 impl Deref for Segments {
@@ -140,8 +280,78 @@ impl Borrow<[Segment]> for Segments {
 
 #[cfg(test)]
 mod tests {
+    use crate::Scale;
+
     use super::*;
 
+    fn segments(json: &str) -> Segments {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn ids(segments: &Segments) -> Vec<u64> {
+        segments.iter().map(|s| s.id().into()).collect()
+    }
+
+    #[test]
+    fn merge_appends_newer_segments() {
+        let mut old = segments(r#"[{"id": 1}, {"id": 2}, {"id": 3}]"#);
+
+        old.merge(segments(r#"[{"id": 3}, {"id": 4}]"#));
+
+        assert_eq!(ids(&old), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn merge_replaces_segments_after_gap() {
+        let mut old = segments(r#"[{"id": 1}, {"id": 2}]"#);
+
+        old.merge(segments(r#"[{"id": 5}, {"id": 6}]"#));
+
+        assert_eq!(ids(&old), [5, 6]);
+    }
+
+    #[test]
+    fn prune_extrapolates_start_time() {
+        let mut segments =
+            segments(r#"[{"id": 1, "timeBounds": {"startTime": 10}}, {"id": 2}, {"id": 3}]"#);
+
+        segments.prune_before(14.into(), Some(ScaledDuration::new(2, Scale::ONE)));
+
+        assert_eq!(ids(&segments), [3]);
+        let start = segments[0].time_bounds().and_then(|b| b.start_time());
+        assert_eq!(start, Some(14.into()));
+    }
+
+    #[test]
+    fn push_checks_sequential_ids() {
+        let mut segments = segments(r#"[{"id": 1}, {"id": 2}]"#);
+        let duration = Some(ScaledDuration::new(2, Scale::ONE));
+
+        let result = segments.push(Segment::new(4.into(), None), duration, "video");
+        assert!(
+            matches!(result, Err(Error::InvalidSegmentIds(a, b)) if a == 2.into() && b == 4.into())
+        );
+        let result = segments.push(Segment::new(3.into(), None), None, "video");
+        assert!(matches!(result, Err(Error::MissingSegmentDuration(_))));
+        segments
+            .push(Segment::new(3.into(), None), duration, "video")
+            .unwrap();
+
+        assert_eq!(ids(&segments), [1, 2, 3]);
+    }
+
+    #[test]
+    fn remove_before_keeps_timeline() {
+        let mut segments =
+            segments(r#"[{"id": 1, "timeBounds": {"startTime": 10}}, {"id": 2}, {"id": 3}]"#);
+
+        segments.remove_before(3.into(), Some(ScaledDuration::new(2, Scale::ONE)));
+
+        assert_eq!(ids(&segments), [3]);
+        let start = segments[0].time_bounds().and_then(|b| b.start_time());
+        assert_eq!(start, Some(14.into()));
+    }
+
     #[test]
     fn deserialize_checks_sequential_ids() {
         let data = r#"
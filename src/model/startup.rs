@@ -0,0 +1,274 @@
+use url::Url;
+
+use crate::{
+    Error, InitId, InitializableTrack, Manifest, Result, ScaledValue, SegmentId, StreamType, Track,
+    TrackUid, UnsignedScaledValue,
+};
+
+/// Where playback should start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartPosition {
+    /// The current time of a live stream.
+    LiveEdge,
+    /// A time on the presentation timeline.
+    At(UnsignedScaledValue),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupRequestKind {
+    Initialization(InitId),
+    Continuation(SegmentId),
+}
+
+/// A single request a player makes to start playback of a track.
+#[derive(Debug, Clone)]
+pub struct StartupRequest {
+    track_uid: TrackUid,
+    kind: StartupRequestKind,
+    url: Url,
+    media_time: Option<ScaledValue>,
+}
+
+impl StartupRequest {
+    #[must_use]
+    pub const fn track_uid(&self) -> &TrackUid {
+        &self.track_uid
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> StartupRequestKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The media time at which the response is expected to start.
+    ///
+    /// For [`InitId::Now`] this is the current time of the stream, the server picks the
+    /// actual frame. `None` when the media time cannot be represented.
+    #[must_use]
+    pub const fn media_time(&self) -> Option<ScaledValue> {
+        self.media_time
+    }
+}
+
+/// The requests to start HESP playback of a set of tracks: first an initialization segment
+/// for each track, then the continuation segment each track continues with.
+#[derive(Debug, Clone)]
+pub struct StartupPlan {
+    position: UnsignedScaledValue,
+    requests: Vec<StartupRequest>,
+}
+
+impl StartupPlan {
+    /// Plans the startup of `track_uids` at `position`.
+    ///
+    /// At the live edge the initialization segments are requested with [`InitId::Now`].
+    /// Otherwise the initialization segment starts at the frame containing `position`, counting
+    /// frames from the `start_sequence_number` of the first frame of the start segment.
+    /// Metadata tracks have no initialization segment and only get a continuation request.
+    pub fn new(
+        manifest: &Manifest,
+        track_uids: &[TrackUid],
+        position: StartPosition,
+    ) -> Result<Self> {
+        let (position, live_edge) = match (position, manifest.stream_type()) {
+            (StartPosition::LiveEdge, StreamType::Live(live)) => (live.current_time, true),
+            (StartPosition::LiveEdge, StreamType::Vod) => return Err(Error::NoLiveEdge),
+            (StartPosition::At(time), _) => (time, false),
+        };
+        let mut initializations = Vec::new();
+        let mut continuations = Vec::new();
+        for uid in track_uids {
+            let track = manifest
+                .track(uid)
+                .ok_or_else(|| Error::UnknownTrack(uid.clone()))?;
+            let presentation = manifest.presentation(uid.presentation_id()).unwrap();
+            let bounds = presentation.time_bounds();
+            if bounds.start_time().is_some_and(|start| position < start)
+                || bounds.end_time().is_some_and(|end| position >= end)
+            {
+                return Err(Error::NoPresentationAt(position));
+            }
+            if let Some(track) = manifest.initializable_track(uid) {
+                let (init_id, time) = if live_edge {
+                    (InitId::Now, Some(position))
+                } else {
+                    frame_at(track, position)
+                        .map(|(frame, time)| (InitId::Numbered(frame), Some(time)))
+                        .ok_or_else(|| Error::NoSegmentAt(uid.clone(), position))?
+                };
+                initializations.push(StartupRequest {
+                    track_uid: uid.clone(),
+                    kind: StartupRequestKind::Initialization(init_id),
+                    url: track.initialization_pattern().init_id(init_id),
+                    media_time: time.and_then(|time| media_time(track, time)),
+                });
+            }
+            let segment_id = track
+                .segment_at(position)
+                .ok_or_else(|| Error::NoSegmentAt(uid.clone(), position))?;
+            let start = track
+                .segment_time_bounds(segment_id)
+                .and_then(|bounds| bounds.start_time());
+            continuations.push(StartupRequest {
+                track_uid: uid.clone(),
+                kind: StartupRequestKind::Continuation(segment_id),
+                url: track.continuation_pattern().segment(segment_id),
+                media_time: start.and_then(|start| media_time(track, start)),
+            });
+        }
+        initializations.append(&mut continuations);
+        Ok(Self {
+            position,
+            requests: initializations,
+        })
+    }
+
+    /// The start position on the presentation timeline.
+    #[must_use]
+    pub const fn position(&self) -> UnsignedScaledValue {
+        self.position
+    }
+
+    #[must_use]
+    pub fn requests(&self) -> &[StartupRequest] {
+        &self.requests
+    }
+}
+
+impl IntoIterator for StartupPlan {
+    type Item = StartupRequest;
+    type IntoIter = std::vec::IntoIter<StartupRequest>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.requests.into_iter()
+    }
+}
+
+/// Returns the sequence number and presentation time of the frame that contains `time`.
+fn frame_at(
+    track: &dyn InitializableTrack,
+    time: UnsignedScaledValue,
+) -> Option<(u64, UnsignedScaledValue)> {
+    let start = track
+        .segment_time_bounds(track.start_segment_id())?
+        .start_time()?;
+    let frame_rate = UnsignedScaledValue::from(track.frame_rate());
+    let frames = time.checked_sub(start)?.checked_mul(frame_rate)?.floor();
+    let frame_time =
+        start.checked_add(UnsignedScaledValue::from(frames).checked_div(frame_rate)?)?;
+    Some((
+        track.start_sequence_number().checked_add(frames)?,
+        frame_time,
+    ))
+}
+
+fn media_time(track: &dyn Track, time: UnsignedScaledValue) -> Option<ScaledValue> {
+    ScaledValue::try_from(time)
+        .ok()?
+        .checked_add(track.media_time_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scale;
+
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        {
+            "availabilityDuration": {"value": 60},
+            "creationDate": "2021-03-31T08:00:00.000Z",
+            "fallbackPollRate": 300,
+            "manifestVersion": "2.0.0",
+            "streamType": "live",
+            "activePresentation": "main",
+            "currentTime": {"value": 5},
+            "presentations": [{
+                "id": "main",
+                "timeBounds": {"startTime": 0},
+                "video": [{
+                    "id": "video",
+                    "codecs": "avc1.4d001f",
+                    "frameRate": {"value": 25},
+                    "mediaTimeOffset": {"value": 5},
+                    "continuationPattern": "content-{segmentId}.mp4",
+                    "initializationPattern": "init-{initId}.mp4",
+                    "tracks": [{
+                        "id": "720p",
+                        "bandwidth": 3000000,
+                        "resolution": {"width": 1280, "height": 720},
+                        "segmentDuration": {"value": 2},
+                        "startSegmentId": 1,
+                        "startSequenceNumber": 100,
+                        "segments": [{"id": 1, "timeBounds": {"startTime": 0}}, {"id": 2}]
+                    }]
+                }]
+            }]
+        }"#;
+
+    fn plan(position: StartPosition) -> anyhow::Result<Vec<(String, Option<ScaledValue>)>> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let manifest = Manifest::from_json(location, MANIFEST)?;
+        let uid: TrackUid = "main/video/video/720p".parse()?;
+        let plan = StartupPlan::new(&manifest, &[uid], position)?;
+        Ok(plan
+            .into_iter()
+            .map(|request| (request.url().to_string(), request.media_time()))
+            .collect())
+    }
+
+    #[test]
+    fn start_at_live_edge() -> anyhow::Result<()> {
+        assert_eq!(
+            plan(StartPosition::LiveEdge)?,
+            [
+                (
+                    "https://example.com/init-now.mp4".to_owned(),
+                    Some(ScaledValue::from(10))
+                ),
+                (
+                    "https://example.com/content-3.mp4".to_owned(),
+                    Some(ScaledValue::from(9))
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn start_at_position() -> anyhow::Result<()> {
+        let position = UnsignedScaledValue::new(7, Scale::try_from(2u64)?);
+
+        assert_eq!(
+            plan(StartPosition::At(position))?,
+            [
+                (
+                    "https://example.com/init-187.mp4".to_owned(),
+                    Some(ScaledValue::new(212, Scale::try_from(25u64)?))
+                ),
+                (
+                    "https://example.com/content-2.mp4".to_owned(),
+                    Some(ScaledValue::from(7))
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_unknown_track() -> anyhow::Result<()> {
+        let location = Url::parse("https://example.com/manifest.json")?;
+        let manifest = Manifest::from_json(location, MANIFEST)?;
+        let uid: TrackUid = "main/video/video/1080p".parse()?;
+
+        let result = StartupPlan::new(&manifest, &[uid], StartPosition::LiveEdge);
+
+        assert!(matches!(result, Err(Error::UnknownTrack(_))));
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 pub use protection::*;
 
 use crate::util::{Entity, EntityIter, EntityIterMut};
-use crate::{MediaType, Track};
+use crate::{MediaType, Result, Track, TrackUid};
 
 mod protection;
 
@@ -9,9 +9,37 @@ pub trait SwitchingSet: Entity {
     type Track: Track;
 
     fn media_type(&self) -> MediaType;
-    fn tracks(&self) -> EntityIter<Self::Track>;
+    fn tracks(&self) -> EntityIter<'_, Self::Track>;
     fn track(&self, id: &str) -> Option<&Self::Track>;
     fn track_mut(&mut self, id: &str) -> Option<&mut Self::Track>;
-    fn tracks_mut(&mut self) -> EntityIterMut<Self::Track>;
+    fn tracks_mut(&mut self) -> EntityIterMut<'_, Self::Track>;
+    /// Appends `track`, failing when the switching set already has a track with its id.
+    fn insert_track(&mut self, track: Self::Track) -> Result<()>;
+    fn remove_track(&mut self, id: &str) -> Option<Self::Track>;
     fn mime_type(&self) -> &str;
+
+    /// Returns the tracks ordered by ascending bandwidth, tracks with equal bandwidth by id.
+    fn tracks_by_bandwidth(&self) -> Vec<&Self::Track> {
+        let mut tracks: Vec<_> = self.tracks().collect();
+        tracks.sort_by(|a, b| {
+            a.bandwidth()
+                .cmp(&b.bandwidth())
+                .then_with(|| a.id().cmp(b.id()))
+        });
+        tracks
+    }
+
+    /// Returns the track one step up the bandwidth ladder from the track with `uid`.
+    fn next_higher(&self, uid: &TrackUid) -> Option<&Self::Track> {
+        let ladder = self.tracks_by_bandwidth();
+        let index = ladder.iter().position(|track| track.uid() == uid)?;
+        ladder.get(index + 1).copied()
+    }
+
+    /// Returns the track one step down the bandwidth ladder from the track with `uid`.
+    fn next_lower(&self, uid: &TrackUid) -> Option<&Self::Track> {
+        let ladder = self.tracks_by_bandwidth();
+        let index = ladder.iter().position(|track| track.uid() == uid)?;
+        ladder.get(index.checked_sub(1)?).copied()
+    }
 }
@@ -54,8 +54,8 @@ impl From<Fairplay> for ProtectionSystemData {
         Self {
             scheme_id: Fairplay::SCHEME_ID,
             attributes: HashMap::from([
-                ("uri".to_string(), input.uri.to_string()),
-                ("keyformat".to_string(), input.keyformat.to_string()),
+                ("uri".to_string(), input.uri.clone()),
+                ("keyformat".to_string(), input.keyformat.clone()),
                 (
                     "keyformatversions".to_string(),
                     input.keyformatversions.to_string(),
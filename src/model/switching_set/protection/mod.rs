@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::Result;
+
 pub use fairplay::*;
 pub use scheme::ProtectionScheme;
 pub use system::*;
@@ -14,3 +16,35 @@ pub struct SwitchingSetProtection {
     scheme: ProtectionScheme,
     systems: SwitchingSetProtectionSystemVec,
 }
+
+impl SwitchingSetProtection {
+    pub fn new(
+        scheme: ProtectionScheme,
+        systems: Vec<SwitchingSetProtectionSystem>,
+    ) -> Result<Self> {
+        Ok(Self {
+            scheme,
+            systems: systems.try_into()?,
+        })
+    }
+
+    #[must_use]
+    pub const fn scheme(&self) -> ProtectionScheme {
+        self.scheme
+    }
+
+    pub const fn set_scheme(&mut self, scheme: ProtectionScheme) {
+        self.scheme = scheme;
+    }
+
+    #[must_use]
+    pub fn systems(&self) -> &[SwitchingSetProtectionSystem] {
+        &self.systems.0
+    }
+
+    /// Replaces the protection systems, of which there must be at least one.
+    pub fn set_systems(&mut self, systems: Vec<SwitchingSetProtectionSystem>) -> Result<()> {
+        self.systems = systems.try_into()?;
+        Ok(())
+    }
+}
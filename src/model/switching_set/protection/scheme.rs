@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -6,3 +8,12 @@ pub enum ProtectionScheme {
     Cenc,
     Cbcs,
 }
+
+impl fmt::Display for ProtectionScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cenc => "cenc",
+            Self::Cbcs => "cbcs",
+        })
+    }
+}
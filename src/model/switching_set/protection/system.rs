@@ -37,6 +37,11 @@ pub(super) struct ProtectionSystemData {
 }
 
 impl SwitchingSetProtectionSystem {
+    #[must_use]
+    pub const fn new(pssh: Option<String>, attributes: ProtectionSystemAttributes) -> Self {
+        Self { pssh, attributes }
+    }
+
     #[must_use]
     pub fn pssh(&self) -> Option<&str> {
         self.pssh.as_deref()
@@ -88,7 +93,7 @@ impl From<ProtectionSystemAttributes> for ProtectionSystemData {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(try_from = "Vec<SwitchingSetProtectionSystem>")]
-pub struct SwitchingSetProtectionSystemVec(Vec<SwitchingSetProtectionSystem>);
+pub struct SwitchingSetProtectionSystemVec(pub(super) Vec<SwitchingSetProtectionSystem>);
 
 impl TryFrom<Vec<SwitchingSetProtectionSystem>> for SwitchingSetProtectionSystemVec {
     type Error = Error;
@@ -1,3 +1,4 @@
+use gcd::Gcd;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -35,6 +36,29 @@ impl TimeBounds {
         }
     }
 
+    /// Creates closed time bounds, expressing both values in their least common scale.
+    pub(crate) fn from_range(start: UnsignedScaledValue, end: UnsignedScaledValue) -> Option<Self> {
+        let start_scale = u64::from(start.scale);
+        let end_scale = u64::from(end.scale);
+        let scale = (start_scale / start_scale.gcd(end_scale)).checked_mul(end_scale)?;
+        let start_time = start.value.checked_mul(scale / start_scale)?;
+        let end_time = end.value.checked_mul(scale / end_scale)?;
+        Self::new(Some(start_time), Some(end_time), scale.try_into().ok()?).ok()
+    }
+
+    /// Creates time bounds from scaled values, which need not have the same scale.
+    pub(crate) fn from_values(
+        start: Option<UnsignedScaledValue>,
+        end: Option<UnsignedScaledValue>,
+    ) -> Option<Self> {
+        match (start, end) {
+            (Some(start), Some(end)) => Self::from_range(start, end),
+            (Some(time), None) => Self::new(Some(time.value), None, time.scale).ok(),
+            (None, Some(time)) => Self::new(None, Some(time.value), time.scale).ok(),
+            (None, None) => None,
+        }
+    }
+
     #[must_use]
     pub fn duration(self) -> Option<ScaledDuration> {
         Some(ScaledDuration::new(
@@ -54,8 +78,21 @@ impl TimeBounds {
         self.end_time
             .map(|end| UnsignedScaledValue::new(end, self.scale))
     }
+
+    #[must_use]
+    pub const fn scale(&self) -> Scale {
+        self.scale
+    }
+}
+
+impl PartialEq for TimeBounds {
+    fn eq(&self, other: &Self) -> bool {
+        self.start_time() == other.start_time() && self.end_time() == other.end_time()
+    }
 }
 
+impl Eq for TimeBounds {}
+
 impl TryFrom<TimeBoundsData> for TimeBounds {
     type Error = Error;
 
@@ -53,7 +53,7 @@ mod tests {
         assert!(result.is_err());
         let error = result.unwrap_err().to_string();
         assert!(
-            error.contains("expected relative URL"),
+            error.contains("relative URL without a base"),
             "Error did not indicate scheme parsing failed `{error}`"
         );
     }
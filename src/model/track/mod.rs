@@ -4,7 +4,10 @@ pub use pattern::UrlPattern;
 pub use uid::TrackUid;
 
 use crate::util::Entity;
-use crate::{MediaType, ScaledDuration, Segment, SegmentId};
+use crate::{
+    segment_times, MediaType, Result, ScaledDuration, ScaledValue, Segment, SegmentId, TimeBounds,
+    UnsignedScaledValue,
+};
 
 mod continuation;
 mod initialization;
@@ -20,10 +23,15 @@ pub trait Track: Entity {
             .find(|segment| segment.id() == segment_id)
     }
     fn segments(&self) -> &[Segment];
+    /// Appends `segment`, which must directly follow the last segment of the track.
+    fn push_segment(&mut self, segment: Segment) -> Result<()>;
+    /// Removes the segments before `segment_id`, keeping the timeline of the remaining ones.
+    fn remove_segments_before(&mut self, segment_id: SegmentId);
 
     fn start_segment_id(&self) -> SegmentId;
     #[deprecated(note = "please use `start_segment_id` instead")]
     fn active_segment_id(&self) -> Option<SegmentId>;
+    /// Without a segment duration, every segment has time bounds that join the next segment.
     fn segment_duration(&self) -> Option<ScaledDuration>;
     fn duration_for_segment(&self, segment_id: SegmentId) -> Option<ScaledDuration> {
         self.segment_duration().or_else(|| {
@@ -32,6 +40,57 @@ pub trait Track: Entity {
         })
     }
 
+    /// Returns the time bounds of a segment on the presentation timeline.
+    ///
+    /// Segments without explicit time bounds are placed right after the previous segment.
+    /// When the track has a constant segment duration, segments after the last listed one are
+    /// extrapolated as well.
+    fn segment_time_bounds(&self, segment_id: SegmentId) -> Option<TimeBounds> {
+        let mut last = None;
+        for (id, start, end) in segment_times(self.segments(), self.segment_duration()) {
+            if id == segment_id {
+                return TimeBounds::from_range(start, end);
+            }
+            last = Some((id, end));
+        }
+        let (last_id, last_end) = last?;
+        if segment_id <= last_id {
+            return None;
+        }
+        let duration = UnsignedScaledValue::from(self.segment_duration()?);
+        let skipped = u64::from(segment_id) - u64::from(last_id) - 1;
+        let start = last_end.checked_add(duration.checked_mul(skipped.into())?)?;
+        TimeBounds::from_range(start, start.checked_add(duration)?)
+    }
+
+    /// Returns the id of the segment that contains `time` on the presentation timeline.
+    ///
+    /// See [`Track::segment_time_bounds`] for how segment times are determined.
+    fn segment_at(&self, time: UnsignedScaledValue) -> Option<SegmentId> {
+        let mut last = None;
+        for (id, start, end) in segment_times(self.segments(), self.segment_duration()) {
+            if time < start {
+                return None;
+            }
+            if time < end {
+                return Some(id);
+            }
+            last = Some((id, end));
+        }
+        let (last_id, last_end) = last?;
+        let duration = UnsignedScaledValue::from(self.segment_duration()?);
+        if duration.value == 0 {
+            return None;
+        }
+        let skipped = time.checked_sub(last_end)?.checked_div(duration)?.floor();
+        u64::from(last_id)
+            .checked_add(1)?
+            .checked_add(skipped)
+            .map(SegmentId::from)
+    }
+
+    /// The offset to add to a time on the presentation timeline to get the media time.
+    fn media_time_offset(&self) -> ScaledValue;
     fn average_bandwidth(&self) -> Option<u64>;
     fn bandwidth(&self) -> Option<u64>;
 
@@ -45,3 +104,126 @@ pub trait Track: Entity {
 pub trait InitializableTrack: Track + Initialization + Send {}
 
 impl<T: Track + Initialization + Send> InitializableTrack for T {}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{
+        Manifest, PresentationBuilder, Resolution, Scale, VideoSwitchingSetBuilder,
+        VideoTrackBuilder,
+    };
+
+    use super::*;
+
+    fn scale() -> Scale {
+        Scale::try_from(90_000u64).unwrap()
+    }
+
+    fn manifest(track: VideoTrackBuilder) -> Manifest {
+        Manifest::builder(Url::parse("http://localhost").unwrap(), 300)
+            .presentation(
+                PresentationBuilder::new("0", TimeBounds::new(Some(0), None, scale()).unwrap())
+                    .video(
+                        VideoSwitchingSetBuilder::new("main")
+                            .codecs("avc1.4d001f")
+                            .frame_rate(crate::FrameRate::new(25, Scale::ONE))
+                            .continuation_pattern("content-{segmentId}.mp4")
+                            .initialization_pattern("init-{initId}.mp4")
+                            .track(track),
+                    ),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn seconds(value: u64) -> UnsignedScaledValue {
+        value.into()
+    }
+
+    #[test]
+    fn extrapolate_constant_segment_duration() {
+        let manifest = manifest(
+            VideoTrackBuilder::new("720p", 1, Resolution::new(1280, 720))
+                .segment_duration(ScaledDuration::new(180_000, scale()))
+                .segment(Segment::new(
+                    10.into(),
+                    Some(TimeBounds::new(Some(900_000), None, scale()).unwrap()),
+                ))
+                .segment(Segment::new(11.into(), None)),
+        );
+        let track = manifest.video_tracks().next().unwrap();
+
+        assert_eq!(track.segment_at(seconds(9)), None);
+        assert_eq!(track.segment_at(seconds(10)), Some(10.into()));
+        assert_eq!(track.segment_at(seconds(13)), Some(11.into()));
+        assert_eq!(track.segment_at(seconds(21)), Some(15.into()));
+        assert_eq!(
+            track.segment_time_bounds(15.into()),
+            TimeBounds::new(Some(20), Some(22), Scale::ONE).ok()
+        );
+        assert_eq!(track.segment_time_bounds(9.into()), None);
+    }
+
+    #[test]
+    fn no_segment_beyond_the_last_segment_id() {
+        let manifest = manifest(
+            VideoTrackBuilder::new("720p", 1, Resolution::new(1280, 720))
+                .segment_duration(ScaledDuration::new(1, Scale::ONE))
+                .segment(Segment::new(
+                    1.into(),
+                    Some(TimeBounds::new(Some(0), None, Scale::ONE).unwrap()),
+                )),
+        );
+        let track = manifest.video_tracks().next().unwrap();
+
+        assert_eq!(track.segment_at(seconds(u64::MAX)), None);
+    }
+
+    #[test]
+    fn explicit_time_bounds_without_segment_duration() {
+        let bounds = |start, end| TimeBounds::new(Some(start), Some(end), Scale::ONE).unwrap();
+        let manifest = manifest(
+            VideoTrackBuilder::new("720p", 1, Resolution::new(1280, 720))
+                .segment(Segment::new(1.into(), Some(bounds(0, 2))))
+                .segment(Segment::new(2.into(), Some(bounds(2, 5)))),
+        );
+        let track = manifest.video_tracks().next().unwrap();
+
+        assert_eq!(track.segment_at(seconds(4)), Some(2.into()));
+        assert_eq!(track.segment_at(seconds(5)), None);
+        assert_eq!(track.segment_time_bounds(2.into()), Some(bounds(2, 5)));
+        assert_eq!(track.segment_time_bounds(3.into()), None);
+    }
+
+    #[test]
+    fn segment_duration_setter_keeps_time_bounds_defined() {
+        let bounds = |start, end| TimeBounds::new(Some(start), Some(end), Scale::ONE).unwrap();
+        let mut manifest = manifest(
+            VideoTrackBuilder::new("720p", 1, Resolution::new(1280, 720))
+                .segment_duration(ScaledDuration::new(2, Scale::ONE))
+                .segment(Segment::new(1.into(), Some(bounds(0, 2))))
+                .segment(Segment::new(2.into(), None)),
+        );
+        let track = manifest
+            .presentations_mut()
+            .next()
+            .unwrap()
+            .video_tracks_mut()
+            .next()
+            .unwrap();
+
+        assert!(matches!(
+            track.set_segment_duration(None),
+            Err(crate::Error::MissingSegmentDuration(id)) if id == "720p"
+        ));
+        assert_eq!(
+            track.segment_duration(),
+            Some(ScaledDuration::new(2, Scale::ONE))
+        );
+        track
+            .set_segment_duration(Some(ScaledDuration::new(4, Scale::ONE)))
+            .unwrap();
+        assert_eq!(track.segment_time_bounds(2.into()), Some(bounds(2, 6)));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::data::{VideoSwitchingSetData, VideoTrackData};
+use crate::util::{UInt, Uri};
+use crate::{
+    Error, FrameRate, Resolution, Result, ScaledDuration, ScaledValue, Segment, SegmentId,
+    SwitchingSetProtection, VideoMimeType,
+};
+
+#[derive(Debug, Clone)]
+pub struct VideoSwitchingSetBuilder {
+    id: String,
+    tracks: Vec<VideoTrackBuilder>,
+    align_id: Option<String>,
+    base_url: Option<Uri>,
+    codecs: Option<String>,
+    continuation_pattern: Option<String>,
+    frame_rate: Option<FrameRate>,
+    initialization_pattern: Option<String>,
+    label: Option<String>,
+    media_time_offset: Option<ScaledValue>,
+    mime_type: Option<VideoMimeType>,
+    protection: Option<SwitchingSetProtection>,
+}
+
+impl VideoSwitchingSetBuilder {
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            tracks: Vec::new(),
+            align_id: None,
+            base_url: None,
+            codecs: None,
+            continuation_pattern: None,
+            frame_rate: None,
+            initialization_pattern: None,
+            label: None,
+            media_time_offset: None,
+            mime_type: None,
+            protection: None,
+        }
+    }
+
+    #[must_use]
+    pub fn track(mut self, track: VideoTrackBuilder) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    #[must_use]
+    pub fn align_id(mut self, align_id: impl Into<String>) -> Self {
+        self.align_id = Some(align_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub fn codecs(mut self, codecs: impl Into<String>) -> Self {
+        self.codecs = Some(codecs.into());
+        self
+    }
+
+    #[must_use]
+    pub fn continuation_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.continuation_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn frame_rate(mut self, frame_rate: FrameRate) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
+    #[must_use]
+    pub fn initialization_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.initialization_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(mut self, media_time_offset: ScaledValue) -> Self {
+        self.media_time_offset = Some(media_time_offset);
+        self
+    }
+
+    #[must_use]
+    pub fn mime_type(mut self, mime_type: VideoMimeType) -> Self {
+        self.mime_type = Some(mime_type);
+        self
+    }
+
+    #[must_use]
+    pub fn protection(mut self, protection: SwitchingSetProtection) -> Self {
+        self.protection = Some(protection);
+        self
+    }
+}
+
+impl TryFrom<VideoSwitchingSetBuilder> for VideoSwitchingSetData {
+    type Error = Error;
+
+    fn try_from(input: VideoSwitchingSetBuilder) -> Result<Self> {
+        Ok(Self {
+            id: input.id,
+            tracks: input
+                .tracks
+                .into_iter()
+                .map(VideoTrackData::try_from)
+                .collect::<Result<_>>()?,
+            align_id: input.align_id,
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            frame_rate: input.frame_rate,
+            initialization_pattern: input.initialization_pattern,
+            label: input.label,
+            media_time_offset: input.media_time_offset,
+            mime_type: input.mime_type,
+            protection: input.protection,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoTrackBuilder {
+    id: String,
+    bandwidth: u64,
+    resolution: Resolution,
+    segments: Vec<Segment>,
+    start_segment_id: SegmentId,
+    start_sequence_number: u64,
+    average_bandwidth: Option<u64>,
+    base_url: Option<Uri>,
+    codecs: Option<String>,
+    continuation_pattern: Option<String>,
+    frame_rate: Option<FrameRate>,
+    label: Option<String>,
+    initialization_pattern: Option<String>,
+    media_time_offset: Option<ScaledValue>,
+    segment_duration: Option<ScaledDuration>,
+}
+
+impl VideoTrackBuilder {
+    #[must_use]
+    pub fn new(id: impl Into<String>, bandwidth: u64, resolution: Resolution) -> Self {
+        Self {
+            id: id.into(),
+            bandwidth,
+            resolution,
+            segments: Vec::new(),
+            start_segment_id: SegmentId::default(),
+            start_sequence_number: 0,
+            average_bandwidth: None,
+            base_url: None,
+            codecs: None,
+            continuation_pattern: None,
+            frame_rate: None,
+            label: None,
+            initialization_pattern: None,
+            media_time_offset: None,
+            segment_duration: None,
+        }
+    }
+
+    #[must_use]
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    #[must_use]
+    pub fn segments<I: IntoIterator<Item = Segment>>(mut self, segments: I) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    #[must_use]
+    pub const fn start_segment_id(mut self, start_segment_id: SegmentId) -> Self {
+        self.start_segment_id = start_segment_id;
+        self
+    }
+
+    #[must_use]
+    pub const fn start_sequence_number(mut self, start_sequence_number: u64) -> Self {
+        self.start_sequence_number = start_sequence_number;
+        self
+    }
+
+    #[must_use]
+    pub const fn average_bandwidth(mut self, average_bandwidth: u64) -> Self {
+        self.average_bandwidth = Some(average_bandwidth);
+        self
+    }
+
+    #[must_use]
+    pub fn base_url(mut self, base_url: Uri) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    #[must_use]
+    pub fn codecs(mut self, codecs: impl Into<String>) -> Self {
+        self.codecs = Some(codecs.into());
+        self
+    }
+
+    #[must_use]
+    pub fn continuation_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.continuation_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn frame_rate(mut self, frame_rate: FrameRate) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub fn initialization_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.initialization_pattern = Some(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(mut self, media_time_offset: ScaledValue) -> Self {
+        self.media_time_offset = Some(media_time_offset);
+        self
+    }
+
+    #[must_use]
+    pub const fn segment_duration(mut self, segment_duration: ScaledDuration) -> Self {
+        self.segment_duration = Some(segment_duration);
+        self
+    }
+}
+
+impl TryFrom<VideoTrackBuilder> for VideoTrackData {
+    type Error = Error;
+
+    fn try_from(input: VideoTrackBuilder) -> Result<Self> {
+        #[allow(deprecated)]
+        Ok(Self {
+            id: input.id,
+            bandwidth: input.bandwidth.into(),
+            resolution: input.resolution,
+            segments: input.segments.try_into()?,
+            start_segment_id: input.start_segment_id,
+            active_segment_id: None,
+            start_sequence_number: input.start_sequence_number.into(),
+            active_sequence_number: None,
+            average_bandwidth: input.average_bandwidth.map(UInt::from),
+            base_url: input.base_url,
+            codecs: input.codecs,
+            continuation_pattern: input.continuation_pattern,
+            frame_rate: input.frame_rate,
+            label: input.label,
+            initialization_pattern: input.initialization_pattern,
+            media_time_offset: input.media_time_offset,
+            segment_duration: input.segment_duration,
+        })
+    }
+}
@@ -1,7 +1,9 @@
+pub use builder::*;
 pub use mime::VideoMimeType;
 pub use switching_set::VideoSwitchingSet;
 pub use track::VideoTrack;
 
+mod builder;
 mod mime;
 mod switching_set;
 mod track;
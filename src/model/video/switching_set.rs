@@ -1,12 +1,16 @@
+use itertools::Itertools;
+
 use crate::data::VideoSwitchingSetData;
 use crate::util::{Entity, EntityIter, EntityIterMut, EntityMap, FromEntities};
 use crate::{
-    Address, MediaType, Result, SwitchingSet, SwitchingSetProtection, VideoMimeType, VideoTrack,
+    Address, Error, MediaType, Resolution, Result, SwitchingSet, SwitchingSetProtection, Track,
+    VideoMimeType, VideoTrack,
 };
 
 #[derive(Debug, Clone)]
 pub struct VideoSwitchingSet {
     pub(crate) id: String,
+    presentation_id: String,
     pub(crate) tracks: EntityMap<VideoTrack>,
     pub(crate) align_id: Option<String>,
     pub(crate) label: Option<String>,
@@ -16,6 +20,83 @@ pub struct VideoSwitchingSet {
 
 impl VideoSwitchingSet {
     const MEDIA_TYPE: MediaType = MediaType::Video;
+
+    pub(crate) fn reparent(&mut self, presentation_id: &str) {
+        presentation_id.clone_into(&mut self.presentation_id);
+        for track in &mut self.tracks {
+            track.reparent(presentation_id, &self.id);
+        }
+    }
+
+    #[must_use]
+    pub fn align_id(&self) -> Option<&str> {
+        self.align_id.as_deref()
+    }
+
+    pub fn set_align_id(&mut self, align_id: Option<String>) {
+        self.align_id = align_id;
+    }
+
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    #[must_use]
+    pub const fn protection(&self) -> Option<&SwitchingSetProtection> {
+        self.protection.as_ref()
+    }
+
+    pub fn set_protection(&mut self, protection: Option<SwitchingSetProtection>) {
+        self.protection = protection;
+    }
+
+    /// Returns the track with the highest bandwidth that fits in `bandwidth` bits per second
+    /// and does not exceed `max_resolution`.
+    ///
+    /// Falls back to the lowest track within `max_resolution` when none fits the bandwidth.
+    #[must_use]
+    pub fn best_track_for(
+        &self,
+        bandwidth: u64,
+        max_resolution: Option<&Resolution>,
+    ) -> Option<&VideoTrack> {
+        let ladder: Vec<_> = self
+            .tracks_by_bandwidth()
+            .into_iter()
+            .filter(|track| {
+                max_resolution.is_none_or(|max| {
+                    track.resolution().width() <= max.width()
+                        && track.resolution().height() <= max.height()
+                })
+            })
+            .collect();
+        ladder
+            .iter()
+            .rev()
+            .find(|track| track.bandwidth <= bandwidth)
+            .or_else(|| ladder.first())
+            .copied()
+    }
+
+    /// Checks that the resolution never decreases when moving up the bandwidth ladder.
+    pub fn validate_ladder(&self) -> Result<()> {
+        for (lower, higher) in self.tracks_by_bandwidth().into_iter().tuple_windows() {
+            if higher.resolution().width() < lower.resolution().width()
+                || higher.resolution().height() < lower.resolution().height()
+            {
+                return Err(Error::NonMonotonicLadder {
+                    lower: lower.uid().to_string(),
+                    higher: higher.uid().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Entity for VideoSwitchingSet {
@@ -31,14 +112,23 @@ impl SwitchingSet for VideoSwitchingSet {
         Self::MEDIA_TYPE
     }
 
-    fn tracks(&self) -> EntityIter<VideoTrack> {
+    fn tracks(&self) -> EntityIter<'_, VideoTrack> {
         self.tracks.iter()
     }
 
-    fn tracks_mut(&mut self) -> EntityIterMut<VideoTrack> {
+    fn tracks_mut(&mut self) -> EntityIterMut<'_, VideoTrack> {
         self.tracks.iter_mut()
     }
 
+    fn insert_track(&mut self, mut track: VideoTrack) -> Result<()> {
+        track.reparent(&self.presentation_id, &self.id);
+        self.tracks.insert(track)
+    }
+
+    fn remove_track(&mut self, id: &str) -> Option<VideoTrack> {
+        self.tracks.remove(id)
+    }
+
     fn track(&self, id: &str) -> Option<&VideoTrack> {
         self.tracks.get(id)
     }
@@ -80,6 +170,7 @@ impl VideoSwitchingSet {
             .into_entities()?;
         Ok(Self {
             id: data.id,
+            presentation_id: presentation_id.to_owned(),
             tracks,
             align_id: data.align_id,
             label: data.label,
@@ -88,3 +179,93 @@ impl VideoSwitchingSet {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+
+    fn switching_set(tracks: &[(&str, u64, u64, u64)]) -> anyhow::Result<VideoSwitchingSet> {
+        let tracks = tracks
+            .iter()
+            .map(|(id, bandwidth, width, height)| {
+                format!(
+                    r#"{{
+                        "id": "{id}",
+                        "bandwidth": {bandwidth},
+                        "resolution": {{"width": {width}, "height": {height}}},
+                        "segmentDuration": {{"value": 2}},
+                        "segments": []
+                    }}"#
+                )
+            })
+            .join(",");
+        let data = format!(
+            r#"
+            {{
+                "id": "main",
+                "codecs": "avc1.4d001f",
+                "frameRate": {{"value": 25}},
+                "continuationPattern": "content-{{segmentId}}.mp4",
+                "initializationPattern": "init-{{initId}}.mp4",
+                "tracks": [{tracks}]
+            }}"#
+        );
+        let address = Address::new(Url::parse("http://localhost")?, None)?;
+        let data = serde_json::from_str::<VideoSwitchingSetData>(&data)?;
+        Ok(VideoSwitchingSet::new("p1", &address, data)?)
+    }
+
+    #[test]
+    fn bandwidth_ladder() -> anyhow::Result<()> {
+        let set = switching_set(&[
+            ("1080p", 6_000_000, 1920, 1080),
+            ("360p", 800_000, 640, 360),
+            ("720p", 3_000_000, 1280, 720),
+        ])?;
+
+        let ladder: Vec<_> = set
+            .tracks_by_bandwidth()
+            .into_iter()
+            .map(Entity::id)
+            .collect();
+        assert_eq!(ladder, ["360p", "720p", "1080p"]);
+        let middle = set.track("720p").unwrap().uid();
+        assert_eq!(set.next_higher(middle).map(Entity::id), Some("1080p"));
+        assert_eq!(set.next_lower(middle).map(Entity::id), Some("360p"));
+        let top = set.track("1080p").unwrap().uid();
+        assert!(set.next_higher(top).is_none());
+
+        let best = |bandwidth, max_resolution: Option<Resolution>| {
+            set.best_track_for(bandwidth, max_resolution.as_ref())
+                .map(Entity::id)
+        };
+        assert_eq!(best(4_000_000, None), Some("720p"));
+        assert_eq!(best(10_000_000, None), Some("1080p"));
+        assert_eq!(
+            best(10_000_000, Some(Resolution::new(1280, 720))),
+            Some("720p")
+        );
+        assert_eq!(best(100_000, None), Some("360p"));
+        assert_eq!(best(10_000_000, Some(Resolution::new(320, 180))), None);
+        set.validate_ladder()?;
+        Ok(())
+    }
+
+    #[test]
+    fn reject_non_monotonic_ladder() -> anyhow::Result<()> {
+        let set = switching_set(&[
+            ("720p", 3_000_000, 1280, 720),
+            ("540p", 4_000_000, 960, 540),
+        ])?;
+
+        let error = set.validate_ladder().unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "Track p1/video/main/540p has a higher bandwidth than track p1/video/main/720p \
+             but a lower resolution"
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::data::VideoTrackData;
 use crate::util::Entity;
 use crate::{
-    Address, ContinuationPattern, Error, FrameRate, Initialization, InitializationPattern,
+    Address, Codecs, ContinuationPattern, Error, FrameRate, Initialization, InitializationPattern,
     MediaType, Resolution, Result, ScaledDuration, ScaledValue, Segment, SegmentId, Segments,
     Track, TrackUid, VideoMimeType,
 };
@@ -19,7 +19,7 @@ pub struct VideoTrack {
     #[deprecated(note = "please use `start_sequence_number` instead")]
     pub(crate) active_sequence_number: Option<u64>,
     pub(crate) average_bandwidth: Option<u64>,
-    pub(crate) codecs: String,
+    pub(crate) codecs: Codecs,
     pub(crate) continuation_pattern: ContinuationPattern,
     pub(crate) frame_rate: FrameRate,
     pub(crate) label: Option<String>,
@@ -31,6 +31,15 @@ pub struct VideoTrack {
 
 impl VideoTrack {
     const MEDIA_TYPE: MediaType = MediaType::Video;
+
+    pub(crate) fn reparent(&mut self, presentation_id: &str, switching_set_id: &str) {
+        self.uid = TrackUid::new(
+            presentation_id.to_owned(),
+            Self::MEDIA_TYPE,
+            switching_set_id.to_owned(),
+            self.uid.track_id().to_owned(),
+        );
+    }
 }
 
 impl Entity for VideoTrack {
@@ -48,6 +57,16 @@ impl Track for VideoTrack {
         &self.segments
     }
 
+    fn push_segment(&mut self, segment: Segment) -> Result<()> {
+        let id = self.uid.track_id();
+        self.segments.push(segment, self.segment_duration, id)
+    }
+
+    fn remove_segments_before(&mut self, segment_id: SegmentId) {
+        self.segments
+            .remove_before(segment_id, self.segment_duration);
+    }
+
     fn start_segment_id(&self) -> SegmentId {
         self.start_segment_id
     }
@@ -61,6 +80,10 @@ impl Track for VideoTrack {
         self.segment_duration
     }
 
+    fn media_time_offset(&self) -> ScaledValue {
+        self.media_time_offset
+    }
+
     fn average_bandwidth(&self) -> Option<u64> {
         self.average_bandwidth
     }
@@ -121,7 +144,8 @@ impl VideoTrack {
         let address = switching_set_address.join(data.base_url)?;
         let codecs = data
             .codecs
-            .ok_or_else(|| Error::MissingCodecs(id.clone()))?;
+            .ok_or_else(|| Error::MissingCodecs(id.clone()))?
+            .try_into()?;
         let continuation_pattern = data
             .continuation_pattern
             .ok_or_else(|| Error::MissingContinuationPattern(id.clone()))?;
@@ -160,4 +184,69 @@ impl VideoTrack {
     pub const fn start_sequence_number(&self) -> u64 {
         self.start_sequence_number
     }
+
+    pub const fn set_start_sequence_number(&mut self, start_sequence_number: u64) {
+        self.start_sequence_number = start_sequence_number;
+    }
+
+    #[must_use]
+    pub const fn codecs(&self) -> &Codecs {
+        &self.codecs
+    }
+
+    pub fn set_codecs(&mut self, codecs: Codecs) {
+        self.codecs = codecs;
+    }
+
+    #[must_use]
+    pub const fn resolution(&self) -> &Resolution {
+        &self.resolution
+    }
+
+    pub const fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    pub const fn set_frame_rate(&mut self, frame_rate: FrameRate) {
+        self.frame_rate = frame_rate;
+    }
+
+    pub const fn set_bandwidth(&mut self, bandwidth: u64) {
+        self.bandwidth = bandwidth;
+    }
+
+    pub const fn set_average_bandwidth(&mut self, average_bandwidth: Option<u64>) {
+        self.average_bandwidth = average_bandwidth;
+    }
+
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    #[must_use]
+    pub const fn media_time_offset(&self) -> ScaledValue {
+        self.media_time_offset
+    }
+
+    pub const fn set_media_time_offset(&mut self, media_time_offset: ScaledValue) {
+        self.media_time_offset = media_time_offset;
+    }
+
+    pub const fn set_start_segment_id(&mut self, start_segment_id: SegmentId) {
+        self.start_segment_id = start_segment_id;
+    }
+
+    pub fn set_segment_duration(&mut self, segment_duration: Option<ScaledDuration>) -> Result<()> {
+        if segment_duration.is_none() {
+            self.segments
+                .ensure_time_bounds_defined(self.uid.track_id())?;
+        }
+        self.segment_duration = segment_duration;
+        Ok(())
+    }
 }
@@ -0,0 +1,208 @@
+use std::time::{Duration, Instant};
+
+use url::Url;
+
+use crate::event::ManifestUpdate;
+use crate::{Error, Manifest, Result};
+
+/// Fetches manifests for a [`ManifestPoller`].
+pub trait Transport {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    fn fetch(&mut self, url: &Url) -> std::result::Result<Response, Self::Error>;
+}
+
+/// A fetched manifest.
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// The location of the manifest after following redirects.
+    pub url: Url,
+    pub body: String,
+}
+
+/// Keeps a live manifest up to date by refetching it.
+///
+/// The manifest is refreshed every `fallback_poll_rate` seconds, or as soon as a
+/// [`ManifestUpdate`] event arrives. The poller does not sleep: call [`ManifestPoller::poll`]
+/// whenever it suits the player, e.g. at [`ManifestPoller::next_refresh`].
+pub struct ManifestPoller<T: Transport> {
+    transport: T,
+    location: Url,
+    manifest: Option<Manifest>,
+    refresh_at: Option<Instant>,
+    failures: u32,
+}
+
+impl<T: Transport> ManifestPoller<T> {
+    /// The delay before retrying when there is no manifest to take the poll rate from.
+    pub const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    #[must_use]
+    pub const fn new(transport: T, location: Url) -> Self {
+        Self {
+            transport,
+            location,
+            manifest: None,
+            refresh_at: None,
+            failures: 0,
+        }
+    }
+
+    /// The location the manifest is fetched from, which follows redirects and manifest updates.
+    #[must_use]
+    pub const fn location(&self) -> &Url {
+        &self.location
+    }
+
+    /// The latest manifest that was fetched and parsed successfully.
+    #[must_use]
+    pub const fn manifest(&self) -> Option<&Manifest> {
+        self.manifest.as_ref()
+    }
+
+    /// The number of refreshes that failed since the last successful one.
+    #[must_use]
+    pub const fn consecutive_failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// When the manifest should be refreshed next, `None` when it is due right away.
+    #[must_use]
+    pub const fn next_refresh(&self) -> Option<Instant> {
+        self.refresh_at
+    }
+
+    /// Refreshes the manifest if it is due at `now`.
+    pub fn poll(&mut self, now: Instant) -> Option<Result<&Manifest>> {
+        if self.refresh_at.is_some_and(|refresh_at| now < refresh_at) {
+            return None;
+        }
+        Some(self.refresh(now))
+    }
+
+    /// Fetches and parses the manifest.
+    ///
+    /// On failure the previous manifest is kept and the next refresh is scheduled as usual.
+    pub fn refresh(&mut self, now: Instant) -> Result<&Manifest> {
+        let result = self.fetch();
+        let poll_rate = result
+            .as_ref()
+            .ok()
+            .or(self.manifest.as_ref())
+            .map_or(Self::RETRY_DELAY, |manifest| {
+                Duration::from_secs(manifest.fallback_poll_rate)
+            });
+        self.refresh_at = now.checked_add(poll_rate);
+        match result {
+            Ok(manifest) => {
+                self.failures = 0;
+                Ok(self.manifest.insert(manifest))
+            }
+            Err(error) => {
+                self.failures += 1;
+                Err(error)
+            }
+        }
+    }
+
+    /// Handles a manifest update event by refreshing at the next poll, from its `url` if any.
+    pub fn manifest_update(&mut self, update: &ManifestUpdate) {
+        if let Some(url) = &update.url {
+            self.location = url.clone();
+        }
+        self.refresh_at = None;
+    }
+
+    fn fetch(&mut self) -> Result<Manifest> {
+        let response = self
+            .transport
+            .fetch(&self.location)
+            .map_err(|error| Error::Fetch(self.location.clone(), Box::new(error)))?;
+        self.location = response.url;
+        Manifest::from_json(self.location.clone(), &response.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct InMemory {
+        redirects: HashMap<Url, Url>,
+        manifests: HashMap<Url, String>,
+    }
+
+    impl Transport for InMemory {
+        type Error = io::Error;
+
+        fn fetch(&mut self, url: &Url) -> io::Result<Response> {
+            let url = self.redirects.get(url).unwrap_or(url).clone();
+            let body = self
+                .manifests
+                .get(&url)
+                .ok_or(io::ErrorKind::NotFound)?
+                .clone();
+            Ok(Response { url, body })
+        }
+    }
+
+    fn manifest(poll_rate: u64) -> String {
+        format!(
+            r#"
+            {{
+                "creationDate": "2021-03-31T08:00:00.000Z",
+                "fallbackPollRate": {poll_rate},
+                "manifestVersion": "2.0.0",
+                "presentations": [{{"id": "0", "timeBounds": {{"startTime": 0}}}}],
+                "streamType": "vod"
+            }}"#
+        )
+    }
+
+    #[test]
+    fn poll_and_follow_redirects() -> anyhow::Result<()> {
+        let origin = Url::parse("https://origin.example.com/manifest.json")?;
+        let cdn = Url::parse("https://cdn.example.com/manifest.json")?;
+        let mut transport = InMemory::default();
+        transport.redirects.insert(origin.clone(), cdn.clone());
+        transport.manifests.insert(cdn.clone(), manifest(10));
+        let mut poller = ManifestPoller::new(transport, origin);
+        let now = Instant::now();
+
+        assert!(poller.poll(now).unwrap().is_ok());
+        assert_eq!(poller.location(), &cdn);
+        assert_eq!(poller.next_refresh(), Some(now + Duration::from_secs(10)));
+        assert!(poller.poll(now + Duration::from_secs(9)).is_none());
+
+        poller.transport.manifests.insert(cdn, "{}".to_owned());
+        assert!(poller.poll(now + Duration::from_secs(10)).unwrap().is_err());
+        assert_eq!(poller.consecutive_failures(), 1);
+        assert_eq!(poller.manifest().unwrap().fallback_poll_rate, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn refresh_on_manifest_update() -> anyhow::Result<()> {
+        let first = Url::parse("https://example.com/first.json")?;
+        let second = Url::parse("https://example.com/second.json")?;
+        let mut transport = InMemory::default();
+        transport.manifests.insert(first.clone(), manifest(10));
+        transport.manifests.insert(second.clone(), manifest(20));
+        let mut poller = ManifestPoller::new(transport, first);
+        let now = Instant::now();
+        poller.refresh(now)?;
+
+        poller.manifest_update(&ManifestUpdate {
+            url: Some(second.clone()),
+        });
+
+        assert!(poller.poll(now).unwrap().is_ok());
+        assert_eq!(poller.location(), &second);
+        assert_eq!(poller.manifest().unwrap().fallback_poll_rate, 20);
+        Ok(())
+    }
+}
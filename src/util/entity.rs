@@ -1,5 +1,5 @@
-use std::collections::{hash_map, HashMap};
-use std::vec;
+use std::collections::HashMap;
+use std::{slice, vec};
 
 use crate::{Error, Result};
 
@@ -7,51 +7,133 @@ pub trait Entity {
     fn id(&self) -> &str;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Entities indexed by id that keep their document order.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EntityMap<E: Entity> {
-    inner: HashMap<String, E>,
-    keys: Vec<String>,
+    entities: Vec<E>,
+    index: HashMap<String, usize>,
+}
+
+impl<E: Entity> Default for EntityMap<E> {
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
 }
 
 impl<E: Entity> EntityMap<E> {
     #[must_use]
     pub fn get(&self, id: &str) -> Option<&E> {
-        self.inner.get(id)
+        self.index.get(id).map(|&index| &self.entities[index])
     }
 
     #[must_use]
     pub fn get_mut(&mut self, id: &str) -> Option<&mut E> {
-        self.inner.get_mut(id)
+        self.index.get(id).map(|&index| &mut self.entities[index])
     }
 
+    /// Returns the position of the entity with `id` in document order.
     #[must_use]
-    pub fn len(&self) -> usize {
-        self.inner.len()
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
     }
 
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+    pub const fn len(&self) -> usize {
+        self.entities.len()
     }
 
     #[must_use]
-    pub fn iter(&self) -> EntityIter<E> {
+    pub const fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Appends `entity`, failing when its id is already taken.
+    pub fn insert(&mut self, entity: E) -> Result<()> {
+        self.insert_at(self.len(), entity)
+    }
+
+    /// Inserts `entity` at `index`, shifting the entities after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert_at(&mut self, index: usize, entity: E) -> Result<()> {
+        if self.index.contains_key(entity.id()) {
+            return Err(Error::DuplicateId(entity.id().to_owned()));
+        }
+        self.entities.insert(index, entity);
+        self.reindex(index);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<E> {
+        let index = self.index.remove(id)?;
+        let entity = self.entities.remove(index);
+        self.reindex(index);
+        Some(entity)
+    }
+
+    pub fn retain<F: FnMut(&E) -> bool>(&mut self, f: F) {
+        self.entities.retain(f);
+        self.index.clear();
+        self.reindex(0);
+    }
+
+    /// Moves the entity with `id` to `index`, returning `false` when there is no such entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn move_to(&mut self, id: &str, index: usize) -> bool {
+        let Some(from) = self.position(id) else {
+            return false;
+        };
+        assert!(index < self.len(), "index {index} out of bounds");
+        if from < index {
+            self.entities[from..=index].rotate_left(1);
+        } else {
+            self.entities[index..=from].rotate_right(1);
+        }
+        self.reindex(from.min(index));
+        true
+    }
+
+    /// Reorders the entities with a stable sort.
+    pub fn sort_by<F: FnMut(&E, &E) -> std::cmp::Ordering>(&mut self, compare: F) {
+        self.entities.sort_by(compare);
+        self.reindex(0);
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> EntityIter<'_, E> {
         EntityIter {
-            inner: self.inner.values(),
+            inner: self.entities.iter(),
         }
     }
 
     #[must_use]
-    pub fn iter_mut(&mut self) -> EntityIterMut<E> {
+    pub fn iter_mut(&mut self) -> EntityIterMut<'_, E> {
         EntityIterMut {
-            inner: self.inner.values_mut(),
+            inner: self.entities.iter_mut(),
+        }
+    }
+
+    fn reindex(&mut self, from: usize) {
+        for (index, entity) in self.entities.iter().enumerate().skip(from) {
+            if let Some(position) = self.index.get_mut(entity.id()) {
+                *position = index;
+            } else {
+                self.index.insert(entity.id().to_owned(), index);
+            }
         }
     }
 }
 
 pub struct OrderedEntityIter<E: Entity> {
-    map: HashMap<String, E>,
-    key_iter: vec::IntoIter<String>,
+    inner: vec::IntoIter<E>,
 }
 
 impl<E: Entity> Iterator for OrderedEntityIter<E> {
@@ -59,10 +141,12 @@ impl<E: Entity> Iterator for OrderedEntityIter<E> {
 
     #[inline]
     fn next(&mut self) -> Option<E> {
-        self.key_iter
-            .next()
-            .map(|key| self.map.remove_entry(&key).unwrap())
-            .map(|(_, entity)| entity)
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
 
@@ -73,8 +157,7 @@ impl<E: Entity> IntoIterator for EntityMap<E> {
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
         OrderedEntityIter {
-            map: self.inner,
-            key_iter: self.keys.into_iter(),
+            inner: self.entities.into_iter(),
         }
     }
 }
@@ -100,7 +183,7 @@ impl<'a, E: Entity> IntoIterator for &'a mut EntityMap<E> {
 }
 
 pub struct EntityIter<'a, E: Entity> {
-    inner: hash_map::Values<'a, String, E>,
+    inner: slice::Iter<'a, E>,
 }
 
 impl<'a, E: Entity> Iterator for EntityIter<'a, E> {
@@ -117,7 +200,14 @@ impl<'a, E: Entity> Iterator for EntityIter<'a, E> {
     }
 }
 
-impl<'a, E: Entity> ExactSizeIterator for EntityIter<'a, E> {
+impl<E: Entity> DoubleEndedIterator for EntityIter<'_, E> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<E: Entity> ExactSizeIterator for EntityIter<'_, E> {
     #[inline]
     fn len(&self) -> usize {
         self.inner.len()
@@ -125,7 +215,7 @@ impl<'a, E: Entity> ExactSizeIterator for EntityIter<'a, E> {
 }
 
 pub struct EntityIterMut<'a, E: Entity> {
-    inner: hash_map::ValuesMut<'a, String, E>,
+    inner: slice::IterMut<'a, E>,
 }
 
 impl<'a, E: Entity> Iterator for EntityIterMut<'a, E> {
@@ -142,7 +232,14 @@ impl<'a, E: Entity> Iterator for EntityIterMut<'a, E> {
     }
 }
 
-impl<'a, E: Entity> ExactSizeIterator for EntityIterMut<'a, E> {
+impl<E: Entity> DoubleEndedIterator for EntityIterMut<'_, E> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<E: Entity> ExactSizeIterator for EntityIterMut<'_, E> {
     #[inline]
     fn len(&self) -> usize {
         self.inner.len()
@@ -159,16 +256,14 @@ impl<E: Entity, I: IntoIterator<Item = Result<E>>> FromEntities<E> for I {
     fn into_entities(self) -> Result<EntityMap<E>> {
         let iter = self.into_iter();
         let size_hint = iter.size_hint().0;
-        let mut inner = HashMap::with_capacity(size_hint);
-        let mut keys = Vec::with_capacity(size_hint);
+        let mut map = EntityMap {
+            entities: Vec::with_capacity(size_hint),
+            index: HashMap::with_capacity(size_hint),
+        };
         for entity in iter {
-            let entity = entity?;
-            keys.push(entity.id().to_owned());
-            if let Some(duplicate) = inner.insert(entity.id().to_owned(), entity) {
-                return Err(Error::DuplicateId(duplicate.id().to_string()));
-            }
+            map.insert(entity?)?;
         }
-        Ok(EntityMap { inner, keys })
+        Ok(map)
     }
 }
 
@@ -199,6 +294,72 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn entity_map_insert_rejects_duplicates() -> Result<()> {
+        let mut map: EntityMap<DummyEntity> = vec![Ok(DummyEntity("a"))].into_entities()?;
+
+        map.insert(DummyEntity("b"))?;
+        let result = map.insert(DummyEntity("a"));
+
+        assert!(matches!(result, Err(Error::DuplicateId(id)) if id == "a"));
+        assert_eq!(map.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn entity_map_remove_keeps_order() -> Result<()> {
+        let mut map: EntityMap<DummyEntity> = vec!["a", "b", "c"]
+            .into_iter()
+            .map(|id| Ok(DummyEntity(id)))
+            .into_entities()?;
+
+        assert_eq!(map.remove("b").map(|e| e.0), Some("b"));
+        assert!(map.remove("b").is_none());
+        map.insert(DummyEntity("b"))?;
+
+        let ids: Vec<_> = map.into_iter().map(|e| e.0).collect();
+        assert_eq!(ids, ["a", "c", "b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn entity_map_iterates_in_order() -> Result<()> {
+        let mut map: EntityMap<DummyEntity> = vec!["t", "h", "e", "o", "r", "u", "l", "z"]
+            .into_iter()
+            .map(|id| Ok(DummyEntity(id)))
+            .into_entities()?;
+
+        let ids: String = map.iter().map(|e| e.0).collect();
+        assert_eq!(ids, "theorulz");
+        let ids: String = map.iter_mut().rev().map(|e| e.0).collect();
+        assert_eq!(ids, "zluroeht");
+        Ok(())
+    }
+
+    #[test]
+    fn entity_map_reorders() -> Result<()> {
+        let mut map: EntityMap<DummyEntity> = vec!["a", "b", "c"]
+            .into_iter()
+            .map(|id| Ok(DummyEntity(id)))
+            .into_entities()?;
+
+        map.insert_at(1, DummyEntity("d"))?;
+        assert!(map.insert_at(0, DummyEntity("c")).is_err());
+        assert!(map.move_to("a", 3));
+        assert!(map.move_to("c", 0));
+        assert!(!map.move_to("e", 0));
+        let ids: String = map.iter().map(|e| e.0).collect();
+        assert_eq!(ids, "cdba");
+        assert_eq!(map.position("b"), Some(2));
+
+        map.sort_by(|a, b| a.0.cmp(b.0));
+        let ids: String = map.iter().map(|e| e.0).collect();
+        assert_eq!(ids, "abcd");
+        assert_eq!(map.get("d").map(|e| e.0), Some("d"));
+        assert_eq!(map.position("d"), Some(3));
+        Ok(())
+    }
+
     struct DummyEntity(&'static str);
 
     impl Entity for DummyEntity {
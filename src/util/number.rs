@@ -3,8 +3,8 @@ use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{Error, Result};
 
-const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
-const MIN_SAFE_INTEGER: i64 = -9_007_199_254_740_991;
+pub const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
+pub const MIN_SAFE_INTEGER: i64 = -9_007_199_254_740_991;
 
 #[derive(
     Copy, Clone, Serialize, Deserialize, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Default,
@@ -68,24 +68,15 @@ impl From<Int> for i64 {
 
 pub fn check_js_safety(number: i64) {
     if number > MAX_SAFE_INTEGER {
-        warn!(
-            "Number {} is too large to fit in a JavaScript Number",
-            number
-        );
+        warn!("Number {number} is too large to fit in a JavaScript Number");
     } else if number < MIN_SAFE_INTEGER {
-        warn!(
-            "Number {} is too small to fit in a JavaScript Number",
-            number
-        );
+        warn!("Number {number} is too small to fit in a JavaScript Number");
     }
 }
 
 pub fn check_js_safety_unsigned(number: u64) {
     if number > MAX_SAFE_INTEGER as u64 {
-        warn!(
-            "Number {} is too large to fit in a JavaScript Number",
-            number
-        );
+        warn!("Number {number} is too large to fit in a JavaScript Number");
     }
 }
 
@@ -1,4 +1,4 @@
-use hesp_manifest::Manifest;
+use hesp_manifest::{Error, Manifest, ManifestVersion};
 use std::fs;
 use url::Url;
 
@@ -41,3 +41,90 @@ fn validate_empty_manifest() -> anyhow::Result<()> {
     assert!(error.contains("missing field"), "Wrong error `{error}`");
     Ok(())
 }
+
+#[test]
+fn serialize_legacy_versions() -> anyhow::Result<()> {
+    let location = Url::parse("http://localhost")?;
+    let input = fs::read_to_string("tests/v1_1_0-manifest.json")?;
+    let manifest = Manifest::from_json(location.clone(), &input)?;
+
+    for version in [ManifestVersion::V1_0_0, ManifestVersion::V1_1_0] {
+        let output = manifest.to_json_version(version)?;
+        let json: serde_json::Value = serde_json::from_str(&output)?;
+        assert_eq!(json["manifestVersion"], version.to_string());
+        assert_eq!(
+            json["presentations"][1]["currentTime"]["value"],
+            1_080_000_000
+        );
+        assert!(json["presentations"][0].get("currentTime").is_none());
+
+        let reparsed = Manifest::from_json(location.clone(), &output)?;
+        assert!(manifest.diff(&reparsed).is_empty(), "{version} differs");
+    }
+    Ok(())
+}
+
+#[test]
+fn serialize_v2_0_0_to_legacy_version() -> anyhow::Result<()> {
+    let location = Url::parse("http://localhost")?;
+    let input = fs::read_to_string("tests/v2_0_0-manifest.json")?;
+    let manifest = Manifest::from_json(location.clone(), &input)?;
+
+    let result = manifest.to_json_version(ManifestVersion::V1_1_0);
+    assert!(matches!(result, Err(Error::UnsupportedTimeSourceScheme(_))));
+
+    let mut data: serde_json::Value = serde_json::from_str(&input)?;
+    data.as_object_mut().unwrap().remove("timeSource");
+    let manifest = Manifest::from_json(location.clone(), &data.to_string())?;
+    let result = manifest.to_json_version(ManifestVersion::V1_1_0);
+    assert!(matches!(result, Err(Error::MissingActiveSequenceNumber(_))));
+
+    for kind in ["audio", "video"] {
+        for track in data["presentations"][1][kind][0]["tracks"]
+            .as_array_mut()
+            .unwrap()
+        {
+            track["activeSequenceNumber"] = 7.into();
+        }
+    }
+    data["presentations"][0]["metadata"] = serde_json::from_str(
+        r#"[{
+            "id": "events",
+            "mimeType": "application/json",
+            "schemeId": "urn:example:events",
+            "continuationPattern": "events/{segmentId}.json",
+            "tracks": [{
+                "id": "events",
+                "segmentDuration": {"value": 540000, "scale": 90000},
+                "segments": [{"id": 1799, "timeBounds": {"startTime": 971460000, "scale": 90000}}]
+            }]
+        }]"#,
+    )?;
+    let manifest = Manifest::from_json(location.clone(), &data.to_string())?;
+    let output = manifest.to_json_version(ManifestVersion::V1_1_0)?;
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let track = &json["presentations"][0]["video"][0]["tracks"][0];
+    assert!(track.get("startSegmentId").is_none());
+    assert!(track.get("activeSegment").is_none());
+    assert!(track.get("activeSequenceNumber").is_none());
+    let track = &json["presentations"][0]["metadata"][0]["tracks"][0];
+    assert!(track.get("activeSegment").is_none());
+    let track = &json["presentations"][1]["video"][0]["tracks"][0];
+    assert!(track.get("activeSegment").is_none());
+    assert_eq!(track["activeSequenceNumber"], 7);
+    Manifest::from_json(location.clone(), &output)?;
+
+    let track = &mut data["presentations"][0]["video"][0]["tracks"][0];
+    track["startSegmentId"] = 1799.into();
+    track["startSequenceNumber"] = 42.into();
+    data["presentations"][0]["metadata"][0]["tracks"][0]["startSegmentId"] = 1799.into();
+    let manifest = Manifest::from_json(location, &data.to_string())?;
+    let output = manifest.to_json_version(ManifestVersion::V1_1_0)?;
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let track = &json["presentations"][0]["video"][0]["tracks"][0];
+    assert_eq!(track["activeSegment"], 1799);
+    assert_eq!(track["activeSequenceNumber"], 42);
+    let track = &json["presentations"][0]["metadata"][0]["tracks"][0];
+    assert_eq!(track["activeSegment"], 1799);
+    Ok(())
+}